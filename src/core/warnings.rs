@@ -0,0 +1,165 @@
+//! User-facing warning collection
+//! 
+//! Conditions that degrade a run without failing it (a capture left in
+//! blocking mode, missing kernel modules, a disabled metrics journal) are
+//! recorded here as well as logged, so a front end can show them to the user.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+/// Warnings kept before the oldest are discarded
+pub const MAX_WARNINGS: usize = 256;
+
+/// Area a warning concerns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningCategory {
+    /// Host platform: privileges, drivers, kernel modules
+    Platform,
+    
+    /// Wireless interface setup and state
+    Interface,
+    
+    /// Channel selection and tuning
+    Channel,
+    
+    /// Frame transmission
+    Injection,
+    
+    /// Engine configuration and optional features
+    Configuration,
+}
+
+impl fmt::Display for WarningCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Platform => "Platform",
+            Self::Interface => "Interface",
+            Self::Channel => "Channel",
+            Self::Injection => "Injection",
+            Self::Configuration => "Configuration",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A warning to surface to the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub category: WarningCategory,
+    pub message: String,
+    
+    /// When the warning was first recorded
+    pub timestamp: DateTime<Utc>,
+    
+    /// Times the warning was recorded back to back
+    pub occurrences: u32,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.category, self.message)?;
+        if self.occurrences > 1 {
+            write!(f, " (x{})", self.occurrences)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared, ordered warning accumulator
+///
+/// Clones share the same list, so a handle can be given to any component
+/// that should report to the user.
+#[derive(Debug, Clone, Default)]
+pub struct Warnings {
+    inner: Arc<Mutex<VecDeque<Warning>>>,
+}
+
+impl Warnings {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record a warning
+    ///
+    /// A warning identical to the previous one only bumps its occurrence count,
+    /// so a condition hit on every frame does not flood the list.
+    pub fn push(&self, category: WarningCategory, message: impl Into<String>) {
+        self.record(Warning {
+            category,
+            message: message.into(),
+            timestamp: Utc::now(),
+            occurrences: 1,
+        });
+    }
+    
+    /// Append warnings collected elsewhere, keeping their order
+    pub fn extend(&self, warnings: impl IntoIterator<Item = Warning>) {
+        for warning in warnings {
+            self.record(warning);
+        }
+    }
+    
+    fn record(&self, warning: Warning) {
+        let mut warnings = self.inner.lock();
+        
+        if let Some(last) = warnings.back_mut() {
+            if last.category == warning.category && last.message == warning.message {
+                last.occurrences = last.occurrences.saturating_add(warning.occurrences);
+                return;
+            }
+        }
+        
+        if warnings.len() == MAX_WARNINGS {
+            warnings.pop_front();
+        }
+        warnings.push_back(warning);
+    }
+    
+    /// Remove and return every pending warning, oldest first
+    pub fn take(&self) -> Vec<Warning> {
+        self.inner.lock().drain(..).collect()
+    }
+    
+    /// Number of pending warnings
+    pub fn len(&self) -> usize {
+        self.inner.lock().len()
+    }
+    
+    /// Check if no warnings are pending
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_warnings_coalesce_and_cap() {
+        let warnings = Warnings::new();
+        let reporter = warnings.clone();
+        
+        reporter.push(WarningCategory::Platform, "Wireless kernel modules not loaded");
+        reporter.push(WarningCategory::Injection, "Send buffer full");
+        reporter.push(WarningCategory::Injection, "Send buffer full");
+        assert_eq!(warnings.len(), 2);
+        
+        let taken = warnings.take();
+        assert_eq!(taken[0].category, WarningCategory::Platform);
+        assert_eq!(taken[1].occurrences, 2);
+        assert_eq!(taken[1].to_string(), "[Injection] Send buffer full (x2)");
+        assert!(warnings.is_empty());
+        
+        for i in 0..MAX_WARNINGS + 10 {
+            warnings.push(WarningCategory::Channel, format!("warning {}", i));
+        }
+        let taken = warnings.take();
+        assert_eq!(taken.len(), MAX_WARNINGS);
+        assert_eq!(taken[0].message, "warning 10");
+    }
+}
\ No newline at end of file
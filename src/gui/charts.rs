@@ -1,8 +1,10 @@
 //! Real-time charting and visualization
 
-use crate::core::Metrics;
+use crate::core::{metrics::MetricsJournal, Metrics};
+use crate::Result;
 use chrono::{DateTime, Utc};
 use std::collections::VecDeque;
+use std::path::Path;
 use tracing::debug;
 
 /// Chart data point
@@ -31,9 +33,20 @@ impl MetricsChart {
         }
     }
     
+    /// Rebuild a chart from a metrics journal, e.g. after a crash
+    pub fn from_journal(path: impl AsRef<Path>, max_points: usize) -> Result<Self> {
+        let mut chart = Self::new(max_points);
+        
+        for metrics in MetricsJournal::load(path)? {
+            chart.add_point(&metrics);
+        }
+        
+        Ok(chart)
+    }
+    
     /// Add a new metrics data point
     pub fn add_point(&mut self, metrics: &Metrics) {
-        let now = Utc::now();
+        let now = metrics.last_update;
         
         // Add packets per second
         self.packets_per_second.push_back(ChartPoint {
@@ -130,4 +143,35 @@ impl Default for ChartConfig {
             color: "#4CAF50".to_string(),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_chart_from_journal() {
+        let path = std::env::temp_dir().join(format!("wifi-deauther-journal-{}.jsonl", std::process::id()));
+        
+        let mut journal = MetricsJournal::create(&path).expect("Should create journal");
+        for pps in [10, 20, 30, 40, 50] {
+            let metrics = Metrics {
+                packets_per_second: pps,
+                success_rate: 0.5,
+                ..Metrics::default()
+            };
+            journal.append(&metrics).expect("Should append");
+        }
+        drop(journal);
+        
+        let chart = MetricsChart::from_journal(&path, 3).expect("Should load journal");
+        assert_eq!(chart.get_packets_per_second(), vec![(0.0, 30.0), (1.0, 40.0), (2.0, 50.0)]);
+        assert_eq!(chart.get_success_rate().len(), 3);
+        
+        // A new session truncates the journal
+        MetricsJournal::create(&path).expect("Should recreate journal");
+        assert!(MetricsJournal::load(&path).expect("Should load journal").is_empty());
+        
+        std::fs::remove_file(&path).ok();
+    }
 }
\ No newline at end of file
@@ -1,17 +1,26 @@
 //! PCAP export functionality
 
+use crate::core::DeauthEngine;
+use crate::network::capture::CaptureResult;
+use crate::network::pcap_file::{self, MAX_SNAP_LENGTH};
 use crate::{DeauthError, Result};
 use chrono::{DateTime, Utc};
-use pcap::{Capture, Savefile};
+use std::collections::VecDeque;
 use std::fs::File;
-use std::io::Write;
-use std::path::Path;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TrySendError};
 use tracing::{debug, info};
 
 /// PCAP file exporter
 pub struct PcapExporter {
     filename: String,
     start_time: DateTime<Utc>,
+    metadata_format: MetadataFormat,
+    snap_length: Option<usize>,
 }
 
 impl PcapExporter {
@@ -20,9 +29,26 @@ impl PcapExporter {
         Self {
             filename,
             start_time: Utc::now(),
+            metadata_format: MetadataFormat::default(),
+            snap_length: None,
         }
     }
     
+    /// Write metadata sidecars in `format`
+    pub fn with_metadata_format(mut self, format: MetadataFormat) -> Self {
+        self.metadata_format = format;
+        self
+    }
+    
+    /// Truncate exported packets to at most `snap_length` bytes
+    ///
+    /// Record headers keep each packet's original length, as pcap does for
+    /// live captures with a small snaplen.
+    pub fn with_snap_length(mut self, snap_length: Option<usize>) -> Self {
+        self.snap_length = snap_length;
+        self
+    }
+    
     /// Export captured packets to PCAP file
     pub fn export_packets(&self, packets: &[CapturedPacket]) -> Result<()> {
         info!("Exporting {} packets to {}", packets.len(), self.filename);
@@ -30,40 +56,94 @@ impl PcapExporter {
         let file = File::create(&self.filename)
             .map_err(|e| DeauthError::IoError(e))?;
         
-        let mut savefile = Savefile::new(file)
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to create savefile: {}", e)))?;
+        let mut writer = BufWriter::new(file);
+        self.write_packets(&mut writer, packets)?;
+        writer.flush()?;
+        
+        info!("Successfully exported {} packets to {}", packets.len(), self.filename);
+        Ok(())
+    }
+    
+    /// Write captured packets in classic pcap format
+    pub fn write_packets<W: Write>(&self, writer: &mut W, packets: &[CapturedPacket]) -> Result<()> {
+        let snap_length = self.snap_length
+            .map_or(MAX_SNAP_LENGTH, |length| u32::try_from(length).unwrap_or(MAX_SNAP_LENGTH));
+        
+        pcap_file::write_header(writer, snap_length)?;
         
         for packet in packets {
-            savefile.write(&packet.data, packet.timestamp);
+            let data = match self.snap_length {
+                Some(length) if packet.data.len() > length => &packet.data[..length],
+                _ => &packet.data[..],
+            };
+            let original_length = packet.original_length.max(packet.data.len());
+            pcap_file::write_record(writer, packet.timestamp, data, original_length)?;
         }
         
-        savefile.flush()
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to flush savefile: {}", e)))?;
-        
-        info!("Successfully exported {} packets to {}", packets.len(), self.filename);
         Ok(())
     }
     
-    /// Export metadata to JSON file
+    /// Export metadata to a JSON or YAML sidecar file
     pub fn export_metadata(&self, metadata: &ExportMetadata) -> Result<()> {
-        let json_filename = format!("{}.json", self.filename.trim_end_matches(".pcap"));
+        let metadata_filename = format!(
+            "{}.{}",
+            self.filename.trim_end_matches(".pcap"),
+            self.metadata_format.extension()
+        );
         
-        info!("Exporting metadata to {}", json_filename);
+        info!("Exporting metadata to {}", metadata_filename);
         
-        let json = serde_json::to_string_pretty(metadata)
-            .map_err(|e| DeauthError::ConfigError(format!("Failed to serialize metadata: {}", e)))?;
+        let contents = self.metadata_format.serialize(metadata)?;
         
-        let mut file = File::create(&json_filename)
+        let mut file = File::create(&metadata_filename)
             .map_err(|e| DeauthError::IoError(e))?;
         
-        file.write_all(json.as_bytes())
+        file.write_all(contents.as_bytes())
             .map_err(|e| DeauthError::IoError(e))?;
         
-        info!("Successfully exported metadata to {}", json_filename);
+        info!("Successfully exported metadata to {}", metadata_filename);
         Ok(())
     }
 }
 
+/// Serialization format for metadata sidecar files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetadataFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl MetadataFormat {
+    /// File extension for sidecars in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            MetadataFormat::Json => "json",
+            MetadataFormat::Yaml => "yaml",
+        }
+    }
+    
+    /// Serialize metadata in this format
+    pub fn serialize(self, metadata: &ExportMetadata) -> Result<String> {
+        match self {
+            MetadataFormat::Json => serde_json::to_string_pretty(metadata)
+                .map_err(|e| DeauthError::ConfigError(format!("Failed to serialize metadata: {}", e))),
+            MetadataFormat::Yaml => serde_yaml::to_string(metadata)
+                .map_err(|e| DeauthError::ConfigError(format!("Failed to serialize metadata: {}", e))),
+        }
+    }
+    
+    /// Parse metadata previously written in this format
+    pub fn parse(self, contents: &str) -> Result<ExportMetadata> {
+        match self {
+            MetadataFormat::Json => serde_json::from_str(contents)
+                .map_err(|e| DeauthError::ConfigError(format!("Failed to parse metadata: {}", e))),
+            MetadataFormat::Yaml => serde_yaml::from_str(contents)
+                .map_err(|e| DeauthError::ConfigError(format!("Failed to parse metadata: {}", e))),
+        }
+    }
+}
+
 /// Captured packet data
 #[derive(Debug, Clone)]
 pub struct CapturedPacket {
@@ -72,6 +152,64 @@ pub struct CapturedPacket {
     pub original_length: usize,
 }
 
+impl From<CaptureResult> for CapturedPacket {
+    fn from(capture: CaptureResult) -> Self {
+        Self {
+            timestamp: capture.timestamp,
+            original_length: capture.length,
+            data: capture.data,
+        }
+    }
+}
+
+/// Default number of packets queued between capture and export
+pub const DEFAULT_EXPORT_QUEUE: usize = 1024;
+
+/// Capture side of a bounded capture→export channel
+///
+/// Sending never blocks the capture loop: when the exporter falls behind and
+/// the queue is full, the packet is dropped and counted instead of buffered.
+#[derive(Debug, Clone)]
+pub struct ExportSender {
+    tx: mpsc::Sender<CapturedPacket>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl ExportSender {
+    /// Queue a packet for export, returning false if it was dropped
+    pub fn send(&self, packet: impl Into<CapturedPacket>) -> bool {
+        match self.tx.try_send(packet.into()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+            Err(TrySendError::Closed(_)) => {
+                debug!("Export channel closed, discarding packet");
+                false
+            }
+        }
+    }
+    
+    /// Packets dropped because the export queue was full
+    pub fn dropped_due_to_backpressure(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Create a bounded capture→export channel holding at most `capacity` packets
+///
+/// Feed the receiver to `ExportManager::receive_from`.
+pub fn export_channel(capacity: usize) -> (ExportSender, mpsc::Receiver<CapturedPacket>) {
+    let (tx, rx) = mpsc::channel(capacity);
+    let sender = ExportSender {
+        tx,
+        dropped: Arc::new(AtomicU64::new(0)),
+    };
+    
+    (sender, rx)
+}
+
 /// Export metadata
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ExportMetadata {
@@ -112,28 +250,70 @@ impl ExportMetadata {
     }
 }
 
+/// Timestamp format substituted for `{timestamp}` in export filenames
+const FILENAME_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
 /// Export configuration
 #[derive(Debug, Clone)]
 pub struct ExportConfig {
-    pub filename: String,
+    /// Directory exports are written to
+    pub output_dir: PathBuf,
+    
+    /// File name pattern, resolved when each export is written
+    ///
+    /// `{timestamp}`, `{interface}` and `{channel}` are replaced by the export
+    /// time (UTC, `YYYYMMDD_HHMMSS`) and the fields below; an unset interface
+    /// or channel renders as `unknown`.
+    pub filename_template: String,
+    
+    /// Interface the packets were captured on
+    pub interface: Option<String>,
+    
+    /// Channel the packets were captured on
+    pub channel: Option<u8>,
+    
     pub include_metadata: bool,
+    pub metadata_format: MetadataFormat,
     pub compress: bool,
     pub max_packets: Option<usize>,
     pub max_size: Option<usize>,
+    
+    /// Bytes kept per packet; `None` keeps whole frames
+    pub snap_length: Option<usize>,
 }
 
 impl Default for ExportConfig {
     fn default() -> Self {
         Self {
-            filename: format!("capture_{}.pcap", Utc::now().format("%Y%m%d_%H%M%S")),
+            output_dir: PathBuf::from("."),
+            filename_template: "capture_{timestamp}.pcap".to_string(),
+            interface: None,
+            channel: None,
             include_metadata: true,
+            metadata_format: MetadataFormat::Json,
             compress: false,
             max_packets: None,
             max_size: None,
+            snap_length: None,
         }
     }
 }
 
+impl ExportConfig {
+    /// Path an export written at `time` goes to
+    pub fn output_path(&self, time: DateTime<Utc>) -> PathBuf {
+        let interface = self.interface.as_deref().unwrap_or("unknown");
+        let channel = self.channel.map_or_else(|| "unknown".to_string(), |channel| channel.to_string());
+        
+        let filename = self.filename_template
+            .replace("{timestamp}", &time.format(FILENAME_TIMESTAMP_FORMAT).to_string())
+            .replace("{interface}", interface)
+            .replace("{channel}", &channel);
+        
+        self.output_dir.join(filename)
+    }
+}
+
 /// PCAP export manager
 pub struct ExportManager {
     config: ExportConfig,
@@ -154,7 +334,14 @@ impl ExportManager {
     }
     
     /// Add a packet to the export buffer
-    pub fn add_packet(&mut self, packet: CapturedPacket) {
+    ///
+    /// Packets longer than the configured snap length are truncated here,
+    /// keeping their `original_length`.
+    pub fn add_packet(&mut self, mut packet: CapturedPacket) {
+        if let Some(snap_length) = self.config.snap_length {
+            packet.data.truncate(snap_length);
+        }
+        
         self.total_bytes += packet.data.len();
         self.packets.push(packet);
         
@@ -177,9 +364,21 @@ impl ExportManager {
         }
     }
     
-    /// Export all buffered packets
-    pub fn export(&self) -> Result<()> {
-        let exporter = PcapExporter::new(self.config.filename.clone());
+    /// Buffer packets from a capture→export channel until every sender is dropped
+    pub async fn receive_from(&mut self, mut rx: mpsc::Receiver<CapturedPacket>) {
+        while let Some(packet) = rx.recv().await {
+            self.add_packet(packet);
+        }
+        
+        debug!("Export channel closed after {} packets", self.packets.len());
+    }
+    
+    /// Export all buffered packets, returning the path written
+    pub fn export(&self) -> Result<PathBuf> {
+        let path = self.config.output_path(Utc::now());
+        let exporter = PcapExporter::new(path.to_string_lossy().into_owned())
+            .with_metadata_format(self.config.metadata_format)
+            .with_snap_length(self.config.snap_length);
         
         // Export packets
         exporter.export_packets(&self.packets)?;
@@ -191,8 +390,8 @@ impl ExportManager {
                 Utc::now(),
                 self.packets.len(),
                 self.total_bytes,
-                "wlan0".to_string(), // TODO: Get actual interface
-                Some(6), // TODO: Get actual channel
+                self.config.interface.clone().unwrap_or_else(|| "unknown".to_string()),
+                self.config.channel,
                 None, // TODO: Get actual filter
                 "Wi-Fi Deauther capture".to_string(),
             );
@@ -200,7 +399,7 @@ impl ExportManager {
             exporter.export_metadata(&metadata)?;
         }
         
-        Ok(())
+        Ok(path)
     }
     
     /// Clear buffered packets
@@ -221,6 +420,119 @@ impl ExportManager {
     }
 }
 
+/// Write the frames `engine` injected since the last call to a pcap file
+///
+/// Only frames the engine sent are written, so the file documents exactly
+/// what a session put on the air. Requires
+/// `EngineConfig::record_injected_frames`; returns the number of frames
+/// written. Nothing is written if no frames were recorded.
+pub fn export_injected_frames(engine: &DeauthEngine, config: ExportConfig) -> Result<usize> {
+    let frames = engine.take_injected_frames();
+    if frames.is_empty() {
+        return Ok(0);
+    }
+    
+    let mut manager = ExportManager::new(config);
+    for frame in frames {
+        manager.add_packet(frame.into());
+    }
+    
+    manager.export()?;
+    Ok(manager.packet_count())
+}
+
+/// Retention window for a ring capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingWindow {
+    /// Keep packets captured within this duration of the newest packet
+    Time(Duration),
+    
+    /// Keep the newest packets whose combined size fits this byte budget
+    Bytes(usize),
+}
+
+/// Rolling capture buffer that retains only the most recent window of packets
+/// so the moments leading up to an event can be exported after the fact
+pub struct RingCapture {
+    window: RingWindow,
+    packets: VecDeque<CapturedPacket>,
+    total_bytes: usize,
+}
+
+impl RingCapture {
+    /// Create a new ring capture with the given retention window
+    pub fn new(window: RingWindow) -> Self {
+        Self {
+            window,
+            packets: VecDeque::new(),
+            total_bytes: 0,
+        }
+    }
+    
+    /// Add a packet, evicting the oldest packets that fall outside the window
+    pub fn add_packet(&mut self, packet: CapturedPacket) {
+        self.total_bytes += packet.data.len();
+        self.packets.push_back(packet);
+        self.evict();
+    }
+    
+    /// Drop packets from the front until the window constraint holds
+    fn evict(&mut self) {
+        match self.window {
+            RingWindow::Time(max_age) => {
+                let newest = match self.packets.back() {
+                    Some(packet) => packet.timestamp,
+                    None => return,
+                };
+                
+                while let Some(oldest) = self.packets.front() {
+                    let expired = newest
+                        .duration_since(oldest.timestamp)
+                        .map_or(false, |age| age > max_age);
+                    
+                    if !expired {
+                        break;
+                    }
+                    
+                    if let Some(removed) = self.packets.pop_front() {
+                        self.total_bytes -= removed.data.len();
+                    }
+                }
+            }
+            RingWindow::Bytes(max_bytes) => {
+                while self.total_bytes > max_bytes {
+                    match self.packets.pop_front() {
+                        Some(removed) => self.total_bytes -= removed.data.len(),
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Copy out the current window, oldest packet first
+    pub fn snapshot(&self) -> Vec<CapturedPacket> {
+        debug!("Taking ring capture snapshot of {} packets", self.packets.len());
+        self.packets.iter().cloned().collect()
+    }
+    
+    /// Clear the retained window
+    pub fn clear(&mut self) {
+        self.packets.clear();
+        self.total_bytes = 0;
+    }
+    
+    /// Get current packet count
+    pub fn packet_count(&self) -> usize {
+        self.packets.len()
+    }
+    
+    /// Get total bytes retained
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +556,63 @@ mod tests {
         assert_eq!(metadata.channel, Some(6));
     }
     
+    #[test]
+    fn test_metadata_yaml_round_trip() {
+        let metadata = ExportMetadata::new(
+            Utc::now(),
+            Utc::now(),
+            42,
+            4200,
+            "wlan0mon".to_string(),
+            Some(36),
+            Some("type mgt subtype deauth".to_string()),
+            "YAML capture".to_string(),
+        );
+        
+        let yaml = MetadataFormat::Yaml.serialize(&metadata).expect("Should serialize");
+        assert!(yaml.contains("interface: wlan0mon"));
+        
+        let parsed = MetadataFormat::Yaml.parse(&yaml).expect("Should parse");
+        assert_eq!(parsed.export_time, metadata.export_time);
+        assert_eq!(parsed.start_time, metadata.start_time);
+        assert_eq!(parsed.end_time, metadata.end_time);
+        assert_eq!(parsed.packet_count, 42);
+        assert_eq!(parsed.total_bytes, 4200);
+        assert_eq!(parsed.interface, "wlan0mon");
+        assert_eq!(parsed.channel, Some(36));
+        assert_eq!(parsed.filter.as_deref(), Some("type mgt subtype deauth"));
+        assert_eq!(parsed.description, "YAML capture");
+        
+        assert_eq!(ExportConfig::default().metadata_format, MetadataFormat::Json);
+    }
+    
+    #[test]
+    fn test_filename_template_resolved_at_export() {
+        let time = DateTime::parse_from_rfc3339("2024-03-09T14:05:07Z").unwrap().with_timezone(&Utc);
+        let config = ExportConfig {
+            output_dir: PathBuf::from("/var/captures"),
+            filename_template: "{interface}_ch{channel}_{timestamp}.pcap".to_string(),
+            interface: Some("wlan0mon".to_string()),
+            channel: Some(36),
+            ..ExportConfig::default()
+        };
+        
+        assert_eq!(config.output_path(time), PathBuf::from("/var/captures/wlan0mon_ch36_20240309_140507.pcap"));
+        
+        // Later exports get their own name; unknown fields are spelled out
+        let later = time + chrono::Duration::seconds(1);
+        let unknown = ExportConfig {
+            interface: None,
+            channel: None,
+            ..config
+        };
+        assert_eq!(unknown.output_path(later), PathBuf::from("/var/captures/unknown_chunknown_20240309_140508.pcap"));
+        assert_eq!(
+            ExportConfig::default().output_path(time),
+            PathBuf::from(".").join("capture_20240309_140507.pcap")
+        );
+    }
+    
     #[test]
     fn test_export_manager() {
         let config = ExportConfig::default();
@@ -260,4 +629,166 @@ mod tests {
         assert_eq!(manager.packet_count(), 1);
         assert_eq!(manager.total_bytes(), 4);
     }
+    
+    #[test]
+    fn test_snap_length_truncation() {
+        let config = ExportConfig {
+            snap_length: Some(32),
+            ..ExportConfig::default()
+        };
+        let mut manager = ExportManager::new(config);
+        manager.add_packet(CapturedPacket {
+            timestamp: std::time::UNIX_EPOCH + Duration::from_millis(1_500),
+            data: vec![0xAB; 100],
+            original_length: 100,
+        });
+        manager.add_packet(CapturedPacket {
+            timestamp: std::time::UNIX_EPOCH,
+            data: vec![0xCD; 20],
+            original_length: 20,
+        });
+        assert_eq!(manager.total_bytes(), 32 + 20);
+        
+        let mut pcap = Vec::new();
+        PcapExporter::new("unused.pcap".to_string())
+            .with_snap_length(Some(32))
+            .write_packets(&mut pcap, &manager.packets)
+            .expect("Should write pcap");
+        
+        let u32_at = |offset: usize| u32::from_le_bytes(pcap[offset..offset + 4].try_into().unwrap());
+        assert_eq!(u32_at(16), 32);
+        assert_eq!(u32_at(20), pcap_file::LINKTYPE_IEEE802_11_RADIOTAP);
+        
+        // First record: truncated to the snap length, original length preserved
+        assert_eq!((u32_at(24), u32_at(28)), (1, 500_000));
+        assert_eq!(u32_at(32), 32);
+        assert_eq!(u32_at(36), 100);
+        assert_eq!(&pcap[40..72], &[0xAB; 32][..]);
+        
+        // Second record fits and is stored whole
+        assert_eq!(u32_at(80), 20);
+        assert_eq!(u32_at(84), 20);
+        assert_eq!(pcap.len(), 88 + 20);
+    }
+    
+    #[tokio::test]
+    async fn test_export_channel_backpressure() {
+        let (sender, rx) = export_channel(4);
+        let mut manager = ExportManager::new(ExportConfig::default());
+        let consumer = tokio::spawn(async move {
+            manager.receive_from(rx).await;
+            manager
+        });
+        
+        // The consumer only runs once the producer yields, so the queue fills up
+        let sent = (0..100)
+            .filter(|_| {
+                sender.send(CaptureResult {
+                    timestamp: std::time::SystemTime::now(),
+                    data: vec![0xAA; 64],
+                    length: 64,
+                })
+            })
+            .count();
+        
+        assert_eq!(sent, 4);
+        assert_eq!(sender.dropped_due_to_backpressure(), 96);
+        
+        drop(sender);
+        let manager = consumer.await.expect("Consumer should finish");
+        assert_eq!(manager.packet_count(), 4);
+        assert_eq!(manager.total_bytes(), 4 * 64);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_export_injected_frames_only() {
+        use crate::core::{AttackProfile, AttackTarget, EngineConfig};
+        use mac_address::MacAddress;
+        
+        let config = EngineConfig {
+            worker_threads: 2,
+            record_injected_frames: Some(1000),
+            ..EngineConfig::default()
+        };
+        let mut engine = DeauthEngine::new(config).expect("Should create engine");
+        engine.start().expect("Should start");
+        
+        let profile = AttackProfile {
+            targets: vec![AttackTarget {
+                target: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+                access_point: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            }],
+            frames_per_target: 25,
+            interval: Duration::ZERO,
+            ..AttackProfile::default()
+        };
+        let summary = engine.run_profile(profile)
+            .expect("Should run profile")
+            .wait()
+            .await
+            .expect("Should finish");
+        let mut updates = engine.subscribe_metrics();
+        
+        let path = std::env::temp_dir().join(format!("wifi-deauther-injected-{}.pcap", std::process::id()));
+        let config = ExportConfig {
+            output_dir: std::env::temp_dir(),
+            filename_template: format!("wifi-deauther-injected-{}.pcap", std::process::id()),
+            include_metadata: false,
+            ..ExportConfig::default()
+        };
+        let written = export_injected_frames(&engine, config).expect("Should export");
+        let pcap = std::fs::read(&path).expect("Should read pcap");
+        std::fs::remove_file(&path).ok();
+        
+        // Walk the records after the 24-byte global header
+        let mut offset = 24;
+        let mut records = 0;
+        while offset < pcap.len() {
+            let captured = u32::from_le_bytes(pcap[offset + 8..offset + 12].try_into().unwrap()) as usize;
+            assert_eq!(pcap[offset + 16 + 8], 0xC0, "Every record should be a deauthentication");
+            offset += 16 + captured;
+            records += 1;
+        }
+        
+        let metrics = updates.recv().await.expect("Should publish metrics").metrics;
+        assert_eq!(metrics.packets_injected, 25);
+        assert_eq!(summary.successful_packets, 25);
+        assert_eq!(records as u64, metrics.packets_injected);
+        assert_eq!(written, records);
+        
+        // The log was drained by the export
+        assert!(engine.take_injected_frames().is_empty());
+    }
+    
+    #[test]
+    fn test_ring_capture_windows() {
+        let base = std::time::SystemTime::now();
+        let packet_at = |secs: u64, len: usize| CapturedPacket {
+            timestamp: base + Duration::from_secs(secs),
+            data: vec![0xAA; len],
+            original_length: len,
+        };
+        
+        // Time window keeps only packets within 5 seconds of the newest one
+        let mut by_time = RingCapture::new(RingWindow::Time(Duration::from_secs(5)));
+        for secs in [0, 2, 4, 6, 8, 10] {
+            by_time.add_packet(packet_at(secs, 10));
+        }
+        
+        let window = by_time.snapshot();
+        assert_eq!(window.len(), 3);
+        assert_eq!(window[0].timestamp, base + Duration::from_secs(6));
+        assert_eq!(by_time.total_bytes(), 30);
+        
+        // Byte budget keeps the newest packets that fit
+        let mut by_bytes = RingCapture::new(RingWindow::Bytes(100));
+        for (secs, len) in [(0, 40), (1, 40), (2, 40), (3, 30)] {
+            by_bytes.add_packet(packet_at(secs, len));
+        }
+        
+        let window = by_bytes.snapshot();
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0].timestamp, base + Duration::from_secs(2));
+        assert_eq!(by_bytes.total_bytes(), 70);
+    }
 }
\ No newline at end of file
@@ -7,5 +7,8 @@ pub mod app;
 pub mod targets;
 pub mod charts;
 pub mod export;
+pub mod scan;
+pub mod session;
+pub mod signal;
 
-pub use app::DeauthApp;
\ No newline at end of file
+pub use app::{AppMode, DeauthApp};
\ No newline at end of file
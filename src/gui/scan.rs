@@ -0,0 +1,299 @@
+//! Multi-interface scan coordination
+//! 
+//! A `ScanCoordinator` drives one channel hopper and capture source per
+//! interface, e.g. one radio hopping 2.4 GHz while another hops 5 GHz, and
+//! merges the access points they see into a shared `TargetManager`.
+//! Newly seen access points are also announced as `Event::TargetDiscovered`
+//! as they are found, so a front end can list them before the scan completes.
+
+use super::targets::{Target, TargetManager};
+use crate::core::event::Event;
+use crate::network::capture::CaptureSource;
+use crate::network::channel::{ChannelHopper, WiFiBand};
+use crate::{DeauthError, Result};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// Scan events buffered for each subscriber before the oldest are dropped
+const SCAN_EVENT_CAPACITY: usize = 64;
+
+/// One scanning interface
+struct ScanRadio {
+    interface: String,
+    hopper: ChannelHopper,
+    source: Box<dyn CaptureSource>,
+}
+
+/// Coordinates simultaneous scans on several interfaces
+pub struct ScanCoordinator {
+    radios: Vec<ScanRadio>,
+    targets: Arc<Mutex<TargetManager>>,
+    events_tx: broadcast::Sender<Event>,
+}
+
+impl ScanCoordinator {
+    /// Create a coordinator feeding `targets`
+    pub fn new(targets: Arc<Mutex<TargetManager>>) -> Self {
+        let (events_tx, _) = broadcast::channel(SCAN_EVENT_CAPACITY);
+        
+        Self {
+            radios: Vec::new(),
+            targets,
+            events_tx,
+        }
+    }
+    
+    /// Scan `band` on `interface`, dwelling `dwell` on each channel
+    pub fn add_interface(&mut self, interface: &str, band: WiFiBand, dwell: Duration, source: Box<dyn CaptureSource>) {
+        info!("Scanning {:?} on {}", band, interface);
+        self.radios.push(ScanRadio {
+            interface: interface.to_string(),
+            hopper: ChannelHopper::new(band, dwell),
+            source,
+        });
+    }
+    
+    /// Scan `channels` on `interface`, dwelling `dwell` on each channel
+    ///
+    /// Suits an interface's own list of supported channels, which may span
+    /// bands. With no usable channels the radio listens where it is tuned.
+    pub fn add_interface_channels(&mut self, interface: &str, channels: &[u8], dwell: Duration, source: Box<dyn CaptureSource>) {
+        info!("Scanning channels {:?} on {}", channels, interface);
+        self.radios.push(ScanRadio {
+            interface: interface.to_string(),
+            hopper: ChannelHopper::with_channels(channels, dwell),
+            source,
+        });
+    }
+    
+    /// Shared target manager the scan feeds
+    pub fn targets(&self) -> Arc<Mutex<TargetManager>> {
+        Arc::clone(&self.targets)
+    }
+    
+    /// Subscribe to targets as they are discovered
+    ///
+    /// Each access point is announced once, as `Event::TargetDiscovered`
+    /// with its BSSID; its details are in the target manager. The channel
+    /// closes once the coordinator is dropped.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.events_tx.subscribe()
+    }
+    
+    /// Scan on every interface in parallel for `duration`
+    ///
+    /// Each radio calls `tune(interface, channel)` before dwelling on a
+    /// channel. If tuning fails, e.g. without `iw` or privileges, the radio
+    /// spends that dwell listening on whatever channel it is on. Returns the
+    /// number of beacons merged into the target manager.
+    pub fn run<F>(&mut self, duration: Duration, tune: F) -> Result<u64>
+    where
+        F: Fn(&str, u8) -> Result<()> + Sync,
+    {
+        if self.radios.is_empty() {
+            return Err(DeauthError::ConfigError("No scan interfaces added".to_string()));
+        }
+        
+        let deadline = Instant::now() + duration;
+        let targets = &self.targets;
+        let events_tx = &self.events_tx;
+        let tune = &tune;
+        
+        thread::scope(|scope| {
+            let scans: Vec<_> = self
+                .radios
+                .iter_mut()
+                .map(|radio| scope.spawn(move || scan_radio(radio, targets, events_tx, tune, deadline)))
+                .collect();
+            
+            scans.into_iter().try_fold(0, |total, scan| {
+                let beacons = scan
+                    .join()
+                    .map_err(|_| DeauthError::InterfaceError("Scan thread panicked".to_string()))??;
+                Ok(total + beacons)
+            })
+        })
+    }
+}
+
+/// Hop `radio` across its channels until `deadline`, merging beacons into `targets`
+fn scan_radio<F>(
+    radio: &mut ScanRadio,
+    targets: &Mutex<TargetManager>,
+    events_tx: &broadcast::Sender<Event>,
+    tune: &F,
+    deadline: Instant,
+) -> Result<u64>
+where
+    F: Fn(&str, u8) -> Result<()>,
+{
+    let mut beacons = 0;
+    
+    while Instant::now() < deadline {
+        // The channel the radio is known to be on, if it could be tuned
+        let (channel, dwell_end) = match radio.hopper.next_channel().map(|channel| channel.number) {
+            Some(channel) => {
+                let dwell_end = (Instant::now() + radio.hopper.dwell_time()).min(deadline);
+                match tune(&radio.interface, channel) {
+                    Ok(()) => (Some(channel), dwell_end),
+                    Err(e) => {
+                        warn!("Cannot tune {} to channel {}, listening on its current channel: {}", radio.interface, channel, e);
+                        (None, dwell_end)
+                    }
+                }
+            }
+            None => {
+                warn!("No channels to hop on {}, listening on its current channel", radio.interface);
+                (None, deadline)
+            }
+        };
+        
+        while Instant::now() < dwell_end {
+            let Some(capture) = radio.source.capture_packet()? else {
+                thread::yield_now();
+                continue;
+            };
+            
+            if let Some(mut target) = Target::from_beacon(&capture) {
+                // Beacons without a DS Parameter Set were heard on the tuned channel
+                if target.channel == 0 {
+                    target.channel = channel.unwrap_or(0);
+                }
+                
+                if let Some(target) = merge(&mut targets.lock(), target) {
+                    // No subscribers is not an error
+                    let _ = events_tx.send(Event::TargetDiscovered { target: target.mac_address });
+                }
+                beacons += 1;
+            }
+        }
+    }
+    
+    debug!("Scan on {} merged {} beacons", radio.interface, beacons);
+    Ok(beacons)
+}
+
+/// Add a newly seen access point, or refresh one already known
+///
+/// Returns the target if it was newly added.
+fn merge(manager: &mut TargetManager, target: Target) -> Option<Target> {
+    if manager.get_target(&target.mac_address).is_some() {
+        manager.update_signal(target.mac_address, target.signal_strength);
+        None
+    } else {
+        manager.add_target(target.clone());
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::packet::RADIOTAP_HEADER;
+    use crate::network::injection::Injector;
+    use crate::network::LoopbackChannel;
+    use mac_address::MacAddress;
+    
+    /// Radiotap beacon from `bssid` announcing `channel`
+    fn beacon(bssid: [u8; 6], ssid: &[u8], channel: u8) -> Vec<u8> {
+        let mut frame = RADIOTAP_HEADER.to_vec();
+        frame.extend_from_slice(&[0x80, 0x00, 0x00, 0x00]);
+        frame.extend_from_slice(&[0xFF; 6]);
+        frame.extend_from_slice(&bssid);
+        frame.extend_from_slice(&bssid);
+        frame.extend_from_slice(&[0x00, 0x00]);
+        frame.extend_from_slice(&[0x00; 8]);
+        frame.extend_from_slice(&100u16.to_le_bytes());
+        frame.extend_from_slice(&0x0001u16.to_le_bytes());
+        frame.extend_from_slice(&[0x00, ssid.len() as u8]);
+        frame.extend_from_slice(ssid);
+        frame.extend_from_slice(&[0x03, 0x01, channel]);
+        frame
+    }
+    
+    #[test]
+    fn test_two_band_scan_merges_targets() {
+        let low = LoopbackChannel::new();
+        low.inject_frame(&beacon([0x00, 0x11, 0x22, 0x00, 0x00, 0x01], b"lobby", 6)).unwrap();
+        low.inject_frame(&beacon([0x00, 0x11, 0x22, 0x00, 0x00, 0x01], b"lobby", 6)).unwrap();
+        let high = LoopbackChannel::new();
+        high.inject_frame(&beacon([0x00, 0x11, 0x22, 0x00, 0x00, 0x02], b"office", 36)).unwrap();
+        
+        let manager = Arc::new(Mutex::new(TargetManager::new()));
+        let mut coordinator = ScanCoordinator::new(Arc::clone(&manager));
+        let mut events = coordinator.subscribe();
+        coordinator.add_interface("wlan0", WiFiBand::TwoPointFourGHz, Duration::from_millis(5), Box::new(low));
+        coordinator.add_interface("wlan1", WiFiBand::FiveGHz, Duration::from_millis(5), Box::new(high));
+        
+        let tuned = Mutex::new(Vec::new());
+        let beacons = coordinator
+            .run(Duration::from_millis(50), |interface, channel| {
+                tuned.lock().push((interface.to_string(), channel));
+                Ok(())
+            })
+            .expect("Should scan");
+        assert_eq!(beacons, 3);
+        
+        // Each radio only tuned to channels of its own band
+        let tuned = tuned.into_inner();
+        assert!(tuned.iter().any(|(interface, channel)| interface == "wlan0" && *channel == 1));
+        assert!(tuned.iter().any(|(interface, channel)| interface == "wlan1" && *channel == 36));
+        assert!(tuned
+            .iter()
+            .all(|(interface, channel)| (interface == "wlan0") == (*channel <= 14)));
+        
+        let manager = coordinator.targets();
+        let manager = manager.lock();
+        assert_eq!(manager.get_targets().len(), 2);
+        let lobby = manager.get_target(&MacAddress::new([0x00, 0x11, 0x22, 0x00, 0x00, 0x01])).expect("Should see 2.4 GHz AP");
+        assert_eq!((lobby.ssid.as_str(), lobby.channel), ("lobby", 6));
+        let office = manager.get_target(&MacAddress::new([0x00, 0x11, 0x22, 0x00, 0x00, 0x02])).expect("Should see 5 GHz AP");
+        assert_eq!((office.ssid.as_str(), office.channel), ("office", 36));
+        assert_eq!(manager.targets_in_band(WiFiBand::FiveGHz).len(), 1);
+        
+        // One event per access point, not per beacon
+        let mut discovered = Vec::new();
+        while let Ok(Event::TargetDiscovered { target }) = events.try_recv() {
+            discovered.push(target);
+        }
+        discovered.sort();
+        assert_eq!(
+            discovered,
+            [MacAddress::new([0x00, 0x11, 0x22, 0x00, 0x00, 0x01]), MacAddress::new([0x00, 0x11, 0x22, 0x00, 0x00, 0x02])]
+        );
+    }
+    
+    #[test]
+    fn test_failed_tune_listens_on_current_channel() {
+        let source = LoopbackChannel::new();
+        source.inject_frame(&beacon([0x00, 0x11, 0x22, 0x00, 0x00, 0x03], b"cafe", 11)).unwrap();
+        
+        let manager = Arc::new(Mutex::new(TargetManager::new()));
+        let mut coordinator = ScanCoordinator::new(Arc::clone(&manager));
+        coordinator.add_interface_channels("wlan0", &[1, 6, 11, 36, 0], Duration::from_millis(20), Box::new(source));
+        
+        // Both bands of the supported list are hopped; the invalid channel 0 is dropped
+        let hopped: Vec<u8> = coordinator.radios[0].hopper.channels().iter().map(|channel| channel.number).collect();
+        assert_eq!(hopped, [1, 6, 11, 36]);
+        
+        // Every tune fails, yet the scan still listens instead of spinning through channels
+        let attempts = Mutex::new(Vec::new());
+        let beacons = coordinator
+            .run(Duration::from_millis(50), |_, channel| {
+                attempts.lock().push(channel);
+                Err(DeauthError::PermissionError("iw needs root".to_string()))
+            })
+            .expect("Should scan");
+        assert_eq!(beacons, 1);
+        assert_eq!(manager.lock().get_targets().len(), 1);
+        
+        // One attempt per dwell at most
+        let attempts = attempts.into_inner();
+        assert!((1..=3).contains(&attempts.len()), "Tuned {} times", attempts.len());
+        assert_eq!(attempts[0], 1);
+    }
+}
\ No newline at end of file
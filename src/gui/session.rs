@@ -0,0 +1,179 @@
+//! Passive capture sessions
+//! 
+//! A `CaptureSession` records traffic for a fixed time, optionally hopping
+//! channels, and writes it to a pcap file. Nothing is injected, so a session
+//! can run without an engine.
+
+use super::export::{ExportConfig, ExportManager};
+use crate::network::capture::{CaptureSource, PacketCapture};
+use crate::network::channel::ChannelHopper;
+use crate::Result;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Tunes an interface to a channel
+pub type ChannelTuner = Box<dyn FnMut(&str, u8) -> Result<()> + Send>;
+
+/// Timed, capture-only recording to a pcap file
+pub struct CaptureSession {
+    interface: String,
+    source: Box<dyn CaptureSource>,
+    hopping: Option<(ChannelHopper, ChannelTuner)>,
+}
+
+impl CaptureSession {
+    /// Capture on `interface` for `duration` and export to `export_config`
+    ///
+    /// Stays on the interface's current channel. Returns the pcap path.
+    pub fn run(interface: &str, duration: Duration, export_config: ExportConfig) -> Result<PathBuf> {
+        let source = PacketCapture::new_for_scanning(interface)?;
+        Self::new(interface, Box::new(source)).capture(duration, export_config)
+    }
+    
+    /// Create a session recording from `source`, captured on `interface`
+    pub fn new(interface: &str, source: Box<dyn CaptureSource>) -> Self {
+        Self {
+            interface: interface.to_string(),
+            source,
+            hopping: None,
+        }
+    }
+    
+    /// Hop channels with `hopper`, calling `tune(interface, channel)` on each
+    ///
+    /// If a tune fails, that dwell is spent capturing on whatever channel the
+    /// interface is on.
+    pub fn with_channel_hopping(mut self, hopper: ChannelHopper, tune: ChannelTuner) -> Self {
+        self.hopping = Some((hopper, tune));
+        self
+    }
+    
+    /// Record for `duration`, then write everything captured to a pcap file
+    ///
+    /// The export's interface defaults to this session's. Returns the pcap path.
+    pub fn capture(mut self, duration: Duration, mut export_config: ExportConfig) -> Result<PathBuf> {
+        export_config.interface.get_or_insert_with(|| self.interface.clone());
+        info!("Capturing on {} for {:?}", self.interface, duration);
+        
+        let mut manager = ExportManager::new(export_config);
+        let deadline = Instant::now() + duration;
+        
+        while Instant::now() < deadline {
+            let dwell_end = match self.hopping {
+                Some((ref mut hopper, ref mut tune)) => match hopper.next_channel().map(|channel| channel.number) {
+                    Some(channel) => {
+                        if let Err(e) = tune(&self.interface, channel) {
+                            warn!("Cannot tune {} to channel {}, capturing on its current channel: {}", self.interface, channel, e);
+                        }
+                        (Instant::now() + hopper.dwell_time()).min(deadline)
+                    }
+                    None => deadline,
+                },
+                None => deadline,
+            };
+            
+            while Instant::now() < dwell_end {
+                match self.source.capture_packet()? {
+                    Some(capture) => manager.add_packet(capture.into()),
+                    None => thread::yield_now(),
+                }
+            }
+        }
+        
+        info!("Captured {} packets on {}", manager.packet_count(), self.interface);
+        manager.export()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::packet::{DeauthPacket, MacAddress};
+    use crate::gui::export::{ExportMetadata, MetadataFormat};
+    use crate::network::channel::WiFiBand;
+    use crate::network::injection::Injector;
+    use crate::network::LoopbackChannel;
+    use std::sync::{Arc, Mutex};
+    
+    #[test]
+    fn test_capture_session_writes_window() {
+        let loopback = LoopbackChannel::new();
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let frame = DeauthPacket::new(client, ap, ap, 7).to_bytes();
+        for _ in 0..3 {
+            loopback.inject_frame(&frame).unwrap();
+        }
+        
+        let tuned = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&tuned);
+        let session = CaptureSession::new("wlan0mon", Box::new(loopback)).with_channel_hopping(
+            ChannelHopper::new(WiFiBand::TwoPointFourGHz, Duration::from_millis(10)),
+            Box::new(move |_, channel| {
+                recorder.lock().unwrap().push(channel);
+                Ok(())
+            }),
+        );
+        
+        let config = ExportConfig {
+            output_dir: std::env::temp_dir(),
+            filename_template: format!("wifi-deauther-session-{}.pcap", std::process::id()),
+            metadata_format: MetadataFormat::Json,
+            ..ExportConfig::default()
+        };
+        let started = chrono::Utc::now();
+        let path = session.capture(Duration::from_millis(50), config).expect("Should capture and export");
+        
+        // Global header plus a record header and frame per packet
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(written.len(), 24 + 3 * (16 + frame.len()));
+        assert!(tuned.lock().unwrap().contains(&1));
+        
+        let sidecar = path.with_extension("json");
+        let metadata: ExportMetadata = serde_json::from_str(&std::fs::read_to_string(&sidecar).unwrap()).unwrap();
+        assert_eq!(metadata.packet_count, 3);
+        assert_eq!(metadata.interface, "wlan0mon");
+        assert!(metadata.start_time >= started);
+        assert!(metadata.end_time - metadata.start_time >= chrono::Duration::milliseconds(50));
+        
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&sidecar).unwrap();
+    }
+    
+    #[test]
+    fn test_failed_tune_keeps_capturing() {
+        let loopback = LoopbackChannel::new();
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let frame = DeauthPacket::new(client, ap, ap, 7).to_bytes();
+        loopback.inject_frame(&frame).unwrap();
+        
+        let attempts = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&attempts);
+        let session = CaptureSession::new("wlan0mon", Box::new(loopback)).with_channel_hopping(
+            ChannelHopper::new(WiFiBand::TwoPointFourGHz, Duration::from_millis(20)),
+            Box::new(move |_, _| {
+                *counter.lock().unwrap() += 1;
+                Err(crate::DeauthError::PermissionError("iw needs root".to_string()))
+            }),
+        );
+        
+        let config = ExportConfig {
+            output_dir: std::env::temp_dir(),
+            filename_template: format!("wifi-deauther-session-untuned-{}.pcap", std::process::id()),
+            metadata_format: MetadataFormat::Json,
+            ..ExportConfig::default()
+        };
+        let path = session.capture(Duration::from_millis(50), config).expect("Should capture and export");
+        
+        // The frame is still captured, with one tune attempt per dwell rather than a busy loop
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(written.len(), 24 + 16 + frame.len());
+        assert!((1..=3).contains(&*attempts.lock().unwrap()));
+        
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(path.with_extension("json")).unwrap();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,253 @@
+//! Live signal strength of a target access point
+//! 
+//! While attacking, the signal worth showing is the access point's as heard on
+//! the capture stream, not the interface's own link quality. A `SignalPoller`
+//! follows the frames its BSSIDs transmit, smooths each one's radiotap signal
+//! over recent frames and keeps their entries in a `TargetManager` current.
+
+use super::targets::TargetManager;
+use crate::core::packet::{self, MacAddress};
+use crate::network::capture::{CaptureResult, CaptureSource};
+use crate::Result;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Frames averaged by default
+pub const DEFAULT_SIGNAL_WINDOW: usize = 8;
+
+/// Frames read per poll, so a busy channel cannot keep a poll from returning
+const POLL_BATCH: usize = 256;
+
+/// Wait between polls on the background thread
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Moving average of the signal one BSSID is heard at
+#[derive(Debug, Clone)]
+pub struct SignalTracker {
+    bssid: MacAddress,
+    samples: VecDeque<i8>,
+    window: usize,
+}
+
+impl SignalTracker {
+    /// Average the last `window` frames transmitted by `bssid`
+    pub fn new(bssid: MacAddress, window: usize) -> Self {
+        let window = window.max(1);
+        
+        Self {
+            bssid,
+            samples: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+    
+    /// BSSID whose signal is tracked
+    pub fn bssid(&self) -> MacAddress {
+        self.bssid
+    }
+    
+    /// Take `capture` into account if the tracked BSSID transmitted it
+    ///
+    /// Returns the new smoothed signal, or `None` if the frame came from
+    /// another station or its radiotap header reports no signal.
+    pub fn process_capture(&mut self, capture: &CaptureResult) -> Option<i8> {
+        let frame = packet::strip_radiotap(&capture.data)?;
+        
+        // Control frames such as ACK and CTS carry no transmitter address
+        if frame.first()? & 0x0C == 0x04 {
+            return None;
+        }
+        
+        let transmitter: [u8; 6] = frame.get(10..16)?.try_into().ok()?;
+        if MacAddress::new(transmitter) != self.bssid {
+            return None;
+        }
+        
+        let signal = packet::radiotap_signal(&capture.data)?;
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(signal);
+        self.smoothed()
+    }
+    
+    /// Average of the recent samples, rounded to the nearest dBm
+    pub fn smoothed(&self) -> Option<i8> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        
+        let sum: i32 = self.samples.iter().map(|&signal| i32::from(signal)).sum();
+        let len = i32::try_from(self.samples.len()).ok()?;
+        i8::try_from((2 * sum + len).div_euclid(2 * len)).ok()
+    }
+}
+
+/// Keeps targets' signal in a `TargetManager` current from a capture stream
+pub struct SignalPoller {
+    source: Box<dyn CaptureSource>,
+    trackers: Vec<SignalTracker>,
+    targets: Arc<Mutex<TargetManager>>,
+}
+
+impl SignalPoller {
+    /// Follow `bssid` on `source`, averaging `DEFAULT_SIGNAL_WINDOW` frames
+    pub fn new(bssid: MacAddress, source: Box<dyn CaptureSource>, targets: Arc<Mutex<TargetManager>>) -> Self {
+        Self::for_bssids(&[bssid], source, targets)
+    }
+    
+    /// Follow each of `bssids` on the one `source`
+    ///
+    /// Pair with a capture restricted to the BSSIDs, such as
+    /// `PacketCapture::new_for_transmitters`.
+    pub fn for_bssids(bssids: &[MacAddress], source: Box<dyn CaptureSource>, targets: Arc<Mutex<TargetManager>>) -> Self {
+        Self {
+            source,
+            trackers: bssids.iter().map(|&bssid| SignalTracker::new(bssid, DEFAULT_SIGNAL_WINDOW)).collect(),
+            targets,
+        }
+    }
+    
+    /// Average the last `window` frames instead
+    pub fn with_window(mut self, window: usize) -> Self {
+        for tracker in &mut self.trackers {
+            *tracker = SignalTracker::new(tracker.bssid(), window);
+        }
+        self
+    }
+    
+    /// Smoothed signal of `bssid`, or `None` until a frame from it with a
+    /// signal reading has been seen
+    pub fn signal(&self, bssid: MacAddress) -> Option<i8> {
+        self.trackers.iter().find(|tracker| tracker.bssid() == bssid)?.smoothed()
+    }
+    
+    /// Read the frames the source has ready and update the targets' signal
+    pub fn poll(&mut self) -> Result<()> {
+        let mut updated = vec![None; self.trackers.len()];
+        for _ in 0..POLL_BATCH {
+            let Some(capture) = self.source.capture_packet()? else {
+                break;
+            };
+            
+            for (tracker, updated) in self.trackers.iter_mut().zip(&mut updated) {
+                if let Some(signal) = tracker.process_capture(&capture) {
+                    *updated = Some(signal);
+                }
+            }
+        }
+        
+        let updates: Vec<(MacAddress, i8)> = self.trackers
+            .iter()
+            .zip(updated)
+            .filter_map(|(tracker, signal)| Some((tracker.bssid(), signal?)))
+            .collect();
+        if !updates.is_empty() {
+            let mut targets = self.targets.lock();
+            for (bssid, signal) in updates {
+                targets.update_signal(bssid, signal);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Poll on a background thread until `stop` is set
+    pub fn spawn(mut self, stop: Arc<AtomicBool>) -> thread::JoinHandle<Result<()>> {
+        thread::spawn(move || {
+            info!("Following signal of {} access point(s)", self.trackers.len());
+            
+            while !stop.load(Ordering::Relaxed) {
+                self.poll()?;
+                thread::sleep(POLL_INTERVAL);
+            }
+            
+            debug!("Stopped following signal of {} access point(s)", self.trackers.len());
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::packet::DeauthPacket;
+    use crate::gui::targets::{EncryptionType, Target};
+    use crate::network::injection::Injector;
+    use crate::network::LoopbackChannel;
+    
+    /// Capture side of a loopback the test keeps injecting into
+    struct SharedLoopback(Arc<LoopbackChannel>);
+    
+    impl CaptureSource for SharedLoopback {
+        fn capture_packet(&self) -> Result<Option<CaptureResult>> {
+            self.0.capture_packet()
+        }
+    }
+    
+    /// Frame sent by `source` with a radiotap header reporting `signal`
+    fn frame_at(source: MacAddress, signal: i8) -> Vec<u8> {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let frame = DeauthPacket::new(client, source, source, 7).to_bytes();
+        
+        // Antenna signal only
+        let radiotap = [0x00, 0x00, 0x09, 0x00, 0x20, 0x00, 0x00, 0x00, signal.to_le_bytes()[0]];
+        [&radiotap[..], &frame[packet::RADIOTAP_HEADER.len()..]].concat()
+    }
+    
+    #[test]
+    fn test_signal_poller_smooths_updates() {
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let other = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x66]);
+        
+        let mut manager = TargetManager::new();
+        manager.add_target(Target {
+            mac_address: ap,
+            ssid: "target".to_string(),
+            channel: 6,
+            signal_strength: -80,
+            encryption: EncryptionType::WPA2,
+            vendor: None,
+            last_seen: std::time::SystemTime::UNIX_EPOCH,
+        });
+        let targets = Arc::new(Mutex::new(manager));
+        let signal = || targets.lock().get_target(&ap).expect("Target should remain").signal_strength;
+        
+        let loopback = Arc::new(LoopbackChannel::new());
+        let mut poller = SignalPoller::new(ap, Box::new(SharedLoopback(Arc::clone(&loopback))), Arc::clone(&targets))
+            .with_window(4);
+        poller.poll().unwrap();
+        assert_eq!(poller.signal(ap), None);
+        assert_eq!(signal(), -80);
+        
+        // Another BSSID's frames are ignored
+        for frame in [frame_at(ap, -40), frame_at(other, -90), frame_at(ap, -45)] {
+            loopback.inject_frame(&frame).unwrap();
+        }
+        poller.poll().unwrap();
+        assert_eq!(poller.signal(ap), Some(-42));
+        assert_eq!(signal(), -42);
+        
+        // Older samples fall out of the window as the AP fades
+        for _ in 0..3 {
+            loopback.inject_frame(&frame_at(ap, -60)).unwrap();
+        }
+        poller.poll().unwrap();
+        assert_eq!(poller.signal(ap), Some(-56));
+        assert_eq!(signal(), -56);
+        assert!(targets.lock().get_target(&ap).unwrap().last_seen > std::time::SystemTime::UNIX_EPOCH);
+        
+        // One source serves several BSSIDs, each averaged on its own
+        let mut poller = SignalPoller::for_bssids(&[ap, other], Box::new(SharedLoopback(Arc::clone(&loopback))), Arc::clone(&targets));
+        for frame in [frame_at(ap, -50), frame_at(other, -70)] {
+            loopback.inject_frame(&frame).unwrap();
+        }
+        poller.poll().unwrap();
+        assert_eq!((poller.signal(ap), poller.signal(other)), (Some(-50), Some(-70)));
+        assert_eq!(signal(), -50);
+    }
+}
\ No newline at end of file
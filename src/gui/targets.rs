@@ -1,8 +1,15 @@
 //! Target management for GUI
 
-use crate::network::NetworkInterface;
-use mac_address::MacAddress;
-use std::collections::HashMap;
+use crate::core::packet::{self, MacAddress, MacPattern};
+use crate::core::warnings::{Warning, WarningCategory};
+use crate::DeauthError;
+use crate::network::{CaptureResult, NetworkInterface};
+use crate::network::channel::WiFiBand;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
 use tracing::{debug, info};
 
 /// Wi-Fi target information
@@ -17,6 +24,38 @@ pub struct Target {
     pub last_seen: std::time::SystemTime,
 }
 
+impl Target {
+    /// Build a target from a radiotap-encapsulated beacon or probe response
+    ///
+    /// The channel comes from the DS Parameter Set element (0 if absent) and
+    /// the signal from the radiotap header (`i8::MIN` if not reported).
+    pub fn from_beacon(capture: &CaptureResult) -> Option<Self> {
+        let frame = packet::strip_radiotap(&capture.data)?;
+        let encryption = EncryptionType::from_beacon(frame)?;
+        let bssid: [u8; 6] = frame.get(16..22)?.try_into().ok()?;
+        
+        let mut ssid = String::new();
+        let mut channel = 0;
+        for (id, body) in elements(&frame[BEACON_CAPABILITY_OFFSET + 2..]) {
+            match id {
+                ELEMENT_SSID => ssid = String::from_utf8_lossy(body).into_owned(),
+                ELEMENT_DS_PARAMETER_SET => channel = body.first().copied().unwrap_or(0),
+                _ => {}
+            }
+        }
+        
+        Some(Self {
+            mac_address: MacAddress::new(bssid),
+            ssid,
+            channel,
+            signal_strength: packet::radiotap_signal(&capture.data).unwrap_or(i8::MIN),
+            encryption,
+            vendor: None,
+            last_seen: capture.timestamp,
+        })
+    }
+}
+
 /// Encryption type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EncryptionType {
@@ -28,10 +67,189 @@ pub enum EncryptionType {
     Unknown,
 }
 
+/// Capability Information "Privacy" bit (802.11-2020, 9.4.1.4)
+const CAPABILITY_PRIVACY: u16 = 0x0010;
+
+/// Offset of the Capability Information field in a beacon (after the
+/// 24-byte header, 8-byte timestamp and 2-byte beacon interval)
+const BEACON_CAPABILITY_OFFSET: usize = 34;
+
+/// Element ID of the SSID element
+const ELEMENT_SSID: u8 = 0;
+
+/// Element ID of the DS Parameter Set element, carrying the current channel
+const ELEMENT_DS_PARAMETER_SET: u8 = 3;
+
+/// Element ID of the RSN element
+const ELEMENT_RSN: u8 = 48;
+
+/// Element ID of vendor-specific elements
+const ELEMENT_VENDOR: u8 = 221;
+
+/// Microsoft OUI and type of the WPA vendor element
+const WPA_VENDOR_PREFIX: [u8; 4] = [0x00, 0x50, 0xF2, 0x01];
+
+/// RSN AKM suite selector for SAE (WPA3-Personal)
+const AKM_SAE: [u8; 4] = [0x00, 0x0F, 0xAC, 0x08];
+
+impl EncryptionType {
+    /// Classify a beacon or probe response frame (without radiotap header)
+    ///
+    /// RSN and WPA elements take precedence; without them the Capability
+    /// Information Privacy bit separates WEP from open networks.
+    pub fn from_beacon(frame: &[u8]) -> Option<Self> {
+        // Management frame, beacon or probe response subtype
+        if !matches!(frame.first(), Some(0x80 | 0x50)) {
+            return None;
+        }
+        
+        let capability = frame.get(BEACON_CAPABILITY_OFFSET..BEACON_CAPABILITY_OFFSET + 2)?;
+        let capability = u16::from_le_bytes([capability[0], capability[1]]);
+        
+        let mut rsn = None;
+        let mut wpa = false;
+        for (id, body) in elements(&frame[BEACON_CAPABILITY_OFFSET + 2..]) {
+            match id {
+                ELEMENT_RSN => rsn = Some(body),
+                ELEMENT_VENDOR if body.starts_with(&WPA_VENDOR_PREFIX) => wpa = true,
+                _ => {}
+            }
+        }
+        
+        let encryption = match rsn {
+            Some(body) if rsn_has_akm(body, AKM_SAE) => EncryptionType::WPA3,
+            Some(_) => EncryptionType::WPA2,
+            None if wpa => EncryptionType::WPA,
+            None if capability & CAPABILITY_PRIVACY != 0 => EncryptionType::WEP,
+            None => EncryptionType::Open,
+        };
+        
+        Some(encryption)
+    }
+}
+
+impl EncryptionType {
+    /// Parse a label as written by `Display`, ignoring case
+    pub fn from_label(label: &str) -> Option<Self> {
+        let encryption = match label.trim().to_ascii_uppercase().as_str() {
+            "OPEN" => EncryptionType::Open,
+            "WEP" => EncryptionType::WEP,
+            "WPA" => EncryptionType::WPA,
+            "WPA2" => EncryptionType::WPA2,
+            "WPA3" => EncryptionType::WPA3,
+            "" | "UNKNOWN" => EncryptionType::Unknown,
+            _ => return None,
+        };
+        Some(encryption)
+    }
+}
+
+impl std::fmt::Display for EncryptionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            EncryptionType::Open => "Open",
+            EncryptionType::WEP => "WEP",
+            EncryptionType::WPA => "WPA",
+            EncryptionType::WPA2 => "WPA2",
+            EncryptionType::WPA3 => "WPA3",
+            EncryptionType::Unknown => "Unknown",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Iterate over the information elements in `data`, stopping at a truncated one
+fn elements(mut data: &[u8]) -> impl Iterator<Item = (u8, &[u8])> {
+    std::iter::from_fn(move || {
+        let (&id, &len) = (data.first()?, data.get(1)?);
+        let body = data.get(2..2 + len as usize)?;
+        data = &data[2 + len as usize..];
+        Some((id, body))
+    })
+}
+
+/// Check if an RSN element body lists `akm` among its AKM suites
+fn rsn_has_akm(body: &[u8], akm: [u8; 4]) -> bool {
+    // Version (2) and group cipher suite (4)
+    let pairwise_count = match body.get(6..8) {
+        Some(count) => u16::from_le_bytes([count[0], count[1]]) as usize,
+        None => return false,
+    };
+    
+    let akm_offset = 8 + 4 * pairwise_count;
+    let akm_count = match body.get(akm_offset..akm_offset + 2) {
+        Some(count) => u16::from_le_bytes([count[0], count[1]]) as usize,
+        None => return false,
+    };
+    
+    body.get(akm_offset + 2..)
+        .map_or(false, |suites| suites.chunks_exact(4).take(akm_count).any(|suite| suite == akm))
+}
+
+/// Criteria for automatic target selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoSelectCriteria {
+    /// The single target with the strongest signal
+    StrongestSignal,
+    
+    /// Every target using this encryption
+    Encryption(EncryptionType),
+    
+    /// The N targets with the strongest signal
+    TopBySignal(usize),
+}
+
+/// Outcome of loading a target file
+#[derive(Debug, Clone, Default)]
+pub struct TargetFileReport {
+    /// Targets added to the manager
+    pub loaded: usize,
+    
+    /// One warning per skipped row, in file order
+    pub skipped: Vec<Warning>,
+}
+
+/// Target row as written in a JSON target file
+#[derive(Debug, serde::Deserialize)]
+struct TargetRecord {
+    bssid: String,
+    #[serde(default)]
+    ssid: String,
+    #[serde(default)]
+    channel: u8,
+    #[serde(default)]
+    encryption: String,
+}
+
+impl TargetRecord {
+    /// Validate the row and turn it into a target
+    fn into_target(self) -> crate::Result<Target> {
+        let mac_address = packet::parse_mac(&self.bssid)?;
+        
+        if self.channel != 0 && WiFiBand::from_channel(self.channel, None).is_none() {
+            return Err(DeauthError::ConfigError(format!("Invalid channel {}", self.channel)));
+        }
+        
+        let encryption = EncryptionType::from_label(&self.encryption)
+            .ok_or_else(|| DeauthError::ConfigError(format!("Unknown encryption \"{}\"", self.encryption)))?;
+        
+        Ok(Target {
+            mac_address,
+            ssid: self.ssid,
+            channel: self.channel,
+            signal_strength: i8::MIN,
+            encryption,
+            vendor: None,
+            last_seen: std::time::SystemTime::now(),
+        })
+    }
+}
+
 /// Target manager
 pub struct TargetManager {
     targets: HashMap<MacAddress, Target>,
     selected_targets: Vec<MacAddress>,
+    starred_targets: HashSet<MacAddress>,
 }
 
 impl TargetManager {
@@ -40,6 +258,7 @@ impl TargetManager {
         Self {
             targets: HashMap::new(),
             selected_targets: Vec::new(),
+            starred_targets: HashSet::new(),
         }
     }
     
@@ -53,6 +272,7 @@ impl TargetManager {
     pub fn remove_target(&mut self, mac: &MacAddress) -> Option<Target> {
         info!("Removing target: {}", mac);
         self.selected_targets.retain(|m| m != mac);
+        self.starred_targets.remove(mac);
         self.targets.remove(mac)
     }
     
@@ -61,11 +281,53 @@ impl TargetManager {
         self.targets.values().collect()
     }
     
+    /// Get targets whose channel lies in `band`
+    ///
+    /// Channel numbers shared with another band are counted as `band`.
+    pub fn targets_in_band(&self, band: WiFiBand) -> Vec<&Target> {
+        self.targets
+            .values()
+            .filter(|target| WiFiBand::from_channel(target.channel, Some(band)) == Some(band))
+            .collect()
+    }
+    
+    /// Get targets whose BSSID matches `pattern`, ordered by BSSID
+    pub fn matching(&self, pattern: &MacPattern) -> Vec<&Target> {
+        let mut matches: Vec<&Target> = self.targets
+            .values()
+            .filter(|target| pattern.matches(target.mac_address))
+            .collect();
+        matches.sort_by_key(|target| target.mac_address);
+        matches
+    }
+    
     /// Get target by MAC address
     pub fn get_target(&self, mac: &MacAddress) -> Option<&Target> {
         self.targets.get(mac)
     }
     
+    /// Star or unstar a target so its requests are serviced first
+    ///
+    /// Pass `starred_targets` to `DeauthEngine::set_priority_targets` to apply.
+    pub fn set_starred(&mut self, mac: MacAddress, starred: bool) {
+        if starred {
+            self.starred_targets.insert(mac);
+        } else {
+            self.starred_targets.remove(&mac);
+        }
+        debug!("Target {} starred: {}", mac, starred);
+    }
+    
+    /// Check if a target is starred
+    pub fn is_starred(&self, mac: &MacAddress) -> bool {
+        self.starred_targets.contains(mac)
+    }
+    
+    /// Get starred targets
+    pub fn starred_targets(&self) -> Vec<MacAddress> {
+        self.starred_targets.iter().copied().collect()
+    }
+    
     /// Select a target for attack
     pub fn select_target(&mut self, mac: MacAddress) -> Result<(), String> {
         if !self.targets.contains_key(&mac) {
@@ -80,6 +342,37 @@ impl TargetManager {
         Ok(())
     }
     
+    /// Replace the selection with the targets matching `criteria`
+    ///
+    /// Targets are ordered by signal strength, strongest first. Fails without
+    /// touching the current selection if nothing matches.
+    pub fn auto_select(&mut self, criteria: AutoSelectCriteria) -> Result<Vec<MacAddress>, String> {
+        let mut candidates: Vec<&Target> = self.targets.values().collect();
+        candidates.sort_by(|a, b| {
+            b.signal_strength
+                .cmp(&a.signal_strength)
+                .then_with(|| a.mac_address.cmp(&b.mac_address))
+        });
+        
+        let selected: Vec<MacAddress> = match criteria {
+            AutoSelectCriteria::StrongestSignal => candidates.iter().take(1).map(|t| t.mac_address).collect(),
+            AutoSelectCriteria::Encryption(encryption) => candidates
+                .iter()
+                .filter(|t| t.encryption == encryption)
+                .map(|t| t.mac_address)
+                .collect(),
+            AutoSelectCriteria::TopBySignal(count) => candidates.iter().take(count).map(|t| t.mac_address).collect(),
+        };
+        
+        if selected.is_empty() {
+            return Err(format!("No targets match {:?}", criteria));
+        }
+        
+        info!("Auto-selected {} targets ({:?})", selected.len(), criteria);
+        self.selected_targets = selected.clone();
+        Ok(selected)
+    }
+    
     /// Deselect a target
     pub fn deselect_target(&mut self, mac: &MacAddress) {
         self.selected_targets.retain(|m| m != mac);
@@ -101,6 +394,90 @@ impl TargetManager {
         self.selected_targets.clear();
     }
     
+    /// Export all targets to a CSV file
+    pub fn export_csv(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        let path = path.as_ref();
+        info!("Exporting {} targets to {}", self.targets.len(), path.display());
+        
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_csv(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+    
+    /// Write all targets as CSV, ordered by BSSID
+    ///
+    /// Last-seen times are written as RFC 3339 UTC timestamps.
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writeln!(writer, "bssid,ssid,channel,signal,encryption,vendor,last_seen")?;
+        
+        let mut targets: Vec<&Target> = self.targets.values().collect();
+        targets.sort_by_key(|target| target.mac_address);
+        
+        for target in targets {
+            writeln!(
+                writer,
+                "{},{},{},{},{:?},{},{}",
+                packet::format_mac(target.mac_address),
+                csv_field(&target.ssid),
+                target.channel,
+                target.signal_strength,
+                target.encryption,
+                csv_field(target.vendor.as_deref().unwrap_or("")),
+                DateTime::<Utc>::from(target.last_seen).to_rfc3339(),
+            )?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Add the targets listed in a CSV or JSON file, chosen by extension
+    ///
+    /// CSV files need a header row naming their columns; `bssid` is required
+    /// and `ssid`, `channel` and `encryption` are optional, so files written by
+    /// `export_csv` load back. JSON files hold an array of objects with the
+    /// same keys. Rows with an invalid address, channel or encryption are
+    /// skipped and reported; an unreadable file or unknown extension fails.
+    /// Loaded targets have no signal reading (`i8::MIN`).
+    pub fn load_from_file(&mut self, path: impl AsRef<Path>) -> crate::Result<TargetFileReport> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+        let parse = match extension.as_deref() {
+            Some("csv") => parse_csv_records,
+            Some("json") => parse_json_records,
+            _ => {
+                return Err(DeauthError::ConfigError(format!(
+                    "Unsupported target file {}: expected .csv or .json",
+                    path.display()
+                )))
+            }
+        };
+        
+        let rows = parse(&std::fs::read_to_string(path)?)?;
+        
+        // Every skipped row is kept, unlike in a capped, merging `Warnings` list
+        let mut skipped = Vec::new();
+        let mut loaded = 0;
+        for (row, record) in rows {
+            match record.and_then(TargetRecord::into_target) {
+                Ok(target) => {
+                    self.add_target(target);
+                    loaded += 1;
+                }
+                Err(e) => skipped.push(Warning {
+                    category: WarningCategory::Configuration,
+                    message: format!("Skipping row {} of {}: {}", row, path.display(), e),
+                    timestamp: chrono::Utc::now(),
+                    occurrences: 1,
+                }),
+            }
+        }
+        
+        let report = TargetFileReport { loaded, skipped };
+        info!("Loaded {} targets from {} ({} rows skipped)", report.loaded, path.display(), report.skipped.len());
+        Ok(report)
+    }
+    
     /// Update target signal strength
     pub fn update_signal(&mut self, mac: MacAddress, signal: i8) {
         if let Some(target) = self.targets.get_mut(&mac) {
@@ -115,4 +492,382 @@ impl Default for TargetManager {
     fn default() -> Self {
         Self::new()
     }
+}
+
+/// Parse CSV target rows, numbered from 1 after the header
+fn parse_csv_records(contents: &str) -> crate::Result<Vec<(usize, crate::Result<TargetRecord>)>> {
+    let mut rows = split_csv_records(contents).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .map(|names| names.into_iter().map(|name| name.trim().to_ascii_lowercase()).collect())
+        .unwrap_or_default();
+    
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let bssid_column = column("bssid")
+        .ok_or_else(|| DeauthError::ConfigError("Target CSV has no bssid column".to_string()))?;
+    let (ssid_column, channel_column, encryption_column) = (column("ssid"), column("channel"), column("encryption"));
+    
+    let records = rows
+        .enumerate()
+        .map(|(index, fields)| {
+            let field = |column: Option<usize>| column.and_then(|i| fields.get(i)).map_or("", |value| value.as_str());
+            
+            let channel = match field(channel_column).trim() {
+                "" => Ok(0),
+                value => value
+                    .parse()
+                    .map_err(|_| DeauthError::ConfigError(format!("Invalid channel \"{}\"", value))),
+            };
+            let record = channel.map(|channel| TargetRecord {
+                bssid: field(Some(bssid_column)).to_string(),
+                ssid: field(ssid_column).to_string(),
+                channel,
+                encryption: field(encryption_column).to_string(),
+            });
+            
+            (index + 1, record)
+        })
+        .collect();
+    
+    Ok(records)
+}
+
+/// Parse JSON target rows, numbered from 1
+fn parse_json_records(contents: &str) -> crate::Result<Vec<(usize, crate::Result<TargetRecord>)>> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(contents)
+        .map_err(|e| DeauthError::ConfigError(format!("Target JSON must be an array of objects: {}", e)))?;
+    
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let record = serde_json::from_value(value).map_err(|e| DeauthError::ConfigError(e.to_string()));
+            (index + 1, record)
+        })
+        .collect())
+}
+
+/// Split CSV text into records of fields, undoing `csv_field` quoting
+///
+/// A quoted field may span line breaks. Blank lines are skipped.
+fn split_csv_records(contents: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = contents.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\r' if !quoted && matches!(chars.peek(), Some('\n') | None) => {}
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut fields));
+            }
+            c => field.push(c),
+        }
+    }
+    
+    fields.push(field);
+    records.push(fields);
+    records.retain(|fields| fields.len() > 1 || fields.iter().any(|field| !field.trim().is_empty()));
+    records
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn target(last_byte: u8, channel: u8) -> Target {
+        Target {
+            mac_address: MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, last_byte]),
+            ssid: format!("net-{}", last_byte),
+            channel,
+            signal_strength: -50,
+            encryption: EncryptionType::WPA2,
+            vendor: None,
+            last_seen: std::time::SystemTime::now(),
+        }
+    }
+    
+    #[test]
+    fn test_targets_in_band() {
+        let mut manager = TargetManager::new();
+        manager.add_target(target(1, 6));
+        manager.add_target(target(2, 36));
+        manager.add_target(target(3, 1));
+        manager.add_target(target(4, 37));
+        
+        let mut in_2ghz: Vec<u8> = manager.targets_in_band(WiFiBand::TwoPointFourGHz).iter().map(|t| t.channel).collect();
+        in_2ghz.sort_unstable();
+        assert_eq!(in_2ghz, vec![1, 6]);
+        
+        let in_5ghz: Vec<u8> = manager.targets_in_band(WiFiBand::FiveGHz).iter().map(|t| t.channel).collect();
+        assert_eq!(in_5ghz, vec![36]);
+        
+        // Channel 1 is also a 6 GHz channel
+        let mut in_6ghz: Vec<u8> = manager.targets_in_band(WiFiBand::SixGHz).iter().map(|t| t.channel).collect();
+        in_6ghz.sort_unstable();
+        assert_eq!(in_6ghz, vec![1, 37]);
+    }
+    
+    #[test]
+    fn test_auto_select() {
+        let mut manager = TargetManager::new();
+        for (last_byte, signal, encryption) in [
+            (1, -70, EncryptionType::Open),
+            (2, -40, EncryptionType::WPA2),
+            (3, -55, EncryptionType::Open),
+            (4, -60, EncryptionType::WPA3),
+        ] {
+            manager.add_target(Target {
+                signal_strength: signal,
+                encryption,
+                ..target(last_byte, 6)
+            });
+        }
+        let mac = |last_byte: u8| MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, last_byte]);
+        
+        assert_eq!(manager.auto_select(AutoSelectCriteria::StrongestSignal), Ok(vec![mac(2)]));
+        assert_eq!(
+            manager.auto_select(AutoSelectCriteria::Encryption(EncryptionType::Open)),
+            Ok(vec![mac(3), mac(1)])
+        );
+        assert_eq!(manager.auto_select(AutoSelectCriteria::TopBySignal(3)), Ok(vec![mac(2), mac(3), mac(4)]));
+        
+        let selected: Vec<MacAddress> = manager.get_selected_targets().iter().map(|t| t.mac_address).collect();
+        assert_eq!(selected, vec![mac(2), mac(3), mac(4)]);
+        
+        // No match leaves the selection alone
+        assert!(manager.auto_select(AutoSelectCriteria::Encryption(EncryptionType::WEP)).is_err());
+        assert!(manager.auto_select(AutoSelectCriteria::TopBySignal(0)).is_err());
+        assert_eq!(manager.get_selected_targets().len(), 3);
+    }
+    
+    #[test]
+    fn test_matching_pattern() {
+        let mut manager = TargetManager::new();
+        manager.add_target(target(1, 6));
+        manager.add_target(target(2, 11));
+        manager.add_target(Target {
+            mac_address: MacAddress::new([0xAA, 0xBB, 0xCC, 0x00, 0x00, 0x01]),
+            ..target(3, 1)
+        });
+        
+        let vendor: MacPattern = "00:11:22:*".parse().expect("Should parse prefix");
+        let matched: Vec<u8> = manager.matching(&vendor).iter().map(|t| t.mac_address.bytes()[5]).collect();
+        assert_eq!(matched, vec![1, 2]);
+        
+        let exact: MacPattern = "00:11:22:33:44:02".parse().expect("Should parse address");
+        let matched: Vec<&str> = manager.matching(&exact).iter().map(|t| t.ssid.as_str()).collect();
+        assert_eq!(matched, vec!["net-2"]);
+        
+        let other: MacPattern = "DE:AD:BE:*".parse().expect("Should parse prefix");
+        assert!(manager.matching(&other).is_empty());
+    }
+    
+    #[test]
+    fn test_write_csv() {
+        let mut manager = TargetManager::new();
+        manager.add_target(Target {
+            ssid: "Cafe, Free \"Wi-Fi\"".to_string(),
+            vendor: Some("Acme".to_string()),
+            last_seen: std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(86_400),
+            ..target(2, 11)
+        });
+        manager.add_target(target(1, 6));
+        
+        let mut buffer = Vec::new();
+        manager.write_csv(&mut buffer).expect("Should write CSV");
+        let csv = String::from_utf8(buffer).expect("Should be UTF-8");
+        let lines: Vec<&str> = csv.lines().collect();
+        
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "bssid,ssid,channel,signal,encryption,vendor,last_seen");
+        assert!(lines[1].starts_with("00:11:22:33:44:01,net-1,6,-50,WPA2,,"));
+        assert_eq!(
+            lines[2],
+            "00:11:22:33:44:02,\"Cafe, Free \"\"Wi-Fi\"\"\",11,-50,WPA2,Acme,1970-01-02T00:00:00+00:00"
+        );
+    }
+    
+    #[test]
+    fn test_csv_round_trip_with_line_breaks() {
+        let mut manager = TargetManager::new();
+        manager.add_target(Target {
+            ssid: "Lobby\nSecond \"floor\"\r\n, east".to_string(),
+            ..target(1, 6)
+        });
+        manager.add_target(target(2, 11));
+        
+        let path = std::env::temp_dir().join(format!("wifi-deauther-round-trip-{}.csv", std::process::id()));
+        manager.export_csv(&path).expect("Should export CSV");
+        let mut loaded = TargetManager::new();
+        let report = loaded.load_from_file(&path).expect("Should load CSV");
+        std::fs::remove_file(&path).ok();
+        
+        assert_eq!((report.loaded, report.skipped.len()), (2, 0));
+        for original in manager.get_targets() {
+            let target = loaded.get_target(&original.mac_address).expect("Should load every target");
+            assert_eq!((target.ssid.as_str(), target.channel), (original.ssid.as_str(), original.channel));
+        }
+    }
+    
+    /// Build a beacon with the given capability field and elements
+    fn beacon(capability: u16, elements: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x80, 0x00, 0x00, 0x00];
+        frame.extend_from_slice(&[0xFF; 6]);
+        frame.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        frame.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        frame.extend_from_slice(&[0x00, 0x00]);
+        frame.extend_from_slice(&[0x00; 8]);
+        frame.extend_from_slice(&100u16.to_le_bytes());
+        frame.extend_from_slice(&capability.to_le_bytes());
+        
+        // SSID element
+        frame.extend_from_slice(&[0x00, 0x04]);
+        frame.extend_from_slice(b"test");
+        frame.extend_from_slice(elements);
+        frame
+    }
+    
+    /// RSN element with CCMP and a single AKM suite
+    fn rsn_element(akm_type: u8) -> Vec<u8> {
+        vec![
+            ELEMENT_RSN, 20,
+            0x01, 0x00,
+            0x00, 0x0F, 0xAC, 0x04,
+            0x01, 0x00, 0x00, 0x0F, 0xAC, 0x04,
+            0x01, 0x00, 0x00, 0x0F, 0xAC, akm_type,
+            0x00, 0x00,
+        ]
+    }
+    
+    #[test]
+    fn test_target_from_beacon() {
+        use crate::core::packet::RADIOTAP_HEADER;
+        
+        let ds_parameter_set = [ELEMENT_DS_PARAMETER_SET, 1, 11];
+        let mut data = RADIOTAP_HEADER.to_vec();
+        data.extend_from_slice(&beacon(0x0011, &[&ds_parameter_set[..], &rsn_element(0x02)].concat()));
+        let capture = CaptureResult {
+            timestamp: std::time::UNIX_EPOCH,
+            length: data.len(),
+            data,
+        };
+        
+        let target = Target::from_beacon(&capture).expect("Should parse beacon");
+        assert_eq!(target.mac_address, MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]));
+        assert_eq!(target.ssid, "test");
+        assert_eq!(target.channel, 11);
+        assert_eq!(target.signal_strength, i8::MIN);
+        assert_eq!(target.encryption, EncryptionType::WPA2);
+        assert_eq!(target.last_seen, std::time::UNIX_EPOCH);
+        assert_eq!(target.encryption.to_string(), "WPA2");
+        
+        // Frames without radiotap, or other frame types, are not targets
+        let bare = CaptureResult { data: beacon(0x0001, &[]), length: 0, timestamp: std::time::UNIX_EPOCH };
+        assert!(Target::from_beacon(&bare).is_none());
+    }
+    
+    #[test]
+    fn test_privacy_bit_classification() {
+        // ESS capability only: open network
+        assert_eq!(EncryptionType::from_beacon(&beacon(0x0001, &[])), Some(EncryptionType::Open));
+        
+        // Privacy bit without RSN/WPA elements: WEP
+        assert_eq!(EncryptionType::from_beacon(&beacon(0x0011, &[])), Some(EncryptionType::WEP));
+    }
+    
+    #[test]
+    fn test_security_element_classification() {
+        assert_eq!(EncryptionType::from_beacon(&beacon(0x0011, &rsn_element(0x02))), Some(EncryptionType::WPA2));
+        assert_eq!(EncryptionType::from_beacon(&beacon(0x0011, &rsn_element(0x08))), Some(EncryptionType::WPA3));
+        
+        let wpa = [ELEMENT_VENDOR, 6, 0x00, 0x50, 0xF2, 0x01, 0x01, 0x00];
+        assert_eq!(EncryptionType::from_beacon(&beacon(0x0011, &wpa)), Some(EncryptionType::WPA));
+        
+        // Not a beacon, or truncated before the capability field
+        assert_eq!(EncryptionType::from_beacon(&[0xC0, 0x00]), None);
+        assert_eq!(EncryptionType::from_beacon(&beacon(0x0011, &[])[..30]), None);
+    }
+    
+    #[test]
+    fn test_load_targets_from_csv() {
+        let path = std::env::temp_dir().join(format!("wifi-deauther-targets-{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            "bssid,ssid,channel,encryption\n\
+             00:11:22:33:44:01,lobby,6,WPA2\n\
+             00-11-22-33-44-02,\"Cafe, Free\",36,open\n\
+             00:11:22:*,prefix,1,WPA2\n\
+             00:11:22:33:44:03,bad-channel,15,WPA2\n\
+             00:11:22:33:44:04,,,\n",
+        )
+        .unwrap();
+        
+        let mut manager = TargetManager::new();
+        let report = manager.load_from_file(&path).expect("Should load CSV");
+        
+        // Every skipped row is reported, however many there are
+        let bad_rows: String = (0..300).map(|row| format!("bad-{},x,1,WPA2\n", row)).collect();
+        std::fs::write(&path, format!("bssid,ssid,channel,encryption\n{}", bad_rows)).unwrap();
+        let bad = TargetManager::new().load_from_file(&path).expect("Should load CSV");
+        std::fs::remove_file(&path).ok();
+        assert_eq!((bad.loaded, bad.skipped.len()), (0, 300));
+        
+        assert_eq!(report.loaded, 3);
+        assert_eq!(report.skipped.len(), 2);
+        assert!(report.skipped[0].message.starts_with("Skipping row 3"));
+        assert!(report.skipped[1].message.starts_with("Skipping row 4"));
+        
+        let cafe = manager.get_target(&MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x02])).expect("Should load cafe");
+        assert_eq!((cafe.ssid.as_str(), cafe.channel, cafe.encryption), ("Cafe, Free", 36, EncryptionType::Open));
+        let bare = manager.get_target(&MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x04])).expect("Should load bare row");
+        assert_eq!((bare.channel, bare.encryption, bare.signal_strength), (0, EncryptionType::Unknown, i8::MIN));
+    }
+    
+    #[test]
+    fn test_load_targets_from_json() {
+        let path = std::env::temp_dir().join(format!("wifi-deauther-targets-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"[
+                {"bssid": "aa:bb:cc:dd:ee:01", "ssid": "office", "channel": 11, "encryption": "WPA3"},
+                {"bssid": "not-a-mac", "channel": 6},
+                {"bssid": "aa:bb:cc:dd:ee:02", "channel": "six"},
+                {"bssid": "aa:bb:cc:dd:ee:03"}
+            ]"#,
+        )
+        .unwrap();
+        
+        let mut manager = TargetManager::new();
+        let report = manager.load_from_file(&path).expect("Should load JSON");
+        std::fs::remove_file(&path).ok();
+        
+        assert_eq!(report.loaded, 2);
+        assert_eq!(report.skipped.len(), 2);
+        assert!(report.skipped.iter().all(|warning| warning.category == WarningCategory::Configuration));
+        
+        let office = manager.get_target(&MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01])).expect("Should load office");
+        assert_eq!((office.ssid.as_str(), office.channel, office.encryption), ("office", 11, EncryptionType::WPA3));
+        assert!(manager.get_target(&MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x03])).is_some());
+        
+        assert!(matches!(manager.load_from_file("targets.txt"), Err(DeauthError::ConfigError(_))));
+    }
 }
\ No newline at end of file
@@ -8,12 +8,14 @@
 
 pub mod core;
 pub mod network;
+#[cfg(feature = "gui")]
 pub mod gui;
 pub mod platform;
 
-pub use core::{engine::DeauthEngine, metrics::Metrics};
+pub use core::{engine::DeauthEngine, metrics::Metrics, packet::{format_mac, MacAddress}};
 pub use network::{interface::NetworkInterface, injection::PacketInjector};
-pub use gui::app::DeauthApp;
+#[cfg(feature = "gui")]
+pub use gui::app::{AppMode, DeauthApp};
 
 use thiserror::Error;
 
@@ -25,6 +27,10 @@ pub enum DeauthError {
     #[error("Packet injection failed: {0}")]
     InjectionError(String),
     
+    /// The kernel send buffer is full (`ENOBUFS`); sending slower will help
+    #[error("Send buffer full: {0}")]
+    SendBufferFull(String),
+    
     #[error("Permission denied: {0}")]
     PermissionError(String),
     
@@ -34,6 +40,10 @@ pub enum DeauthError {
     #[error("Invalid configuration: {0}")]
     ConfigError(String),
     
+    /// A single configuration field is invalid; `field` is its path, e.g. `worker_threads`
+    #[error("Invalid configuration: {field}: {message}")]
+    InvalidField { field: String, message: String },
+    
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     
@@ -41,4 +51,95 @@ pub enum DeauthError {
     PcapError(#[from] pcap::Error),
 }
 
-pub type Result<T> = std::result::Result<T, DeauthError>;
\ No newline at end of file
+impl DeauthError {
+    /// Wrap a pcap error from an injection operation, naming the operation
+    ///
+    /// Produces messages like "sending frame on wlan0mon: ..." instead of a bare pcap string.
+    /// `ENOBUFS` failures become `SendBufferFull` so callers can back off instead.
+    pub fn injection_from_pcap(error: pcap::Error, context: impl std::fmt::Display) -> Self {
+        let message = format!("{}: {}", context, error);
+        if message.contains(&enobufs_message()) {
+            DeauthError::SendBufferFull(message)
+        } else {
+            DeauthError::InjectionError(message)
+        }
+    }
+    
+    /// Wrap a pcap error from a device operation, naming the operation
+    pub fn interface_from_pcap(error: pcap::Error, context: impl std::fmt::Display) -> Self {
+        DeauthError::InterfaceError(format!("{}: {}", context, error))
+    }
+    
+    /// Report an invalid configuration field
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        DeauthError::InvalidField {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+    
+    /// Path of the offending configuration field, if the error names one
+    ///
+    /// Lets the GUI highlight the field instead of only showing the message.
+    pub fn config_field(&self) -> Option<&str> {
+        match self {
+            DeauthError::InvalidField { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, DeauthError>;
+
+/// `strerror` text for `ENOBUFS`, which pcap embeds in `sendpacket` errors
+///
+/// pcap reports send failures as text only, so the error code cannot be
+/// compared directly; the platform's own message for `libc::ENOBUFS` is the
+/// closest match. This still depends on pcap and the standard library
+/// rendering it the same way: a translated `strerror` under a non-C locale
+/// would leave the failure as a plain `InjectionError`.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn enobufs_message() -> String {
+    let message = std::io::Error::from_raw_os_error(libc::ENOBUFS).to_string();
+    let suffix = format!(" (os error {})", libc::ENOBUFS);
+    match message.strip_suffix(&suffix) {
+        Some(text) => text.to_string(),
+        None => message,
+    }
+}
+
+/// `strerror` text for `ENOBUFS`; without libc, the C library's English text
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn enobufs_message() -> String {
+    "No buffer space available".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_pcap_error_context() {
+        let error = DeauthError::interface_from_pcap(
+            pcap::Error::PcapError("No such device exists".to_string()),
+            "opening device wlan0",
+        );
+        let message = error.to_string();
+        assert!(message.starts_with("Network interface error: opening device wlan0: "));
+        assert!(message.contains("No such device exists"));
+        
+        let error = DeauthError::injection_from_pcap(
+            pcap::Error::PcapError("send: Resource temporarily unavailable".to_string()),
+            format!("sending frame on {}", "wlan0mon"),
+        );
+        assert!(matches!(error, DeauthError::InjectionError(_)));
+        assert!(error.to_string().contains("sending frame on wlan0mon: "));
+        
+        let error = DeauthError::injection_from_pcap(
+            pcap::Error::PcapError("send: No buffer space available".to_string()),
+            "sending frame on wlan0mon",
+        );
+        assert!(matches!(error, DeauthError::SendBufferFull(_)));
+        assert!(!enobufs_message().contains("os error"));
+    }
+}
\ No newline at end of file
@@ -7,8 +7,12 @@
 //! - Lightweight Slint GUI
 //! - Cross-platform support (Linux, Windows, macOS)
 
-use wifi_deauther::{DeauthApp, Result};
+use wifi_deauther::{core::{packet::reason_codes, EngineConfig}, DeauthEngine, Result};
+#[cfg(feature = "gui")]
+use wifi_deauther::{core::{WarningCategory, Warnings}, platform, AppMode, DeauthApp};
 use tracing::{info, error};
+#[cfg(feature = "gui")]
+use tracing::warn;
 use tracing_subscriber;
 
 #[tokio::main]
@@ -19,81 +23,119 @@ async fn main() -> Result<()> {
         .init();
 
     info!("Starting Wi-Fi Deauther v{}", env!("CARGO_PKG_VERSION"));
+    
+    // Offline self-test needs neither hardware nor privileges
+    if std::env::args().any(|arg| arg == "--self-test") {
+        return run_self_test();
+    }
+    
+    if std::env::args().any(|arg| arg == "--list-reason-codes") {
+        list_reason_codes();
+        return Ok(());
+    }
+
+    run_gui().await?;
+    
+    info!("Wi-Fi Deauther shutdown complete");
+    Ok(())
+}
 
+#[cfg(feature = "gui")]
+async fn run_gui() -> Result<()> {
     // Check platform compatibility
-    if let Err(e) = check_platform_compatibility() {
-        error!("Platform compatibility check failed: {}", e);
-        return Err(e);
-    }
+    let warnings = Warnings::new();
+    let mode = match check_platform_compatibility(&warnings) {
+        Ok(mode) => mode,
+        Err(e) => {
+            error!("Platform compatibility check failed: {}", e);
+            return Err(e);
+        }
+    };
 
     // Initialize and run the GUI application
-    match DeauthApp::new().await {
+    match DeauthApp::with_mode(mode).await {
         Ok(app) => {
             info!("GUI application initialized successfully");
-            app.run().await?;
+            app.warnings().extend(warnings.take());
+            app.run().await
         }
         Err(e) => {
             error!("Failed to initialize GUI application: {}", e);
-            return Err(e);
+            Err(e)
         }
     }
+}
 
-    info!("Wi-Fi Deauther shutdown complete");
-    Ok(())
+#[cfg(not(feature = "gui"))]
+async fn run_gui() -> Result<()> {
+    error!("Built without the `gui` feature; only --self-test and --list-reason-codes are available");
+    Err(wifi_deauther::DeauthError::PlatformError("GUI support not compiled in".to_string()))
 }
 
-fn check_platform_compatibility() -> Result<()> {
-    #[cfg(target_os = "linux")]
-    {
-        use std::process::Command;
-        
-        // Check if running as root (required for packet injection)
-        if unsafe { libc::geteuid() } != 0 {
-            return Err(wifi_deauther::DeauthError::PermissionError(
-                "This application requires root privileges for packet injection. Please run with sudo.".to_string()
-            ));
-        }
-        
-        // Check for required kernel modules
-        let output = Command::new("lsmod")
-            .output()
-            .map_err(|e| wifi_deauther::DeauthError::PlatformError(format!("Failed to check kernel modules: {}", e)))?;
-        
-        let modules = String::from_utf8_lossy(&output.stdout);
-        if !modules.contains("mac80211") && !modules.contains("cfg80211") {
-            tracing::warn!("Wireless kernel modules not loaded. Some features may be limited.");
-        }
+fn list_reason_codes() {
+    for &code in reason_codes::all() {
+        println!("{:>3}  {}", code, reason_codes::description(code));
     }
+}
+
+fn run_self_test() -> Result<()> {
+    let engine = DeauthEngine::new(EngineConfig::default())?;
+    let report = engine.self_test();
     
-    #[cfg(target_os = "windows")]
-    {
-        // Check for Npcap/WinPcap
-        use std::path::Path;
-        if !Path::new("C:\\Windows\\System32\\Npcap.dll").exists() && 
-           !Path::new("C:\\Windows\\System32\\wpcap.dll").exists() {
-            return Err(wifi_deauther::DeauthError::PlatformError(
-                "Npcap or WinPcap not found. Please install Npcap from https://npcap.com/".to_string()
-            ));
-        }
+    println!("packet build: {}", if report.packet_build { "ok" } else { "FAILED" });
+    println!("buffer pool:  {}", if report.buffer_pool { "ok" } else { "FAILED" });
+    println!("metrics:      {}", if report.metrics { "ok" } else { "FAILED" });
+    for failure in &report.failures {
+        println!("  - {}", failure);
+    }
+    
+    let integrity = engine.verify_frame_integrity();
+    match &integrity {
+        Ok(()) => println!("frame integrity: ok"),
+        Err(e) => println!("frame integrity: FAILED ({})", e),
+    }
+    
+    if report.passed() && integrity.is_ok() {
+        Ok(())
+    } else {
+        Err(wifi_deauther::DeauthError::PlatformError("Self-test failed".to_string()))
+    }
+}
+
+#[cfg(feature = "gui")]
+fn check_platform_compatibility(warnings: &Warnings) -> Result<AppMode> {
+    let privileges = platform::privileges();
+    for missing in &privileges.missing {
+        warn!("{}", missing);
+        warnings.push(WarningCategory::Platform, missing.clone());
     }
     
-    #[cfg(target_os = "macos")]
+    // Without capture there is nothing left to offer; without injection we can still scan
+    if !privileges.can_capture {
+        return Err(wifi_deauther::DeauthError::PlatformError(privileges.missing.join("; ")));
+    }
+    let mode = if privileges.can_inject {
+        AppMode::Full
+    } else {
+        warn!("Packet injection is disabled; starting in scan-only mode");
+        AppMode::ScanOnly
+    };
+    
+    #[cfg(target_os = "linux")]
     {
-        // Check for BPF permissions
-        use std::fs;
-        if let Ok(entries) = fs::read_dir("/dev") {
-            let bpf_devices: Vec<_> = entries
-                .filter_map(|e| e.ok())
-                .filter(|e| e.file_name().to_string_lossy().starts_with("bpf"))
-                .collect();
-            
-            if bpf_devices.is_empty() {
-                return Err(wifi_deauther::DeauthError::PlatformError(
-                    "No BPF devices found. You may need to load the BPF kernel extension.".to_string()
-                ));
+        // Missing wireless modules limit features but are not fatal
+        match platform::check_kernel_modules() {
+            Ok(modules) if !modules.wireless_available() => {
+                warn!("Wireless kernel modules not loaded. Some features may be limited.");
+                warnings.push(WarningCategory::Platform, "Wireless kernel modules not loaded; some features may be limited");
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!("{}", e);
+                warnings.push(WarningCategory::Platform, e.to_string());
             }
         }
     }
     
-    Ok(())
+    Ok(mode)
 }
\ No newline at end of file
@@ -0,0 +1,338 @@
+//! Access point ↔ client association graph
+//! 
+//! `AssociationGraph` maps which clients associate with which access points
+//! from a capture: association exchanges and unicast data frames add edges,
+//! beacons and probe responses label access points with their SSID. The graph
+//! exports to Graphviz DOT for drawing and to JSON for other tools.
+
+use super::capture::CaptureResult;
+use crate::core::packet::{self, format_mac, MacAddress};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Management subtypes, as the first frame control byte
+const ASSOC_REQUEST: u8 = 0x00;
+const ASSOC_RESPONSE: u8 = 0x10;
+const REASSOC_REQUEST: u8 = 0x20;
+const REASSOC_RESPONSE: u8 = 0x30;
+const PROBE_RESPONSE: u8 = 0x50;
+const BEACON: u8 = 0x80;
+
+/// Offset of the first element in a beacon or probe response body
+const BEACON_ELEMENTS_OFFSET: usize = packet::MGMT_HEADER_LEN + 12;
+
+/// Offset of the status code in an association response
+const ASSOC_STATUS_OFFSET: usize = packet::MGMT_HEADER_LEN + 2;
+
+/// Role of a node in the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeRole {
+    AccessPoint,
+    Client,
+}
+
+impl NodeRole {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::AccessPoint => "access_point",
+            Self::Client => "client",
+        }
+    }
+}
+
+/// A station seen in the capture
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssociationNode {
+    pub mac: MacAddress,
+    pub role: NodeRole,
+    
+    /// Network name, for access points that announced one
+    pub ssid: Option<String>,
+    
+    /// Manufacturer, if set with `AssociationGraph::set_vendor`
+    pub vendor: Option<String>,
+}
+
+/// An observed association between a client and an access point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssociationEdge {
+    pub access_point: MacAddress,
+    pub client: MacAddress,
+    
+    /// Frames that showed the association
+    pub frames: u64,
+}
+
+/// Graph of clients and the access points they associate with
+#[derive(Debug, Default)]
+pub struct AssociationGraph {
+    nodes: BTreeMap<MacAddress, AssociationNode>,
+    edges: BTreeMap<(MacAddress, MacAddress), u64>,
+}
+
+impl AssociationGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Process a radiotap-encapsulated capture as delivered by a monitor-mode interface
+    pub fn process_capture(&mut self, capture: &CaptureResult) -> bool {
+        match packet::strip_radiotap(&capture.data) {
+            Some(frame) => self.process_frame(frame),
+            None => false,
+        }
+    }
+    
+    /// Process a raw IEEE 802.11 frame, returning true if it added to the graph
+    ///
+    /// Failed association responses, group-addressed data and frames between
+    /// stations outside an infrastructure network are ignored.
+    pub fn process_frame(&mut self, frame: &[u8]) -> bool {
+        if frame.len() < packet::MGMT_HEADER_LEN {
+            return false;
+        }
+        
+        let address = |offset: usize| {
+            let mut bytes = [0u8; 6];
+            bytes.copy_from_slice(&frame[offset..offset + 6]);
+            MacAddress::new(bytes)
+        };
+        let (addr1, addr2) = (address(4), address(10));
+        
+        let frame_type = (frame[0] >> 2) & 0x03;
+        let to_ds = frame[1] & 0x01 != 0;
+        let from_ds = frame[1] & 0x02 != 0;
+        
+        match (frame_type, frame[0] & 0xF0) {
+            (0, BEACON | PROBE_RESPONSE) => {
+                let ssid = ssid(frame.get(BEACON_ELEMENTS_OFFSET..).unwrap_or_default());
+                let node = self.node(addr2, NodeRole::AccessPoint);
+                if ssid.is_some() {
+                    node.ssid = ssid;
+                }
+                true
+            }
+            (0, ASSOC_REQUEST | REASSOC_REQUEST) => self.associate(addr1, addr2),
+            (0, ASSOC_RESPONSE | REASSOC_RESPONSE) => {
+                let accepted = frame
+                    .get(ASSOC_STATUS_OFFSET..ASSOC_STATUS_OFFSET + 2)
+                    .is_some_and(|status| status == [0, 0]);
+                accepted && self.associate(addr2, addr1)
+            }
+            (2, _) => match (to_ds, from_ds) {
+                (true, false) => self.associate(addr1, addr2),
+                (false, true) => self.associate(addr2, addr1),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+    
+    /// Record that `client` is associated with `access_point`
+    fn associate(&mut self, access_point: MacAddress, client: MacAddress) -> bool {
+        if !packet::is_unicast(access_point) || !packet::is_unicast(client) || access_point == client {
+            return false;
+        }
+        
+        self.node(access_point, NodeRole::AccessPoint);
+        self.node(client, NodeRole::Client);
+        *self.edges.entry((access_point, client)).or_insert(0) += 1;
+        true
+    }
+    
+    /// Node for `mac`, created with `role` if new
+    ///
+    /// A station seen as an access point stays one.
+    fn node(&mut self, mac: MacAddress, role: NodeRole) -> &mut AssociationNode {
+        let node = self.nodes.entry(mac).or_insert_with(|| AssociationNode {
+            mac,
+            role,
+            ssid: None,
+            vendor: None,
+        });
+        if role == NodeRole::AccessPoint {
+            node.role = role;
+        }
+        node
+    }
+    
+    /// Label a node with its manufacturer, e.g. from an OUI database
+    pub fn set_vendor(&mut self, mac: MacAddress, vendor: impl Into<String>) {
+        if let Some(node) = self.nodes.get_mut(&mac) {
+            node.vendor = Some(vendor.into());
+        }
+    }
+    
+    /// Nodes ordered by MAC address
+    pub fn nodes(&self) -> Vec<AssociationNode> {
+        self.nodes.values().cloned().collect()
+    }
+    
+    /// Edges ordered by access point, then client
+    pub fn edges(&self) -> Vec<AssociationEdge> {
+        self.edges
+            .iter()
+            .map(|(&(access_point, client), &frames)| AssociationEdge { access_point, client, frames })
+            .collect()
+    }
+    
+    /// Clients seen associated with `access_point`
+    pub fn clients_of(&self, access_point: MacAddress) -> Vec<MacAddress> {
+        self.edges
+            .keys()
+            .filter(|(ap, _)| *ap == access_point)
+            .map(|&(_, client)| client)
+            .collect()
+    }
+    
+    /// Render the graph in Graphviz DOT
+    ///
+    /// Access points are boxes, clients ellipses; labels show the MAC followed
+    /// by the SSID and vendor when known.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph associations {\n");
+        
+        for node in self.nodes.values() {
+            let mut label = format_mac(node.mac);
+            for extra in [&node.ssid, &node.vendor].into_iter().flatten() {
+                label.push_str("\\n");
+                label.push_str(&escape_dot(extra));
+            }
+            let shape = match node.role {
+                NodeRole::AccessPoint => "box",
+                NodeRole::Client => "ellipse",
+            };
+            let _ = writeln!(dot, "    \"{}\" [label=\"{}\", shape={}];", format_mac(node.mac), label, shape);
+        }
+        
+        for (&(access_point, client), frames) in &self.edges {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -- \"{}\" [label=\"{}\"];",
+                format_mac(access_point), format_mac(client), frames
+            );
+        }
+        
+        dot.push_str("}\n");
+        dot
+    }
+    
+    /// Render the graph as a JSON object with `nodes` and `edges` arrays
+    pub fn to_json(&self) -> Value {
+        let nodes: Vec<Value> = self
+            .nodes
+            .values()
+            .map(|node| {
+                json!({
+                    "mac": format_mac(node.mac),
+                    "role": node.role.as_str(),
+                    "ssid": node.ssid,
+                    "vendor": node.vendor,
+                })
+            })
+            .collect();
+        let edges: Vec<Value> = self
+            .edges()
+            .iter()
+            .map(|edge| {
+                json!({
+                    "access_point": format_mac(edge.access_point),
+                    "client": format_mac(edge.client),
+                    "frames": edge.frames,
+                })
+            })
+            .collect();
+        
+        json!({ "nodes": nodes, "edges": edges })
+    }
+}
+
+/// SSID from a beacon's elements, if present and not hidden
+fn ssid(mut elements: &[u8]) -> Option<String> {
+    while let [id, len, rest @ ..] = elements {
+        let body = rest.get(..usize::from(*len))?;
+        if *id == 0 {
+            return (!body.is_empty() && body.iter().any(|&b| b != 0))
+                .then(|| String::from_utf8_lossy(body).into_owned());
+        }
+        elements = &rest[body.len()..];
+    }
+    None
+}
+
+/// Escape a label for a double-quoted DOT string
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const AP: [u8; 6] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+    const PHONE: [u8; 6] = [0x66, 0x77, 0x88, 0x99, 0xAA, 0x01];
+    const LAPTOP: [u8; 6] = [0x66, 0x77, 0x88, 0x99, 0xAA, 0x02];
+    
+    /// 802.11 header with the given frame control and addresses
+    fn frame(control: [u8; 2], addr1: [u8; 6], addr2: [u8; 6], addr3: [u8; 6]) -> Vec<u8> {
+        let mut frame = control.to_vec();
+        frame.extend_from_slice(&[0x00, 0x00]);
+        frame.extend_from_slice(&addr1);
+        frame.extend_from_slice(&addr2);
+        frame.extend_from_slice(&addr3);
+        frame.extend_from_slice(&[0x00, 0x00]);
+        frame
+    }
+    
+    #[test]
+    fn test_association_graph_edges() {
+        let mut graph = AssociationGraph::new();
+        
+        let mut beacon = frame([0x80, 0x00], [0xFF; 6], AP, AP);
+        beacon.extend_from_slice(&[0x00; 12]);
+        beacon.extend_from_slice(&[0x00, 0x06]);
+        beacon.extend_from_slice(b"\"lab\"");
+        beacon.push(b'!');
+        assert!(graph.process_frame(&beacon));
+        
+        // Phone associates and the AP accepts; the laptop is refused
+        assert!(graph.process_frame(&frame([0x00, 0x00], AP, PHONE, AP)));
+        let mut accepted = frame([0x10, 0x00], PHONE, AP, AP);
+        accepted.extend_from_slice(&[0x01, 0x00, 0x00, 0x00, 0x01, 0xC0]);
+        assert!(graph.process_frame(&accepted));
+        let mut refused = frame([0x10, 0x00], LAPTOP, AP, AP);
+        refused.extend_from_slice(&[0x01, 0x00, 0x11, 0x00, 0x00, 0x00]);
+        assert!(!graph.process_frame(&refused));
+        
+        // The laptop is seen in data instead, both directions
+        assert!(graph.process_frame(&frame([0x08, 0x01], AP, LAPTOP, [0x01; 6])));
+        assert!(graph.process_frame(&frame([0x08, 0x02], LAPTOP, AP, [0x01; 6])));
+        
+        // Group-addressed data and control frames add nothing
+        assert!(!graph.process_frame(&frame([0x08, 0x02], [0xFF; 6], AP, AP)));
+        assert!(!graph.process_frame(&[0xD4, 0x00, 0x00, 0x00, 0x66, 0x77, 0x88, 0x99, 0xAA, 0x01]));
+        
+        let edges: Vec<_> = graph.edges().iter().map(|edge| (edge.client, edge.frames)).collect();
+        assert_eq!(edges, [(MacAddress::new(PHONE), 2), (MacAddress::new(LAPTOP), 2)]);
+        assert_eq!(graph.clients_of(MacAddress::new(AP)).len(), 2);
+        
+        graph.set_vendor(MacAddress::new(PHONE), "Acme");
+        let nodes = graph.nodes();
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[0].role, NodeRole::AccessPoint);
+        assert_eq!(nodes[0].ssid.as_deref(), Some("\"lab\"!"));
+        
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph associations {\n"));
+        assert!(dot.contains("\"00:11:22:33:44:55\" [label=\"00:11:22:33:44:55\\n\\\"lab\\\"!\", shape=box];"));
+        assert!(dot.contains("\"66:77:88:99:AA:01\" [label=\"66:77:88:99:AA:01\\nAcme\", shape=ellipse];"));
+        assert!(dot.contains("\"00:11:22:33:44:55\" -- \"66:77:88:99:AA:02\" [label=\"2\"];"));
+        
+        let json = graph.to_json();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(json["edges"][0]["client"], "66:77:88:99:AA:01");
+        assert_eq!(json["nodes"][1]["vendor"], "Acme");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,151 @@
+//! Direct BPF device injection for macOS
+//! 
+//! libpcap's `sendpacket` does not reliably transmit 802.11 frames on macOS.
+//! This module writes frames straight to a `/dev/bpfN` device bound to the
+//! interface, and is used by `PacketInjector` as a fallback when pcap fails.
+
+use crate::core::packet;
+use crate::{DeauthError, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::os::unix::io::AsRawFd;
+use tracing::{debug, info};
+
+/// Data link type: IEEE 802.11 frames without a radiotap header
+pub const DLT_IEEE802_11: u32 = 105;
+
+/// Data link type: IEEE 802.11 frames with a radiotap header
+pub const DLT_IEEE802_11_RADIO: u32 = 127;
+
+// BPF ioctl requests from <net/bpf.h>
+const BIOCSETIF: libc::c_ulong = 0x8020_426C;
+const BIOCGDLT: libc::c_ulong = 0x4004_426A;
+const BIOCSDLT: libc::c_ulong = 0x8004_4278;
+const BIOCSHDRCMPLT: libc::c_ulong = 0x8004_4275;
+
+/// `struct ifreq` as used by `BIOCSETIF`; only the name is read
+#[repr(C)]
+#[allow(dead_code)] // Fields are read by the kernel
+struct IfReq {
+    name: [u8; libc::IFNAMSIZ],
+    data: [u8; 16],
+}
+
+/// Prepare a radiotap-encapsulated frame for writing to a BPF device with link type `dlt`
+///
+/// Radiotap devices take the frame verbatim; plain 802.11 devices get it with
+/// the radiotap header stripped.
+pub fn bpf_write_buffer(frame: &[u8], dlt: u32) -> Result<&[u8]> {
+    let stripped = packet::strip_radiotap(frame)
+        .ok_or_else(|| DeauthError::InjectionError("frame has no valid radiotap header".to_string()))?;
+    
+    match dlt {
+        DLT_IEEE802_11_RADIO => Ok(frame),
+        DLT_IEEE802_11 => Ok(stripped),
+        other => Err(DeauthError::InjectionError(format!(
+            "BPF link type {} does not carry 802.11 frames",
+            other
+        ))),
+    }
+}
+
+/// BPF device bound to a wireless interface for writing
+pub struct BpfWriter {
+    file: File,
+    path: String,
+    dlt: u32,
+}
+
+impl BpfWriter {
+    /// Open `path` (e.g. `/dev/bpf3`) and bind it to `interface_name`
+    ///
+    /// Radiotap is requested so frames go out with their header intact; if
+    /// the driver refuses, the device's current link type is used.
+    pub fn open(path: &str, interface_name: &str) -> Result<Self> {
+        info!("Opening BPF device {} for {}", path, interface_name);
+        
+        let file = OpenOptions::new().write(true).open(path)?;
+        let fd = file.as_raw_fd();
+        
+        if interface_name.len() >= libc::IFNAMSIZ {
+            return Err(DeauthError::InterfaceError(format!("Interface name {} is too long", interface_name)));
+        }
+        
+        let mut request = IfReq {
+            name: [0; libc::IFNAMSIZ],
+            data: [0; 16],
+        };
+        request.name[..interface_name.len()].copy_from_slice(interface_name.as_bytes());
+        
+        // SAFETY: fd is an open BPF device and each argument matches the request's type
+        unsafe {
+            if libc::ioctl(fd, BIOCSETIF, &request) < 0 {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            
+            // Frames carry their own addresses
+            let complete: libc::c_uint = 1;
+            if libc::ioctl(fd, BIOCSHDRCMPLT, &complete) < 0 {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            
+            let radiotap: libc::c_uint = DLT_IEEE802_11_RADIO;
+            if libc::ioctl(fd, BIOCSDLT, &radiotap) < 0 {
+                debug!("{} does not accept radiotap frames", interface_name);
+            }
+        }
+        
+        let mut dlt: libc::c_uint = 0;
+        // SAFETY: as above, BIOCGDLT writes a single u_int
+        if unsafe { libc::ioctl(fd, BIOCGDLT, &mut dlt) } < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        
+        debug!("BPF device {} bound to {} with link type {}", path, interface_name, dlt);
+        Ok(Self {
+            file,
+            path: path.to_string(),
+            dlt,
+        })
+    }
+    
+    /// Link type frames are written with
+    pub fn link_type(&self) -> u32 {
+        self.dlt
+    }
+    
+    /// Write a radiotap-encapsulated frame, returning the number of bytes written
+    pub fn write_frame(&mut self, frame: &[u8]) -> Result<usize> {
+        let buffer = bpf_write_buffer(frame, self.dlt)?;
+        self.file
+            .write(buffer)
+            .map_err(|e| DeauthError::InjectionError(format!("writing frame to {}: {}", self.path, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::packet::{DeauthPacket, MacAddress, RADIOTAP_HEADER};
+    
+    #[test]
+    fn test_bpf_write_buffer_layout() {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let bytes = DeauthPacket::new(client, ap, ap, 7).to_bytes();
+        
+        // Radiotap devices take the frame as serialized
+        let radio = bpf_write_buffer(&bytes, DLT_IEEE802_11_RADIO).expect("Should accept frame");
+        assert_eq!(radio, bytes.as_ref());
+        
+        // Plain 802.11 devices start at the frame control field
+        let plain = bpf_write_buffer(&bytes, DLT_IEEE802_11).expect("Should accept frame");
+        assert_eq!(plain.len(), bytes.len() - RADIOTAP_HEADER.len());
+        assert_eq!(&plain[0..2], &[0xC0, 0x00]);
+        assert_eq!(&plain[4..10], &client.bytes());
+        
+        // Ethernet devices and frames without radiotap are rejected
+        assert!(bpf_write_buffer(&bytes, 1).is_err());
+        assert!(bpf_write_buffer(&bytes[..2], DLT_IEEE802_11_RADIO).is_err());
+    }
+}
\ No newline at end of file
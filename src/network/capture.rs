@@ -1,9 +1,18 @@
 //! Packet capture functionality for monitoring and analysis
 
 use crate::{DeauthError, Result};
-use pcap::{Capture, Device};
-use std::sync::Arc;
-use tracing::{debug, info};
+use crate::core::{event::Event, packet, packet::{DeauthPacket, MacAddress}};
+use flate2::read::GzDecoder;
+use pcap::{Activated, Capture, Device};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+use tokio::sync::broadcast;
+use tracing::{debug, error, info, warn};
 
 /// Packet capture result
 #[derive(Debug, Clone)]
@@ -13,40 +22,157 @@ pub struct CaptureResult {
     pub length: usize,
 }
 
+/// Snapshot length for full captures
+const FULL_SNAPLEN: i32 = 65535;
+
+/// Snapshot length for scan captures: radiotap, management header and the
+/// leading information elements (SSID, rates, RSN) that scans parse
+const SCAN_SNAPLEN: i32 = 512;
+
+/// BPF filter keeping beacons, probes, deauthentications and other management frames
+const MANAGEMENT_FILTER: &str = "type mgt";
+
+/// Leading bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Re-open attempts a supervised capture makes after a device error
+pub const DEFAULT_MAX_RESTARTS: u32 = 5;
+
+/// Delay before the first re-open attempt; doubled after each failure
+pub const DEFAULT_RESTART_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Attempts at picking an unused name for a decompressed capture
+const TEMP_FILE_ATTEMPTS: u32 = 16;
+
 /// High-performance packet capture
 pub struct PacketCapture {
-    capture: Arc<std::sync::Mutex<Capture<pcap::Active>>>,
+    capture: Arc<Mutex<Capture<dyn Activated>>>,
     interface_name: String,
+    
+    // Declared after `capture` so the reader closes the file before it is removed
+    replay_file: Option<TempFile>,
+}
+
+/// Decompressed replay file, removed when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Forget a file that has already been removed
+    fn disarm(mut self) {
+        self.0 = PathBuf::new();
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if self.0.as_os_str().is_empty() {
+            return;
+        }
+        
+        if let Err(e) = std::fs::remove_file(&self.0) {
+            warn!("Could not remove temporary capture {}: {}", self.0.display(), e);
+        }
+    }
 }
 
 impl PacketCapture {
     /// Create a new packet capture instance
     pub fn new(interface_name: &str) -> Result<Self> {
+        Self::open(interface_name, FULL_SNAPLEN, None)
+    }
+    
+    /// Create a capture that only returns management frames
+    ///
+    /// Scans need nothing beyond beacons, probes and deauthentications, so
+    /// data frames are dropped in the kernel and captures are truncated after
+    /// the information elements, saving CPU and memory.
+    pub fn new_for_scanning(interface_name: &str) -> Result<Self> {
+        Self::open(interface_name, SCAN_SNAPLEN, Some(MANAGEMENT_FILTER))
+    }
+    
+    fn open(interface_name: &str, snaplen: i32, filter: Option<&str>) -> Result<Self> {
         info!("Creating packet capture for interface: {}", interface_name);
         
         let device = Device::list()
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to list devices: {}", e)))?
+            .map_err(|e| DeauthError::interface_from_pcap(e, "listing devices"))?
             .into_iter()
             .find(|d| d.name == interface_name)
             .ok_or_else(|| DeauthError::InterfaceError(format!("Interface {} not found", interface_name)))?;
         
         let capture = Capture::from_device(device)
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to create capture: {}", e)))?
+            .map_err(|e| DeauthError::interface_from_pcap(e, format!("opening device {}", interface_name)))?
             .promisc(true)
-            .snaplen(65535)
+            .snaplen(snaplen)
             .timeout(100)
             .open()
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to open capture: {}", e)))?;
+            .map_err(|e| DeauthError::interface_from_pcap(e, format!("activating capture on {}", interface_name)))?;
+        
+        Self::with_filter(capture.into(), interface_name, filter)
+    }
+    
+    /// Create a capture that only returns frames transmitted by `transmitters`
+    ///
+    /// Following access points' signal needs no more than the radiotap header
+    /// of the frames they send, so one capture serves any number of them.
+    pub fn new_for_transmitters(interface_name: &str, transmitters: &[MacAddress]) -> Result<Self> {
+        Self::open(interface_name, SCAN_SNAPLEN, Some(&transmitter_filter(transmitters)))
+    }
+    
+    /// Replay the frames of a pcap file
+    ///
+    /// Gzip-compressed captures such as `.pcap.gz`, recognized by extension or
+    /// magic bytes, are decompressed to a temporary file for pcap's offline
+    /// reader. Reads return `Ok(None)` once the file is exhausted.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        info!("Replaying capture file: {}", name);
+        
+        let open_error = |e| DeauthError::interface_from_pcap(e, format!("opening capture file {}", name));
+        if !is_gzip(path)? {
+            let capture = Capture::from_file(path).map_err(open_error)?;
+            return Self::with_filter(capture.into(), &name, None);
+        }
+        
+        let decompressed = TempFile(decompress_to_temp(path)?);
+        let capture = Capture::from_file(&decompressed.0).map_err(open_error)?;
+        let mut replay = Self::with_filter(capture.into(), &name, None)?;
+        
+        // The reader holds the file open, so on Unix it can be unlinked right
+        // away; where an open file cannot be removed it goes with the capture
+        if std::fs::remove_file(&decompressed.0).is_err() {
+            replay.replay_file = Some(decompressed);
+        } else {
+            decompressed.disarm();
+        }
+        Ok(replay)
+    }
+    
+    /// Wrap an activated capture, optionally restricting it with a BPF filter
+    fn with_filter(mut capture: Capture<dyn Activated>, interface_name: &str, filter: Option<&str>) -> Result<Self> {
+        if let Some(filter) = filter {
+            apply_filter(&mut capture, interface_name, filter)?;
+        }
         
         Ok(Self {
-            capture: Arc::new(std::sync::Mutex::new(capture)),
+            capture: Arc::new(Mutex::new(capture)),
             interface_name: interface_name.to_string(),
+            replay_file: None,
         })
     }
     
+    /// Only capture frames sent to or from `station`
+    ///
+    /// Replaces any filter already applied, e.g. the management-frame filter
+    /// of a scanning capture. Pair with `StationTracker` to follow one device.
+    pub fn set_station_filter(&self, station: MacAddress) -> Result<()> {
+        let mut capture = lock_recovering(&self.capture, &self.interface_name);
+        apply_filter(&mut capture, &self.interface_name, &station_filter(station))
+    }
+    
     /// Capture a single packet
     pub fn capture_packet(&self) -> Result<Option<CaptureResult>> {
-        let mut capture = self.capture.lock().unwrap();
+        let mut capture = lock_recovering(&self.capture, &self.interface_name);
         
         match capture.next_packet() {
             Ok(packet) => {
@@ -59,11 +185,12 @@ impl PacketCapture {
                 debug!("Captured packet: {} bytes", result.length);
                 Ok(Some(result))
             }
-            Err(pcap::Error::TimeoutExpired) => {
+            // The end of a capture file reads like a timeout
+            Err(pcap::Error::TimeoutExpired | pcap::Error::NoMorePackets) => {
                 Ok(None)
             }
             Err(e) => {
-                Err(DeauthError::InterfaceError(format!("Capture error: {}", e)))
+                Err(DeauthError::interface_from_pcap(e, format!("reading packet on {}", self.interface_name)))
             }
         }
     }
@@ -114,10 +241,986 @@ impl PacketCapture {
     }
 }
 
+/// Source of captured frames, such as a pcap handle or a loopback channel
+pub trait CaptureSource: Send {
+    /// Capture a single frame; `Ok(None)` when none arrived before the timeout
+    fn capture_packet(&self) -> Result<Option<CaptureResult>>;
+}
+
+impl CaptureSource for PacketCapture {
+    fn capture_packet(&self) -> Result<Option<CaptureResult>> {
+        PacketCapture::capture_packet(self)
+    }
+}
+
+/// Opens a fresh capture source for a `SupervisedCapture`
+pub type CaptureOpener = Box<dyn Fn() -> Result<Box<dyn CaptureSource>> + Send + Sync>;
+
+/// Capture source that re-opens its device after an error
+///
+/// Resetting a monitor interface (e.g. by NetworkManager) leaves the old pcap
+/// handle failing every read. On a read error the source is re-opened up to
+/// `max_restarts` times with exponential backoff, publishing an
+/// `Event::CaptureRestarted` once it is back; the read that failed returns
+/// `Ok(None)` like a timeout. If every attempt fails, the original error is
+/// returned. While one caller re-opens the source, other reads time out at
+/// once instead of waiting out the backoff.
+pub struct SupervisedCapture {
+    interface_name: String,
+    open: CaptureOpener,
+    source: Mutex<Box<dyn CaptureSource>>,
+    max_restarts: u32,
+    backoff: Duration,
+    restarts: AtomicU64,
+    restarting: AtomicBool,
+    events: Option<broadcast::Sender<Event>>,
+}
+
+impl SupervisedCapture {
+    /// Open a source with `open` and supervise it
+    pub fn new<F>(interface_name: &str, open: F) -> Result<Self>
+    where
+        F: Fn() -> Result<Box<dyn CaptureSource>> + Send + Sync + 'static,
+    {
+        let source = open()?;
+        
+        Ok(Self {
+            interface_name: interface_name.to_string(),
+            open: Box::new(open),
+            source: Mutex::new(source),
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            backoff: DEFAULT_RESTART_BACKOFF,
+            restarts: AtomicU64::new(0),
+            restarting: AtomicBool::new(false),
+            events: None,
+        })
+    }
+    
+    /// Supervise a pcap capture on `interface_name`
+    pub fn pcap(interface_name: &str) -> Result<Self> {
+        let name = interface_name.to_string();
+        Self::new(interface_name, move || {
+            PacketCapture::new(&name).map(|capture| Box::new(capture) as Box<dyn CaptureSource>)
+        })
+    }
+    
+    /// Set the number of re-open attempts per error and the initial backoff
+    pub fn with_restart_policy(mut self, max_restarts: u32, backoff: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.backoff = backoff;
+        self
+    }
+    
+    /// Publish an `Event::CaptureRestarted` after each successful re-open
+    pub fn with_events(mut self, events: broadcast::Sender<Event>) -> Self {
+        self.events = Some(events);
+        self
+    }
+    
+    /// Number of times the source has been re-opened
+    pub fn restarts(&self) -> u64 {
+        self.restarts.load(Ordering::Relaxed)
+    }
+    
+    /// Re-open the source after `error`, unless another caller already is
+    fn restart(&self, error: DeauthError) -> Result<()> {
+        if self.restarting.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        
+        let result = self.reopen(error);
+        self.restarting.store(false, Ordering::Release);
+        result
+    }
+    
+    /// Re-open the source, backing off between attempts
+    ///
+    /// The source lock is only taken to swap in the re-opened source, never
+    /// while sleeping.
+    fn reopen(&self, error: DeauthError) -> Result<()> {
+        let mut delay = self.backoff;
+        
+        for attempt in 1..=self.max_restarts {
+            warn!("Capture on {} failed ({}), re-opening in {:?} (attempt {}/{})",
+                self.interface_name, error, delay, attempt, self.max_restarts);
+            std::thread::sleep(delay);
+            
+            match (self.open)() {
+                Ok(reopened) => {
+                    *lock_recovering(&self.source, &self.interface_name) = reopened;
+                    self.restarts.fetch_add(1, Ordering::Relaxed);
+                    info!("Capture on {} restarted after {} attempt(s)", self.interface_name, attempt);
+                    
+                    if let Some(ref events) = self.events {
+                        let _ = events.send(Event::CaptureRestarted {
+                            interface: self.interface_name.clone(),
+                            attempt,
+                        });
+                    }
+                    return Ok(());
+                }
+                Err(e) => debug!("Re-opening capture on {} failed: {}", self.interface_name, e),
+            }
+            
+            delay = delay.saturating_mul(2);
+        }
+        
+        error!("Giving up on capture on {} after {} attempt(s)", self.interface_name, self.max_restarts);
+        Err(error)
+    }
+}
+
+impl CaptureSource for SupervisedCapture {
+    fn capture_packet(&self) -> Result<Option<CaptureResult>> {
+        // The failed source stays in place until it is replaced
+        if self.restarting.load(Ordering::Acquire) {
+            return Ok(None);
+        }
+        
+        let result = lock_recovering(&self.source, &self.interface_name).capture_packet();
+        match result {
+            Err(e) => self.restart(e).map(|()| None),
+            result => result,
+        }
+    }
+}
+
+/// Whether `path` holds a gzip stream, judged by extension or magic bytes
+fn is_gzip(path: &Path) -> Result<bool> {
+    if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("gz")) {
+        return Ok(true);
+    }
+    
+    let mut magic = [0u8; 2];
+    let read = File::open(path)?.read(&mut magic)?;
+    Ok(read == magic.len() && magic == GZIP_MAGIC)
+}
+
+/// Decompress the gzip file at `path` into a fresh temporary file
+///
+/// The file gets a random name and is created exclusively, so a file or
+/// symlink planted in the shared temporary directory is never written through.
+fn decompress_to_temp(path: &Path) -> Result<PathBuf> {
+    let (target, mut output) = create_temp_file()?;
+    let mut decoder = GzDecoder::new(File::open(path)?);
+    let bytes = std::io::copy(&mut decoder, &mut output).map_err(|e| {
+        let _ = std::fs::remove_file(&target);
+        DeauthError::ConfigError(format!("Failed to decompress {}: {}", path.display(), e))
+    })?;
+    
+    debug!("Decompressed {} to {} ({} bytes)", path.display(), target.display(), bytes);
+    Ok(target)
+}
+
+/// Create a new, owner-only file with a random name in the temporary directory
+fn create_temp_file() -> Result<(PathBuf, File)> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    
+    for _ in 0..TEMP_FILE_ATTEMPTS {
+        let target = std::env::temp_dir().join(format!("wifi-deauther-replay-{:016x}.pcap", rand::random::<u64>()));
+        match options.open(&target) {
+            Ok(file) => return Ok((target, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    
+    Err(DeauthError::ConfigError("Could not create a temporary capture file".to_string()))
+}
+
+/// BPF filter matching frames that carry `station` in addr1, addr2 or addr3
+pub fn station_filter(station: MacAddress) -> String {
+    format!("wlan addr1 {0} or wlan addr2 {0} or wlan addr3 {0}", station)
+}
+
+/// BPF filter matching frames any of `transmitters` sent (addr2)
+pub fn transmitter_filter(transmitters: &[MacAddress]) -> String {
+    transmitters
+        .iter()
+        .map(|transmitter| format!("wlan addr2 {}", transmitter))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// Compile `filter` and install it on `capture`
+fn apply_filter(capture: &mut Capture<dyn Activated>, interface_name: &str, filter: &str) -> Result<()> {
+    capture.filter(filter, true)
+        .map_err(|e| DeauthError::interface_from_pcap(e, format!("applying filter \"{}\" on {}", filter, interface_name)))?;
+    debug!("Applied capture filter \"{}\" on {}", filter, interface_name);
+    Ok(())
+}
+
+/// Lock a capture mutex, recovering the guard if a previous holder panicked
+///
+/// A panic in a capture consumer leaves the pcap handle itself intact, so the
+/// poison flag is cleared instead of failing every later capture.
+fn lock_recovering<'a, T>(mutex: &'a Mutex<T>, interface_name: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        warn!("Capture lock on {} was poisoned by a panic, recovering", interface_name);
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
 /// Capture statistics
 #[derive(Debug, Clone)]
 pub struct CaptureStats {
     pub packets_captured: u64,
     pub packets_dropped: u64,
     pub bytes_captured: u64,
+}
+
+/// Capture analyzer that counts deauthentication frames seen on the air per BSSID
+///
+/// This observes our own frames as well as anyone else's, so sent and seen
+/// counts can be correlated to gauge how much of an attack actually airs.
+pub struct DeauthWatcher {
+    counts: HashMap<MacAddress, u64>,
+    events: Option<broadcast::Sender<Event>>,
+}
+
+impl DeauthWatcher {
+    /// Create a new watcher
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+            events: None,
+        }
+    }
+    
+    /// Create a watcher that publishes an `Event::DeauthObserved` for each frame seen
+    pub fn with_events(events: broadcast::Sender<Event>) -> Self {
+        Self {
+            counts: HashMap::new(),
+            events: Some(events),
+        }
+    }
+    
+    /// Process a radiotap-encapsulated capture as delivered by a monitor-mode interface
+    pub fn process_capture(&mut self, capture: &CaptureResult) -> bool {
+        match packet::strip_radiotap(&capture.data) {
+            Some(frame) => self.process_frame(frame),
+            None => false,
+        }
+    }
+    
+    /// Process a raw IEEE 802.11 frame, returning true if it was a deauthentication
+    pub fn process_frame(&mut self, frame: &[u8]) -> bool {
+        let Some(DeauthPacket { destination, source, bssid, reason_code, .. }) = DeauthPacket::parse(frame) else {
+            return false;
+        };
+        
+        *self.counts.entry(bssid).or_insert(0) += 1;
+        debug!("Observed deauth from {} to {} (BSSID {}, reason {})", source, destination, bssid, reason_code);
+        
+        if let Some(ref events) = self.events {
+            // No subscribers is not an error
+            let _ = events.send(Event::DeauthObserved {
+                bssid,
+                source,
+                destination,
+                reason_code,
+            });
+        }
+        
+        true
+    }
+    
+    /// Number of deauthentication frames seen for a BSSID
+    pub fn deauths_seen(&self, bssid: MacAddress) -> u64 {
+        self.counts.get(&bssid).copied().unwrap_or(0)
+    }
+    
+    /// Total deauthentication frames seen across all BSSIDs
+    pub fn total_seen(&self) -> u64 {
+        self.counts.values().sum()
+    }
+    
+    /// Clear all counters
+    pub fn reset(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl Default for DeauthWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Frame control byte of an association request
+const ASSOC_REQUEST: u8 = 0x00;
+
+/// Frame control byte of a reassociation request
+const REASSOC_REQUEST: u8 = 0x20;
+
+/// Capture analyzer estimating how quickly clients reconnect after being deauthenticated
+///
+/// The interval runs from the first deauthentication addressed to a client to
+/// its next (re)association request. Clients that stay off never produce an
+/// interval; a short one means the attack is not keeping them off. Broadcast
+/// deauthentications name no client and are not tracked.
+#[derive(Debug, Default)]
+pub struct ReconnectionEstimator {
+    deauthenticated: HashMap<MacAddress, SystemTime>,
+    intervals: HashMap<MacAddress, Vec<Duration>>,
+}
+
+impl ReconnectionEstimator {
+    /// Create a new estimator
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Process a radiotap-encapsulated capture as delivered by a monitor-mode interface
+    pub fn process_capture(&mut self, capture: &CaptureResult) {
+        if let Some(frame) = packet::strip_radiotap(&capture.data) {
+            self.process_frame(frame, capture.timestamp);
+        }
+    }
+    
+    /// Process a raw IEEE 802.11 frame captured at `timestamp`
+    pub fn process_frame(&mut self, frame: &[u8], timestamp: SystemTime) {
+        if let Some(deauth) = DeauthPacket::parse(frame) {
+            if packet::is_unicast(deauth.destination) {
+                self.deauthenticated.entry(deauth.destination).or_insert(timestamp);
+            }
+            return;
+        }
+        
+        if frame.len() < packet::MGMT_HEADER_LEN || !matches!(frame[0], ASSOC_REQUEST | REASSOC_REQUEST) {
+            return;
+        }
+        
+        let mut source = [0u8; 6];
+        source.copy_from_slice(&frame[10..16]);
+        let client = MacAddress::new(source);
+        
+        if let Some(deauth_time) = self.deauthenticated.remove(&client) {
+            // Out-of-order timestamps count as an immediate reconnection
+            let interval = timestamp.duration_since(deauth_time).unwrap_or(Duration::ZERO);
+            debug!("Client {} reconnected {:?} after deauthentication", client, interval);
+            self.intervals.entry(client).or_default().push(interval);
+        }
+    }
+    
+    /// Average time a client took to reconnect after deauthentication
+    ///
+    /// `None` if the client has not reconnected after any observed deauthentication.
+    pub fn reconnection_interval(&self, client: MacAddress) -> Option<Duration> {
+        let intervals = self.intervals.get(&client)?;
+        let count = u32::try_from(intervals.len()).ok()?;
+        Some(intervals.iter().sum::<Duration>() / count)
+    }
+    
+    /// Number of reconnections observed for a client
+    pub fn reconnections(&self, client: MacAddress) -> usize {
+        self.intervals.get(&client).map_or(0, Vec::len)
+    }
+    
+    /// Clear all observations
+    pub fn reset(&mut self) {
+        self.deauthenticated.clear();
+        self.intervals.clear();
+    }
+}
+
+/// Capture analyzer following one station and the BSSIDs it talks to
+///
+/// Frames not involving the station are ignored, so it can be fed an
+/// unfiltered capture as well as one narrowed with `set_station_filter`.
+/// Control frames carry no BSSID and are not attributed.
+#[derive(Debug)]
+pub struct StationTracker {
+    station: MacAddress,
+    bssids: HashMap<MacAddress, u64>,
+    frames_seen: u64,
+}
+
+impl StationTracker {
+    /// Create a tracker for `station`
+    pub fn new(station: MacAddress) -> Self {
+        Self {
+            station,
+            bssids: HashMap::new(),
+            frames_seen: 0,
+        }
+    }
+    
+    /// Station being tracked
+    pub fn station(&self) -> MacAddress {
+        self.station
+    }
+    
+    /// Process a radiotap-encapsulated capture as delivered by a monitor-mode interface
+    pub fn process_capture(&mut self, capture: &CaptureResult) -> bool {
+        match packet::strip_radiotap(&capture.data) {
+            Some(frame) => self.process_frame(frame),
+            None => false,
+        }
+    }
+    
+    /// Process a raw IEEE 802.11 frame, returning true if it involves the station
+    pub fn process_frame(&mut self, frame: &[u8]) -> bool {
+        if frame.len() < packet::MGMT_HEADER_LEN {
+            return false;
+        }
+        
+        let address = |offset: usize| {
+            let mut bytes = [0u8; 6];
+            bytes.copy_from_slice(&frame[offset..offset + 6]);
+            MacAddress::new(bytes)
+        };
+        let (addr1, addr2, addr3) = (address(4), address(10), address(16));
+        if ![addr1, addr2, addr3].contains(&self.station) {
+            return false;
+        }
+        self.frames_seen += 1;
+        
+        let frame_type = (frame[0] >> 2) & 0x03;
+        let to_ds = frame[1] & 0x01 != 0;
+        let from_ds = frame[1] & 0x02 != 0;
+        let bssid = match (frame_type, to_ds, from_ds) {
+            // Management frames always carry the BSSID in addr3
+            (0, _, _) | (2, false, false) => Some(addr3),
+            (2, true, false) => Some(addr1),
+            (2, false, true) => Some(addr2),
+            // Control frames and WDS links
+            _ => None,
+        };
+        
+        if let Some(bssid) = bssid.filter(|&bssid| bssid != self.station && packet::is_unicast(bssid)) {
+            *self.bssids.entry(bssid).or_insert(0) += 1;
+        }
+        
+        true
+    }
+    
+    /// BSSIDs the station exchanged frames with, most frames first
+    pub fn bssids(&self) -> Vec<(MacAddress, u64)> {
+        let mut bssids: Vec<_> = self.bssids.iter().map(|(&bssid, &frames)| (bssid, frames)).collect();
+        bssids.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        bssids
+    }
+    
+    /// Frames seen to or from the station
+    pub fn frames_seen(&self) -> u64 {
+        self.frames_seen
+    }
+    
+    /// Clear all observations
+    pub fn reset(&mut self) {
+        self.bssids.clear();
+        self.frames_seen = 0;
+    }
+}
+
+/// Frame control flag marking a retransmission
+const RETRY_FLAG: u8 = 0x08;
+
+/// Capture analyzer recognizing retransmitted frames
+///
+/// A retransmission has the Retry bit set and repeats the sequence control
+/// field of the frame it replaces, so a frame is a duplicate when it is a
+/// retry and its transmitter's previous frame carried the same sequence
+/// control. Control frames have no sequence number and are never duplicates.
+#[derive(Debug, Default)]
+pub struct DuplicateDetector {
+    last_sequence: HashMap<MacAddress, u16>,
+    duplicates: u64,
+}
+
+impl DuplicateDetector {
+    /// Create a new detector
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Process a radiotap-encapsulated capture, returning true if it is a duplicate
+    pub fn process_capture(&mut self, capture: &CaptureResult) -> bool {
+        match packet::strip_radiotap(&capture.data) {
+            Some(frame) => self.process_frame(frame),
+            None => false,
+        }
+    }
+    
+    /// Process a raw IEEE 802.11 frame, returning true if it is a duplicate
+    pub fn process_frame(&mut self, frame: &[u8]) -> bool {
+        let frame_type = frame.first().map_or(1, |control| (control >> 2) & 0x03);
+        if frame_type == 1 || frame.len() < packet::MGMT_HEADER_LEN {
+            return false;
+        }
+        
+        let mut transmitter = [0u8; 6];
+        transmitter.copy_from_slice(&frame[10..16]);
+        let sequence = u16::from_le_bytes([frame[22], frame[23]]);
+        let retry = frame[1] & RETRY_FLAG != 0;
+        
+        let previous = self.last_sequence.insert(MacAddress::new(transmitter), sequence);
+        let duplicate = retry && previous == Some(sequence);
+        if duplicate {
+            self.duplicates += 1;
+            debug!("Duplicate frame from {} (sequence {})", MacAddress::new(transmitter), sequence >> 4);
+        }
+        
+        duplicate
+    }
+    
+    /// Duplicates identified so far
+    pub fn duplicates_seen(&self) -> u64 {
+        self.duplicates
+    }
+    
+    /// Forget all transmitters and counts
+    pub fn reset(&mut self) {
+        self.last_sequence.clear();
+        self.duplicates = 0;
+    }
+}
+
+/// Capture source that drops retransmitted frames
+///
+/// Retries inflate capture files and skew frame counts. Deduplication can be
+/// switched off at any time, in which case every frame passes through.
+pub struct DedupCapture {
+    source: Box<dyn CaptureSource>,
+    detector: Mutex<DuplicateDetector>,
+    enabled: AtomicBool,
+}
+
+impl DedupCapture {
+    /// Deduplicate frames captured from `source`
+    pub fn new(source: Box<dyn CaptureSource>) -> Self {
+        Self {
+            source,
+            detector: Mutex::new(DuplicateDetector::new()),
+            enabled: AtomicBool::new(true),
+        }
+    }
+    
+    /// Turn deduplication on or off
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+    
+    /// Check if duplicates are being dropped
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+    
+    /// Duplicate frames dropped so far
+    pub fn duplicates_dropped(&self) -> u64 {
+        lock_recovering(&self.detector, "dedup").duplicates_seen()
+    }
+}
+
+impl CaptureSource for DedupCapture {
+    fn capture_packet(&self) -> Result<Option<CaptureResult>> {
+        loop {
+            let Some(capture) = self.source.capture_packet()? else {
+                return Ok(None);
+            };
+            
+            if !self.is_enabled() || !lock_recovering(&self.detector, "dedup").process_capture(&capture) {
+                return Ok(Some(capture));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn frame(frame_control: u8, destination: [u8; 6], bssid: [u8; 6], reason: u16) -> Vec<u8> {
+        let mut frame = vec![frame_control, 0x00, 0x3A, 0x01];
+        frame.extend_from_slice(&destination);
+        frame.extend_from_slice(&bssid);
+        frame.extend_from_slice(&bssid);
+        frame.extend_from_slice(&[0x00, 0x00]);
+        frame.extend_from_slice(&reason.to_le_bytes());
+        frame
+    }
+    
+    #[test]
+    fn test_deauth_watcher_counts_per_bssid() {
+        let ap1 = [0x00, 0x11, 0x22, 0x33, 0x44, 0x01];
+        let ap2 = [0x00, 0x11, 0x22, 0x33, 0x44, 0x02];
+        let client = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+        let (tx, mut rx) = broadcast::channel(16);
+        let mut watcher = DeauthWatcher::with_events(tx);
+        
+        assert!(watcher.process_frame(&frame(0xC0, client, ap1, 7)));
+        assert!(watcher.process_frame(&frame(0xC0, [0xFF; 6], ap1, 3)));
+        assert!(watcher.process_frame(&frame(0xC0, client, ap2, 7)));
+        
+        // Beacons, disassociations and truncated frames are ignored
+        assert!(!watcher.process_frame(&frame(0x80, [0xFF; 6], ap1, 0)));
+        assert!(!watcher.process_frame(&frame(0xA0, client, ap2, 8)));
+        assert!(!watcher.process_frame(&frame(0xC0, client, ap2, 7)[..20]));
+        
+        assert_eq!(watcher.deauths_seen(MacAddress::new(ap1)), 2);
+        assert_eq!(watcher.deauths_seen(MacAddress::new(ap2)), 1);
+        assert_eq!(watcher.deauths_seen(MacAddress::new(client)), 0);
+        assert_eq!(watcher.total_seen(), 3);
+        
+        match rx.try_recv().expect("Should publish event") {
+            Event::DeauthObserved { bssid, destination, reason_code, .. } => {
+                assert_eq!(bssid, MacAddress::new(ap1));
+                assert_eq!(destination, MacAddress::new(client));
+                assert_eq!(reason_code, 7);
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_deauth_watcher_strips_radiotap() {
+        let mut data = vec![0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00];
+        data.extend(frame(0xC0, [0xFF; 6], [0x00, 0x11, 0x22, 0x33, 0x44, 0x01], 7));
+        let capture = CaptureResult {
+            timestamp: std::time::SystemTime::now(),
+            length: data.len(),
+            data,
+        };
+        
+        let mut watcher = DeauthWatcher::new();
+        assert!(watcher.process_capture(&capture));
+        assert_eq!(watcher.total_seen(), 1);
+    }
+    
+    #[test]
+    fn test_reconnection_interval() {
+        let ap = [0x00, 0x11, 0x22, 0x33, 0x44, 0x01];
+        let client = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+        let stubborn = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xCC];
+        let at = |millis: u64| SystemTime::UNIX_EPOCH + Duration::from_millis(millis);
+        let mut estimator = ReconnectionEstimator::new();
+        
+        // Burst of deauths, reassociation 800 ms after the first one
+        estimator.process_frame(&frame(0xC0, client, ap, 7), at(1_000));
+        estimator.process_frame(&frame(0xC0, client, ap, 7), at(1_100));
+        estimator.process_frame(&frame(0x20, ap, client, 0), at(1_800));
+        assert_eq!(estimator.reconnection_interval(MacAddress::new(client)), Some(Duration::from_millis(800)));
+        
+        // A second round averages in; association without a preceding deauth is ignored
+        estimator.process_frame(&frame(0xC0, client, ap, 7), at(5_000));
+        estimator.process_frame(&frame(0x00, ap, client, 0), at(5_400));
+        estimator.process_frame(&frame(0x00, ap, client, 0), at(9_000));
+        assert_eq!(estimator.reconnections(MacAddress::new(client)), 2);
+        assert_eq!(estimator.reconnection_interval(MacAddress::new(client)), Some(Duration::from_millis(600)));
+        
+        // Clients kept off and broadcast deauths yield no interval
+        estimator.process_frame(&frame(0xC0, stubborn, ap, 7), at(1_000));
+        estimator.process_frame(&frame(0xC0, [0xFF; 6], ap, 7), at(1_000));
+        assert_eq!(estimator.reconnection_interval(MacAddress::new(stubborn)), None);
+        assert_eq!(estimator.reconnection_interval(MacAddress::new([0xFF; 6])), None);
+    }
+    
+    /// Write radiotap frames to a classic little-endian pcap file
+    fn write_pcap(path: &std::path::Path, frames: &[Vec<u8>]) {
+        let mut file = Vec::new();
+        file.extend_from_slice(&0xA1B2_C3D4u32.to_le_bytes());
+        file.extend_from_slice(&2u16.to_le_bytes());
+        file.extend_from_slice(&4u16.to_le_bytes());
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&65535u32.to_le_bytes());
+        file.extend_from_slice(&127u32.to_le_bytes()); // LINKTYPE_IEEE802_11_RADIOTAP
+        
+        for frame in frames {
+            let mut data = packet::RADIOTAP_HEADER.to_vec();
+            data.extend_from_slice(frame);
+            
+            let length = u32::try_from(data.len()).expect("Frame fits in a pcap record");
+            file.extend_from_slice(&[0; 8]);
+            file.extend_from_slice(&length.to_le_bytes());
+            file.extend_from_slice(&length.to_le_bytes());
+            file.extend_from_slice(&data);
+        }
+        
+        std::fs::write(path, file).expect("Should write pcap");
+    }
+    
+    #[test]
+    fn test_replay_gzip_capture() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+        
+        let ap = [0x00, 0x11, 0x22, 0x33, 0x44, 0x01];
+        let client = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+        let base = std::env::temp_dir().join(format!("wifi-deauther-replay-test-{}", std::process::id()));
+        let plain = base.with_extension("pcap");
+        write_pcap(&plain, &[
+            frame(0x80, [0xFF; 6], ap, 0),
+            frame(0x08, client, ap, 0),
+            frame(0xC0, client, ap, 7),
+        ]);
+        
+        // Once named .pcap.gz, once with only the magic bytes to go by
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&std::fs::read(&plain).unwrap()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let named = base.with_extension("pcap.gz");
+        let unnamed = base.with_extension("cap");
+        std::fs::write(&named, &compressed).unwrap();
+        std::fs::write(&unnamed, &compressed).unwrap();
+        
+        let count = |path: &Path| {
+            let capture = PacketCapture::from_file(path).expect("Should open capture file");
+            std::iter::from_fn(|| capture.capture_packet().expect("Should read until the end")).count()
+        };
+        let expected = count(&plain);
+        assert_eq!(expected, 3);
+        assert_eq!(count(&named), expected);
+        assert_eq!(count(&unnamed), expected);
+        
+        for path in [plain, named, unnamed] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+    
+    #[test]
+    fn test_scan_filter_keeps_management_frames() {
+        let ap = [0x00, 0x11, 0x22, 0x33, 0x44, 0x01];
+        let client = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+        let path = std::env::temp_dir().join(format!("wifi-deauther-scan-{}.pcap", std::process::id()));
+        write_pcap(&path, &[
+            frame(0x80, [0xFF; 6], ap, 0), // beacon
+            frame(0x08, client, ap, 0),    // data
+            frame(0x40, [0xFF; 6], ap, 0), // probe request
+            frame(0x88, client, ap, 0),    // QoS data
+            frame(0xC0, client, ap, 7),    // deauthentication
+        ]);
+        
+        let offline = Capture::from_file(&path).expect("Should open pcap");
+        let capture = PacketCapture::with_filter(offline.into(), "offline", Some(MANAGEMENT_FILTER))
+            .expect("Should apply filter");
+        
+        let mut frame_controls = Vec::new();
+        while let Ok(Some(result)) = capture.capture_packet() {
+            let frame = packet::strip_radiotap(&result.data).expect("Should have radiotap header");
+            frame_controls.push(frame[0]);
+        }
+        std::fs::remove_file(&path).ok();
+        
+        assert_eq!(frame_controls, vec![0x80, 0x40, 0xC0]);
+    }
+    
+    /// 802.11 header with explicit frame control and addresses
+    fn header(frame_control: [u8; 2], addr1: [u8; 6], addr2: [u8; 6], addr3: [u8; 6]) -> Vec<u8> {
+        let mut frame = vec![frame_control[0], frame_control[1], 0x00, 0x00];
+        frame.extend_from_slice(&addr1);
+        frame.extend_from_slice(&addr2);
+        frame.extend_from_slice(&addr3);
+        frame.extend_from_slice(&[0x00, 0x00]);
+        frame
+    }
+    
+    #[test]
+    fn test_station_filter_follows_one_client() {
+        let home = [0x00, 0x11, 0x22, 0x33, 0x44, 0x01];
+        let cafe = [0x00, 0x11, 0x22, 0x33, 0x44, 0x02];
+        let client = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+        let other = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xCC];
+        let path = std::env::temp_dir().join(format!("wifi-deauther-station-{}.pcap", std::process::id()));
+        write_pcap(&path, &[
+            header([0x08, 0x01], home, client, home),       // data to the AP, client in addr2
+            header([0x08, 0x02], client, home, home),       // data from the AP, client in addr1
+            header([0x08, 0x01], home, other, home),        // another client's data
+            header([0x40, 0x00], [0xFF; 6], client, [0xFF; 6]), // broadcast probe request
+            header([0x80, 0x00], [0xFF; 6], cafe, cafe),    // beacon
+            header([0x00, 0x00], cafe, client, cafe),       // association request
+            frame(0xC0, other, home, 7),                    // deauthentication of another client
+        ]);
+        
+        let offline = Capture::from_file(&path).expect("Should open pcap");
+        let filter = station_filter(MacAddress::new(client));
+        let capture = PacketCapture::with_filter(offline.into(), "offline", Some(&filter))
+            .expect("Should apply filter");
+        
+        let mut tracker = StationTracker::new(MacAddress::new(client));
+        let mut passed = 0;
+        while let Ok(Some(result)) = capture.capture_packet() {
+            assert!(tracker.process_capture(&result), "Filter passed a frame without the client");
+            passed += 1;
+        }
+        
+        // Frames the access points sent, whoever they went to
+        let offline = Capture::from_file(&path).expect("Should open pcap");
+        let filter = transmitter_filter(&[MacAddress::new(home), MacAddress::new(cafe)]);
+        let capture = PacketCapture::with_filter(offline.into(), "offline", Some(&filter))
+            .expect("Should apply filter");
+        let transmitted = std::iter::from_fn(|| capture.capture_packet().ok().flatten()).count();
+        std::fs::remove_file(&path).ok();
+        
+        assert_eq!(passed, 4);
+        assert_eq!(transmitted, 3);
+        assert_eq!(tracker.frames_seen(), 4);
+        assert_eq!(
+            tracker.bssids(),
+            vec![(MacAddress::new(home), 2), (MacAddress::new(cafe), 1)]
+        );
+    }
+    
+    #[test]
+    fn test_lock_recovers_after_panic() {
+        let mutex = Arc::new(Mutex::new(0u32));
+        
+        let poisoner = Arc::clone(&mutex);
+        let result = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("capture consumer panicked");
+        })
+        .join();
+        assert!(result.is_err());
+        assert!(mutex.is_poisoned());
+        
+        // Subsequent captures still get the lock
+        *lock_recovering(&mutex, "wlan0") += 1;
+        *lock_recovering(&mutex, "wlan0") += 1;
+        assert_eq!(*lock_recovering(&mutex, "wlan0"), 2);
+        assert!(!mutex.is_poisoned());
+    }
+    
+    /// Source replaying a fixed sequence of reads, then timing out
+    struct ScriptedSource {
+        reads: Mutex<std::collections::VecDeque<Result<Option<CaptureResult>>>>,
+    }
+    
+    impl CaptureSource for ScriptedSource {
+        fn capture_packet(&self) -> Result<Option<CaptureResult>> {
+            self.reads.lock().unwrap().pop_front().unwrap_or(Ok(None))
+        }
+    }
+    
+    fn capture(data: &[u8]) -> CaptureResult {
+        CaptureResult {
+            timestamp: SystemTime::now(),
+            data: data.to_vec(),
+            length: data.len(),
+        }
+    }
+    
+    #[test]
+    fn test_supervised_capture_restarts_after_device_error() {
+        let opens = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counter = Arc::clone(&opens);
+        let (tx, mut rx) = broadcast::channel(16);
+        
+        // The first handle dies after one frame; the re-opened one keeps delivering
+        let supervised = SupervisedCapture::new("wlan0mon", move || {
+            let reads = match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => vec![
+                    Ok(Some(capture(b"before"))),
+                    Err(DeauthError::InterfaceError("The interface went down".to_string())),
+                ],
+                1 => return Err(DeauthError::InterfaceError("No such device exists".to_string())),
+                _ => vec![Ok(Some(capture(b"after")))],
+            };
+            Ok(Box::new(ScriptedSource { reads: Mutex::new(reads.into()) }) as Box<dyn CaptureSource>)
+        })
+        .expect("Should open")
+        .with_restart_policy(3, Duration::from_millis(1))
+        .with_events(tx);
+        
+        assert_eq!(supervised.capture_packet().unwrap().unwrap().data, b"before");
+        
+        // The failing read is absorbed by a restart on the second attempt
+        assert!(supervised.capture_packet().unwrap().is_none());
+        assert_eq!(supervised.restarts(), 1);
+        assert_eq!(opens.load(Ordering::SeqCst), 3);
+        match rx.try_recv().expect("Should publish event") {
+            Event::CaptureRestarted { interface, attempt } => {
+                assert_eq!(interface, "wlan0mon");
+                assert_eq!(attempt, 2);
+            }
+            other => panic!("Unexpected event {:?}", other),
+        }
+        
+        assert_eq!(supervised.capture_packet().unwrap().unwrap().data, b"after");
+        assert!(supervised.capture_packet().unwrap().is_none());
+    }
+    
+    #[test]
+    fn test_supervised_capture_reads_during_restart() {
+        let failing = || {
+            let reads = vec![Err(DeauthError::InterfaceError("The interface went down".to_string()))];
+            Ok(Box::new(ScriptedSource { reads: Mutex::new(reads.into()) }) as Box<dyn CaptureSource>)
+        };
+        let supervised = SupervisedCapture::new("wlan0mon", failing)
+            .expect("Should open")
+            .with_restart_policy(2, Duration::from_millis(100));
+        
+        std::thread::scope(|scope| {
+            let restart = scope.spawn(|| supervised.capture_packet());
+            std::thread::sleep(Duration::from_millis(30));
+            
+            // Neither the restart flag nor the source lock holds up another reader
+            let started = std::time::Instant::now();
+            assert!(supervised.capture_packet().unwrap().is_none());
+            assert!(supervised.source.try_lock().is_ok());
+            assert!(started.elapsed() < Duration::from_millis(50));
+            
+            assert!(restart.join().unwrap().unwrap().is_none());
+        });
+        assert_eq!(supervised.restarts(), 1);
+    }
+    
+    #[test]
+    fn test_retried_frame_is_duplicate() {
+        use crate::network::injection::Injector;
+        use crate::network::LoopbackChannel;
+        
+        let ap = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+        let client = [0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+        
+        // Data frame from `transmitter` with radiotap header
+        let data = |transmitter: [u8; 6], sequence: u16, retry: bool| {
+            let mut frame = packet::RADIOTAP_HEADER.to_vec();
+            frame.extend_from_slice(&[0x08, if retry { 0x0A } else { 0x02 }, 0x00, 0x00]);
+            frame.extend_from_slice(&client);
+            frame.extend_from_slice(&transmitter);
+            frame.extend_from_slice(&ap);
+            frame.extend_from_slice(&(sequence << 4).to_le_bytes());
+            frame.extend_from_slice(b"payload");
+            frame
+        };
+        let frames = [
+            data(ap, 0x120, false),
+            data(ap, 0x120, true),
+            data(ap, 0x121, true),
+            data(client, 0x121, true),
+            data(ap, 0x121, true),
+        ];
+        
+        let mut detector = DuplicateDetector::new();
+        let flagged: Vec<bool> = frames
+            .iter()
+            .map(|frame| detector.process_frame(packet::strip_radiotap(frame).unwrap()))
+            .collect();
+        assert_eq!(flagged, [false, true, false, false, true]);
+        assert_eq!(detector.duplicates_seen(), 2);
+        
+        // ACKs carry no sequence number
+        assert!(!detector.process_frame(&[0xD4, 0x08, 0x00, 0x00, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]));
+        
+        let loopback = LoopbackChannel::new();
+        for frame in &frames {
+            loopback.inject_frame(frame).unwrap();
+        }
+        let dedup = DedupCapture::new(Box::new(loopback));
+        let passed: Vec<Vec<u8>> = (0..3).map(|_| dedup.capture_packet().unwrap().unwrap().data).collect();
+        assert_eq!(passed, vec![frames[0].clone(), frames[2].clone(), frames[3].clone()]);
+        assert_eq!(dedup.duplicates_dropped(), 1);
+        
+        // Disabled, the last retry passes through uncounted
+        dedup.set_enabled(false);
+        assert_eq!(dedup.capture_packet().unwrap().unwrap().data, frames[4]);
+        assert!(dedup.capture_packet().unwrap().is_none());
+        assert_eq!(dedup.duplicates_dropped(), 1);
+    }
 }
\ No newline at end of file
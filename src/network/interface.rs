@@ -4,8 +4,10 @@
 //! across Linux, Windows, and macOS platforms.
 
 use crate::{DeauthError, Result};
-use mac_address::MacAddress;
+use crate::core::packet::MacAddress;
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
@@ -40,6 +42,25 @@ pub struct NetworkInterface {
     pub platform_data: PlatformInterfaceData,
 }
 
+impl NetworkInterface {
+    /// Check if the interface can tune to `channel`
+    ///
+    /// Interfaces that have not reported their channel list yet are assumed
+    /// to support every channel.
+    pub fn supports_channel(&self, channel: u8) -> bool {
+        self.supported_channels.is_empty() || self.supported_channels.contains(&channel)
+    }
+    
+    /// Kernel driver bound to the interface, where the platform reports one
+    pub fn driver(&self) -> Option<&str> {
+        match self.platform_data {
+            // Discovery records "unknown" when sysfs has no driver link
+            PlatformInterfaceData::Linux(ref data) if !data.driver.is_empty() && data.driver != "unknown" => Some(&data.driver),
+            _ => None,
+        }
+    }
+}
+
 /// Interface type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InterfaceType {
@@ -86,17 +107,90 @@ pub struct MacOSInterfaceData {
     pub io_service: String,
 }
 
+/// Output of an external command
+#[derive(Debug, Clone, Default)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl From<std::process::Output> for CommandOutput {
+    fn from(output: std::process::Output) -> Self {
+        Self {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }
+    }
+}
+
+/// Boxed future returned by `CommandRunner::run_async`
+pub type CommandFuture<'a> = Pin<Box<dyn Future<Output = Result<CommandOutput>> + Send + 'a>>;
+
+/// Runs the external tools (`iw`, `ip`) used to reconfigure interfaces
+///
+/// Replaceable so tests can stub out the system.
+pub trait CommandRunner: Send + Sync {
+    /// Run `program` with `args`, blocking until it exits
+    fn run(&self, program: &str, args: &[String]) -> Result<CommandOutput>;
+    
+    /// Run `program` with `args` without blocking the async runtime
+    fn run_async<'a>(&'a self, program: &'a str, args: &'a [String]) -> CommandFuture<'a>;
+}
+
+/// Command runner executing real processes
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemCommandRunner;
+
+impl CommandRunner for SystemCommandRunner {
+    fn run(&self, program: &str, args: &[String]) -> Result<CommandOutput> {
+        let output = std::process::Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|e| DeauthError::InterfaceError(format!("Failed to run {}: {}", program, e)))?;
+        Ok(output.into())
+    }
+    
+    fn run_async<'a>(&'a self, program: &'a str, args: &'a [String]) -> CommandFuture<'a> {
+        Box::pin(async move {
+            let output = tokio::process::Command::new(program)
+                .args(args)
+                .output()
+                .await
+                .map_err(|e| DeauthError::InterfaceError(format!("Failed to run {}: {}", program, e)))?;
+            Ok(output.into())
+        })
+    }
+}
+
+/// Linux drivers known to inject frames reliably in monitor mode
+pub const INJECTION_CAPABLE_DRIVERS: &[&str] = &[
+    "ath9k",
+    "ath9k_htc",
+    "carl9170",
+    "rt2800pci",
+    "rt2800usb",
+    "rt73usb",
+    "rtl8187",
+    "mt7601u",
+    "mt76x0u",
+    "mt76x2u",
+];
+
 /// Interface manager for discovering and managing network interfaces
 pub struct InterfaceManager {
     interfaces: Arc<std::sync::RwLock<HashMap<String, NetworkInterface>>>,
+    runner: Arc<dyn CommandRunner>,
+    injection_drivers: Vec<String>,
+    cycle_link: bool,
+    admin_states: std::sync::Mutex<HashMap<String, bool>>,
 }
 
 impl InterfaceManager {
     /// Create a new interface manager
     pub fn new() -> Result<Self> {
-        let manager = Self {
-            interfaces: Arc::new(std::sync::RwLock::new(HashMap::new())),
-        };
+        let manager = Self::from_interfaces([]);
         
         // Discover interfaces on creation
         manager.discover_interfaces()?;
@@ -104,6 +198,34 @@ impl InterfaceManager {
         Ok(manager)
     }
     
+    /// Create a manager knowing only `interfaces`, without discovering any
+    fn from_interfaces(interfaces: impl IntoIterator<Item = NetworkInterface>) -> Self {
+        Self {
+            interfaces: Arc::new(std::sync::RwLock::new(
+                interfaces.into_iter().map(|interface| (interface.name.clone(), interface)).collect(),
+            )),
+            runner: Arc::new(SystemCommandRunner),
+            injection_drivers: default_injection_drivers(),
+            cycle_link: false,
+            admin_states: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+    
+    /// Run interface commands through `runner` instead of spawning processes
+    pub fn with_command_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = runner;
+        self
+    }
+    
+    /// Take the interface down before switching it to monitor mode and back up after
+    ///
+    /// Some drivers refuse to change the type of an interface that is up,
+    /// others leave it down afterwards. Off by default.
+    pub fn with_link_cycling(mut self, enabled: bool) -> Self {
+        self.cycle_link = enabled;
+        self
+    }
+    
     /// Discover all available network interfaces
     pub fn discover_interfaces(&self) -> Result<Vec<NetworkInterface>> {
         info!("Discovering network interfaces");
@@ -112,7 +234,10 @@ impl InterfaceManager {
             Platform::Linux => self.discover_linux_interfaces()?,
             Platform::Windows => self.discover_windows_interfaces()?,
             Platform::MacOS => self.discover_macos_interfaces()?,
-            Platform::Unknown => return Err(DeauthError::PlatformError("Unsupported platform".to_string())),
+            Platform::Unknown => {
+                warn!("Interface discovery is not supported on this platform");
+                Vec::new()
+            }
         };
         
         // Update internal cache
@@ -129,24 +254,133 @@ impl InterfaceManager {
     }
     
     /// Get all interfaces
+    ///
+    /// Interfaces are sorted by index, then name, so lists built from them
+    /// keep their order between calls.
     pub fn get_interfaces(&self) -> Vec<NetworkInterface> {
-        self.interfaces.read().unwrap().values().cloned().collect()
+        let mut interfaces: Vec<_> = self.interfaces.read().unwrap().values().cloned().collect();
+        sort_interfaces(&mut interfaces);
+        interfaces
     }
     
-    /// Get Wi-Fi interfaces only
+    /// Get Wi-Fi interfaces only, sorted like `get_interfaces`
     pub fn get_wifi_interfaces(&self) -> Vec<NetworkInterface> {
-        self.interfaces.read().unwrap()
+        let mut interfaces: Vec<_> = self.interfaces.read().unwrap()
             .values()
             .filter(|iface| iface.interface_type == InterfaceType::WiFi)
             .cloned()
+            .collect();
+        sort_interfaces(&mut interfaces);
+        interfaces
+    }
+    
+    /// Wi-Fi interfaces whose driver is known to inject well, sorted like `get_interfaces`
+    ///
+    /// Drivers are matched against `INJECTION_CAPABLE_DRIVERS` unless replaced
+    /// with `with_injection_drivers`. Only Linux reports drivers, so this is
+    /// empty elsewhere.
+    pub fn with_injection_capable_drivers(&self) -> Vec<NetworkInterface> {
+        self.get_wifi_interfaces()
+            .into_iter()
+            .filter(|iface| {
+                iface.driver().is_some_and(|driver| self.injection_drivers.iter().any(|known| known == driver))
+            })
             .collect()
     }
     
+    /// Wi-Fi interface to scan on
+    ///
+    /// The `selected` interface wins if it is a known Wi-Fi interface, then an
+    /// adapter from `with_injection_capable_drivers`, then any Wi-Fi interface.
+    pub fn preferred_wifi_interface(&self, selected: &str) -> Option<NetworkInterface> {
+        self.get_interface(selected)
+            .filter(|iface| iface.interface_type == InterfaceType::WiFi)
+            .or_else(|| self.with_injection_capable_drivers().into_iter().next())
+            .or_else(|| self.get_wifi_interfaces().into_iter().next())
+    }
+    
+    /// Replace the built-in list of injection-capable drivers
+    pub fn with_injection_drivers<I, S>(mut self, drivers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.injection_drivers = drivers.into_iter().map(Into::into).collect();
+        self
+    }
+    
     /// Get interface by name
     pub fn get_interface(&self, name: &str) -> Option<NetworkInterface> {
         self.interfaces.read().unwrap().get(name).cloned()
     }
     
+    /// Check if the named interface supports `channel`
+    ///
+    /// Unknown interfaces support no channels.
+    pub fn is_channel_supported(&self, interface: &str, channel: u8) -> bool {
+        self.interfaces.read().unwrap()
+            .get(interface)
+            .map_or(false, |iface| iface.supports_channel(channel))
+    }
+    
+    /// Re-read the volatile fields of one interface
+    ///
+    /// Updates status, current channel and signal strength in place, which is
+    /// cheap enough to poll. Platforms without a fast path fall back to a full
+    /// rediscovery.
+    pub fn refresh(&self, name: &str) -> Result<NetworkInterface> {
+        match platform::get_platform() {
+            platform::Platform::Linux => self.refresh_from(
+                name,
+                std::path::Path::new("/sys/class/net"),
+                std::path::Path::new("/proc/net/wireless"),
+                iw_channel,
+            ),
+            _ => {
+                self.discover_interfaces()?;
+                self.get_interface(name)
+                    .ok_or_else(|| DeauthError::InterfaceError(format!("Interface {} not found", name)))
+            }
+        }
+    }
+    
+    /// Refresh `name` from a sysfs `class/net` directory and a `/proc/net/wireless` file
+    fn refresh_from<F>(
+        &self,
+        name: &str,
+        net_path: &std::path::Path,
+        wireless_path: &std::path::Path,
+        read_channel: F,
+    ) -> Result<NetworkInterface>
+    where
+        F: FnOnce(&str) -> Option<u8>,
+    {
+        let not_found = || DeauthError::InterfaceError(format!("Interface {} not found", name));
+        let interface_type = self.get_interface(name).ok_or_else(not_found)?.interface_type;
+        
+        // Read everything before taking the write lock, so `iw` never runs while it is held
+        let status = read_operstate(&net_path.join(name))?;
+        let wireless = (interface_type == InterfaceType::WiFi).then(|| {
+            // A missing file or entry means no signal is reported, not an error
+            let wireless = std::fs::read_to_string(wireless_path).unwrap_or_default();
+            (parse_proc_wireless_signal(&wireless, name), read_channel(name))
+        });
+        
+        let mut cache = self.interfaces.write().unwrap();
+        let interface = cache.get_mut(name).ok_or_else(not_found)?;
+        interface.status = status;
+        if let Some((signal_strength, current_channel)) = wireless {
+            interface.signal_strength = signal_strength;
+            interface.current_channel = current_channel;
+        }
+        
+        debug!(
+            "Refreshed {}: {:?}, channel {:?}, signal {:?}",
+            name, interface.status, interface.current_channel, interface.signal_strength
+        );
+        Ok(interface.clone())
+    }
+    
     /// Check if interface supports monitor mode
     pub fn supports_monitor_mode(&self, interface: &NetworkInterface) -> Result<bool> {
         match &interface.platform_data {
@@ -165,134 +399,264 @@ impl InterfaceManager {
         }
     }
     
+    /// Check if `interface` is currently in monitor mode
+    ///
+    /// On Linux this reads the interface type from `iw dev <name> info`,
+    /// falling back to the ARP hardware type in sysfs when `iw` is missing.
+    /// macOS captures through BPF without switching modes and counts as
+    /// monitoring; other platforms never are.
+    pub fn is_in_monitor_mode(&self, interface: &NetworkInterface) -> Result<bool> {
+        match &interface.platform_data {
+            PlatformInterfaceData::Linux(_) => {}
+            PlatformInterfaceData::MacOS(_) => return Ok(true),
+            PlatformInterfaceData::Windows(_) | PlatformInterfaceData::Unknown => return Ok(false),
+        }
+        
+        let args = ["dev", interface.name.as_str(), "info"].map(String::from);
+        match self.runner.run("iw", &args) {
+            Ok(output) => {
+                let output = check_output(output, &format!("Querying {} failed", interface.name))?;
+                parse_iw_type(&output.stdout)
+                    .map(|mode| mode == "monitor")
+                    .ok_or_else(|| DeauthError::InterfaceError(format!("iw reported no type for {}", interface.name)))
+            }
+            Err(e) => {
+                debug!("iw unavailable ({}), reading the interface type from sysfs", e);
+                let path = std::path::Path::new("/sys/class/net").join(&interface.name).join("type");
+                let hardware_type = std::fs::read_to_string(path)?;
+                Ok(hardware_type.trim() == ARPHRD_IEEE80211_RADIOTAP)
+            }
+        }
+    }
+    
     /// Enable monitor mode on interface (Linux only)
+    ///
+    /// Blocks while `iw` runs; async callers should use `enable_monitor_mode_async`.
     pub fn enable_monitor_mode(&self, interface: &NetworkInterface) -> Result<()> {
-        if interface.interface_type != InterfaceType::WiFi {
-            return Err(DeauthError::InterfaceError(
-                "Monitor mode only supported on Wi-Fi interfaces".to_string()
-            ));
+        if !monitor_mode_needs_iw(interface)? {
+            return Ok(());
         }
         
-        match &interface.platform_data {
-            PlatformInterfaceData::Linux(_) => {
-                self.enable_linux_monitor_mode(&interface.name)
-            }
-            PlatformInterfaceData::Windows(_) => {
-                Err(DeauthError::PlatformError(
-                    "Monitor mode not supported on Windows".to_string()
-                ))
+        info!("Enabling monitor mode for {}", interface.name);
+        if !self.cycle_link {
+            let output = self.runner.run("iw", &monitor_mode_args(&interface.name))?;
+            check_output(output, "Monitor mode failed")?;
+            
+            info!("Monitor mode enabled for {}", interface.name);
+            return Ok(());
+        }
+        
+        let output = self.runner.run("ip", &link_show_args(&interface.name))?;
+        self.remember_admin_state(&interface.name, &output)?;
+        
+        self.bring_down(&interface.name)?;
+        let switched = self.runner.run("iw", &monitor_mode_args(&interface.name))
+            .and_then(|output| check_output(output, "Monitor mode failed"));
+        if let Err(e) = switched {
+            // Do not leave an interface that was up down after a failed switch
+            let original = self.original_admin_state(&interface.name).unwrap_or(false);
+            if let Err(restore) = self.set_link(&interface.name, original) {
+                warn!("Failed to restore {} after monitor mode failed: {}", interface.name, restore);
             }
-            PlatformInterfaceData::MacOS(_) => {
-                // macOS uses BPF, no need to enable monitor mode
-                Ok(())
+            return Err(e);
+        }
+        self.bring_up(&interface.name)?;
+        
+        info!("Monitor mode enabled for {}", interface.name);
+        Ok(())
+    }
+    
+    /// Enable monitor mode without blocking the async runtime
+    pub async fn enable_monitor_mode_async(&self, interface: &NetworkInterface) -> Result<()> {
+        if !monitor_mode_needs_iw(interface)? {
+            return Ok(());
+        }
+        
+        info!("Enabling monitor mode for {}", interface.name);
+        if !self.cycle_link {
+            let output = self.runner.run_async("iw", &monitor_mode_args(&interface.name)).await?;
+            check_output(output, "Monitor mode failed")?;
+            
+            info!("Monitor mode enabled for {}", interface.name);
+            return Ok(());
+        }
+        
+        let output = self.runner.run_async("ip", &link_show_args(&interface.name)).await?;
+        self.remember_admin_state(&interface.name, &output)?;
+        
+        self.set_link_async(&interface.name, false).await?;
+        let switched = match self.runner.run_async("iw", &monitor_mode_args(&interface.name)).await {
+            Ok(output) => check_output(output, "Monitor mode failed"),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = switched {
+            let original = self.original_admin_state(&interface.name).unwrap_or(false);
+            if let Err(restore) = self.set_link_async(&interface.name, original).await {
+                warn!("Failed to restore {} after monitor mode failed: {}", interface.name, restore);
             }
-            PlatformInterfaceData::Unknown => {
-                Err(DeauthError::PlatformError("Unknown platform".to_string()))
+            return Err(e);
+        }
+        self.set_link_async(&interface.name, true).await?;
+        
+        info!("Monitor mode enabled for {}", interface.name);
+        Ok(())
+    }
+    
+    /// Bring `interface` administratively up (Linux only)
+    ///
+    /// Blocks while `ip` runs.
+    pub fn bring_up(&self, interface: &str) -> Result<()> {
+        self.set_link(interface, true)
+    }
+    
+    /// Take `interface` administratively down (Linux only)
+    ///
+    /// Blocks while `ip` runs.
+    pub fn bring_down(&self, interface: &str) -> Result<()> {
+        self.set_link(interface, false)
+    }
+    
+    /// Whether `interface` was up before monitor mode first cycled it
+    ///
+    /// `None` if this manager has not cycled the interface.
+    pub fn original_admin_state(&self, interface: &str) -> Option<bool> {
+        self.admin_states.lock().unwrap().get(interface).copied()
+    }
+    
+    /// Return `interface` to the administrative state it had before monitor
+    /// mode first cycled it
+    ///
+    /// Does nothing if this manager has not cycled the interface.
+    pub fn restore_admin_state(&self, interface: &str) -> Result<()> {
+        let Some(up) = self.original_admin_state(interface) else {
+            return Ok(());
+        };
+        
+        self.set_link(interface, up)?;
+        self.admin_states.lock().unwrap().remove(interface);
+        Ok(())
+    }
+    
+    /// `restore_admin_state` for every interface monitor mode has cycled
+    ///
+    /// Keeps going past failures and returns the first.
+    pub fn restore_admin_states(&self) -> Result<()> {
+        let interfaces: Vec<String> = self.admin_states.lock().unwrap().keys().cloned().collect();
+        
+        let mut result = Ok(());
+        for interface in interfaces {
+            if let Err(e) = self.restore_admin_state(&interface) {
+                warn!("Failed to restore {}: {}", interface, e);
+                result = result.and(Err(e));
             }
         }
+        result
     }
     
-    /// Linux interface discovery
-    fn discover_linux_interfaces(&self) -> Result<Vec<NetworkInterface>> {
-        use std::fs;
-        use std::path::Path;
+    /// Run `ip link set` to bring `interface` up or down
+    fn set_link(&self, interface: &str, up: bool) -> Result<()> {
+        let args = link_set_args(interface, up)?;
+        let output = self.runner.run("ip", &args)?;
+        check_output(output, &format!("Setting {} {} failed", interface, link_state(up)))?;
         
-        let mut interfaces = Vec::new();
+        debug!("{} is administratively {}", interface, link_state(up));
+        Ok(())
+    }
+    
+    /// `set_link` without blocking the async runtime
+    async fn set_link_async(&self, interface: &str, up: bool) -> Result<()> {
+        let args = link_set_args(interface, up)?;
+        let output = self.runner.run_async("ip", &args).await?;
+        check_output(output, &format!("Setting {} {} failed", interface, link_state(up)))?;
         
-        // Read network interfaces from /sys/class/net
-        let net_path = Path::new("/sys/class/net");
-        if !net_path.exists() {
-            return Err(DeauthError::PlatformError("/sys/class/net not found".to_string()));
+        debug!("{} is administratively {}", interface, link_state(up));
+        Ok(())
+    }
+    
+    /// Record the admin state in `ip link show` output, unless one is already known
+    ///
+    /// Cycling twice must not make the first cycle's "up" the original state.
+    fn remember_admin_state(&self, interface: &str, output: &CommandOutput) -> Result<()> {
+        if !output.success {
+            return Err(DeauthError::InterfaceError(format!(
+                "Querying {} failed: {}",
+                interface,
+                output.stderr.trim()
+            )));
         }
         
-        for entry in fs::read_dir(net_path)? {
-            let entry = entry?;
-            let name = entry.file_name().to_string_lossy().to_string();
-            
-            // Skip loopback
-            if name == "lo" {
-                continue;
+        let up = parse_ip_link_up(&output.stdout)
+            .ok_or_else(|| DeauthError::InterfaceError(format!("ip reported no flags for {}", interface)))?;
+        self.admin_states.lock().unwrap().entry(interface.to_string()).or_insert(up);
+        Ok(())
+    }
+    
+    /// Tune `interface` to `channel`
+    ///
+    /// Blocks while `iw` runs; async callers should use `set_channel_async`.
+    pub fn set_channel(&self, interface: &str, channel: u8) -> Result<()> {
+        let args = self.channel_args(interface, channel)?;
+        let output = self.runner.run("iw", &args)?;
+        check_output(output, &format!("Setting channel {} on {} failed", channel, interface))?;
+        
+        self.record_channel(interface, Some(channel));
+        Ok(())
+    }
+    
+    /// Tune `interface` to `channel` without blocking the async runtime
+    pub async fn set_channel_async(&self, interface: &str, channel: u8) -> Result<()> {
+        let args = self.channel_args(interface, channel)?;
+        let output = self.runner.run_async("iw", &args).await?;
+        check_output(output, &format!("Setting channel {} on {} failed", channel, interface))?;
+        
+        self.record_channel(interface, Some(channel));
+        Ok(())
+    }
+    
+    /// Query the channel `interface` is tuned to without blocking the async runtime
+    ///
+    /// `None` when the interface does not report one, e.g. while disconnected.
+    pub async fn current_channel_async(&self, interface: &str) -> Result<Option<u8>> {
+        let args = ["dev", interface, "info"].map(String::from);
+        let output = self.runner.run_async("iw", &args).await?;
+        let output = check_output(output, &format!("Querying {} failed", interface))?;
+        
+        let channel = parse_iw_channel(&output.stdout);
+        self.record_channel(interface, channel);
+        Ok(channel)
+    }
+    
+    /// Validate a channel change and build its `iw` arguments
+    fn channel_args(&self, interface: &str, channel: u8) -> Result<Vec<String>> {
+        if let Some(known) = self.get_interface(interface) {
+            if !known.supports_channel(channel) {
+                return Err(DeauthError::InterfaceError(format!(
+                    "Channel {} is not supported by {}",
+                    channel, interface
+                )));
             }
-            
-            let interface_path = entry.path();
-            
-            // Get MAC address
-            let address_file = interface_path.join("address");
-            let mac_address = if address_file.exists() {
-                let addr_str = fs::read_to_string(&address_file)?
-                    .trim()
-                    .to_string();
-                MacAddress::from_str(&addr_str)
-                    .map_err(|_| DeauthError::InterfaceError("Invalid MAC address".to_string()))?
-            } else {
-                continue;
-            };
-            
-            // Get interface index
-            let ifindex_file = interface_path.join("ifindex");
-            let index = if ifindex_file.exists() {
-                fs::read_to_string(&ifindex_file)?
-                    .trim()
-                    .parse::<u32>()
-                    .unwrap_or(0)
-            } else {
-                0
-            };
-            
-            // Check if it's wireless
-            let wireless_path = interface_path.join("wireless");
-            let interface_type = if wireless_path.exists() {
-                InterfaceType::WiFi
-            } else {
-                InterfaceType::Ethernet
-            };
-            
-            // Get operational status
-            let operstate_file = interface_path.join("operstate");
-            let status = if operstate_file.exists() {
-                match fs::read_to_string(&operstate_file)?.trim() {
-                    "up" => InterfaceStatus::Up,
-                    "down" => InterfaceStatus::Down,
-                    _ => InterfaceStatus::Unknown,
-                }
-            } else {
-                InterfaceStatus::Unknown
-            };
-            
-            // Get driver information
-            let device_path = interface_path.join("device");
-            let driver = if device_path.exists() {
-                if let Ok(driver_link) = fs::read_link(device_path.join("driver")) {
-                    driver_link.file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "unknown".to_string())
-                } else {
-                    "unknown".to_string()
-                }
-            } else {
-                "unknown".to_string()
-            };
-            
-            let interface = NetworkInterface {
-                name: name.clone(),
-                index,
-                mac_address,
-                interface_type,
-                status,
-                supported_channels: Vec::new(), // Will be populated later
-                current_channel: None,
-                signal_strength: None,
-                platform_data: PlatformInterfaceData::Linux(LinuxInterfaceData {
-                    ifindex: index,
-                    flags: 0, // Will be populated from netlink
-                    driver,
-                }),
-            };
-            
-            interfaces.push(interface);
         }
         
-        Ok(interfaces)
+        info!("Setting {} to channel {}", interface, channel);
+        Ok(vec![
+            "dev".to_string(),
+            interface.to_string(),
+            "set".to_string(),
+            "channel".to_string(),
+            channel.to_string(),
+        ])
+    }
+    
+    /// Update the cached channel of `interface`, if it is known
+    fn record_channel(&self, interface: &str, channel: Option<u8>) {
+        if let Some(known) = self.interfaces.write().unwrap().get_mut(interface) {
+            known.current_channel = channel;
+        }
+    }
+    
+    /// Linux interface discovery
+    fn discover_linux_interfaces(&self) -> Result<Vec<NetworkInterface>> {
+        discover_sysfs_interfaces(std::path::Path::new("/sys/class/net"))
     }
     
     /// Windows interface discovery
@@ -320,27 +684,247 @@ impl InterfaceManager {
         // Placeholder - would need to check driver capabilities
         Ok(true)
     }
+}
+
+/// Whether enabling monitor mode on `interface` takes an `iw` call
+///
+/// macOS captures through BPF and needs nothing; Windows and unknown
+/// platforms cannot enable it at all.
+fn monitor_mode_needs_iw(interface: &NetworkInterface) -> Result<bool> {
+    if interface.interface_type != InterfaceType::WiFi {
+        return Err(DeauthError::InterfaceError(
+            "Monitor mode only supported on Wi-Fi interfaces".to_string()
+        ));
+    }
     
-    /// Enable monitor mode on Linux
-    fn enable_linux_monitor_mode(&self, interface_name: &str) -> Result<()> {
-        use std::process::Command;
-        
-        info!("Enabling monitor mode for {}", interface_name);
-        
-        // Use iw to set monitor mode
-        let output = Command::new("iw")
-            .args(&[interface_name, "set", "monitor", "fcs"])
-            .output()
-            .map_err(|e| DeauthError::InterfaceError(format!("Failed to enable monitor mode: {}", e)))?;
-        
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(DeauthError::InterfaceError(format!("Monitor mode failed: {}", error)));
+    match &interface.platform_data {
+        PlatformInterfaceData::Linux(_) => Ok(true),
+        PlatformInterfaceData::Windows(_) => {
+            Err(DeauthError::PlatformError(
+                "Monitor mode not supported on Windows".to_string()
+            ))
+        }
+        PlatformInterfaceData::MacOS(_) => Ok(false),
+        PlatformInterfaceData::Unknown => {
+            Err(DeauthError::PlatformError("Unknown platform".to_string()))
+        }
+    }
+}
+
+/// `iw` arguments switching `interface_name` to monitor mode
+fn monitor_mode_args(interface_name: &str) -> Vec<String> {
+    [interface_name, "set", "monitor", "fcs"].map(String::from).to_vec()
+}
+
+/// `ip` arguments showing the link state of `interface_name`
+fn link_show_args(interface_name: &str) -> Vec<String> {
+    ["link", "show", "dev", interface_name].map(String::from).to_vec()
+}
+
+/// `ip` arguments bringing `interface_name` up or down
+///
+/// Only Linux has `ip`; elsewhere this is a `PlatformError`.
+fn link_set_args(interface_name: &str, up: bool) -> Result<Vec<String>> {
+    if platform::get_platform() != platform::Platform::Linux {
+        return Err(DeauthError::PlatformError(
+            "Setting the link state is only supported on Linux".to_string()
+        ));
+    }
+    
+    Ok(["link", "set", "dev", interface_name, link_state(up)].map(String::from).to_vec())
+}
+
+/// `ip` keyword for an admin state
+fn link_state(up: bool) -> &'static str {
+    if up { "up" } else { "down" }
+}
+
+/// Whether `ip link show` output reports the interface administratively up
+///
+/// Reads the `UP` flag, not `state`, which is the operational state and
+/// stays `DOWN` on an interface that is up without a carrier.
+fn parse_ip_link_up(output: &str) -> Option<bool> {
+    let flags = output.split_once('<')?.1.split_once('>')?.0;
+    Some(flags.split(',').any(|flag| flag == "UP"))
+}
+
+/// Turn a failed command into an `InterfaceError` prefixed with `context`
+fn check_output(output: CommandOutput, context: &str) -> Result<CommandOutput> {
+    if output.success {
+        Ok(output)
+    } else {
+        Err(DeauthError::InterfaceError(format!("{}: {}", context, output.stderr.trim())))
+    }
+}
+
+/// Discover interfaces from a sysfs `class/net` directory
+///
+/// Interfaces are parsed in parallel; ones that fail to parse are skipped and
+/// the result is sorted by interface index for a stable order.
+fn discover_sysfs_interfaces(net_path: &std::path::Path) -> Result<Vec<NetworkInterface>> {
+    use rayon::prelude::*;
+    use std::fs;
+    
+    if !net_path.exists() {
+        return Err(DeauthError::PlatformError(format!("{} not found", net_path.display())));
+    }
+    
+    let entries: Vec<_> = fs::read_dir(net_path)?
+        .filter_map(|entry| entry.ok())
+        .collect();
+    
+    let mut interfaces: Vec<NetworkInterface> = entries
+        .par_iter()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            
+            // Skip loopback
+            if name == "lo" {
+                return None;
+            }
+            
+            match read_sysfs_interface(&entry.path(), name.clone()) {
+                Ok(interface) => interface,
+                Err(e) => {
+                    warn!("Skipping interface {}: {}", name, e);
+                    None
+                }
+            }
+        })
+        .collect();
+    
+    sort_interfaces(&mut interfaces);
+    Ok(interfaces)
+}
+
+/// Owned copy of `INJECTION_CAPABLE_DRIVERS`
+fn default_injection_drivers() -> Vec<String> {
+    INJECTION_CAPABLE_DRIVERS.iter().map(|driver| (*driver).to_string()).collect()
+}
+
+/// Sort interfaces by index, then name
+fn sort_interfaces(interfaces: &mut [NetworkInterface]) {
+    interfaces.sort_by(|a, b| a.index.cmp(&b.index).then_with(|| a.name.cmp(&b.name)));
+}
+
+/// Read a single interface from its sysfs directory
+///
+/// Returns `Ok(None)` for interfaces without a hardware address.
+fn read_sysfs_interface(interface_path: &std::path::Path, name: String) -> Result<Option<NetworkInterface>> {
+    use std::fs;
+    
+    // Get MAC address
+    let address_file = interface_path.join("address");
+    let mac_address = if address_file.exists() {
+        fs::read_to_string(&address_file)?
+            .trim()
+            .parse::<MacAddress>()
+            .map_err(|_| DeauthError::InterfaceError("Invalid MAC address".to_string()))?
+    } else {
+        return Ok(None);
+    };
+    
+    // Get interface index
+    let ifindex_file = interface_path.join("ifindex");
+    let index = if ifindex_file.exists() {
+        fs::read_to_string(&ifindex_file)?
+            .trim()
+            .parse::<u32>()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    
+    // Check if it's wireless
+    let interface_type = if interface_path.join("wireless").exists() {
+        InterfaceType::WiFi
+    } else {
+        InterfaceType::Ethernet
+    };
+    
+    // Get operational status
+    let status = read_operstate(interface_path)?;
+    
+    // Get driver information
+    let driver = fs::read_link(interface_path.join("device").join("driver"))
+        .ok()
+        .and_then(|link| link.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "unknown".to_string());
+    
+    Ok(Some(NetworkInterface {
+        name,
+        index,
+        mac_address,
+        interface_type,
+        status,
+        supported_channels: Vec::new(), // Will be populated later
+        current_channel: None,
+        signal_strength: None,
+        platform_data: PlatformInterfaceData::Linux(LinuxInterfaceData {
+            ifindex: index,
+            flags: 0, // Will be populated from netlink
+            driver,
+        }),
+    }))
+}
+
+/// Read the operational status from an interface's sysfs directory
+fn read_operstate(interface_path: &std::path::Path) -> Result<InterfaceStatus> {
+    let operstate_file = interface_path.join("operstate");
+    if !operstate_file.exists() {
+        return Ok(InterfaceStatus::Unknown);
+    }
+    
+    Ok(match std::fs::read_to_string(&operstate_file)?.trim() {
+        "up" => InterfaceStatus::Up,
+        "down" => InterfaceStatus::Down,
+        _ => InterfaceStatus::Unknown,
+    })
+}
+
+/// Extract the signal level (dBm) of `name` from `/proc/net/wireless` contents
+///
+/// Lines look like `wlan0: 0000   54.  -56.  -256 ...`; the third column
+/// after the name is the signal level.
+fn parse_proc_wireless_signal(contents: &str, name: &str) -> Option<i8> {
+    contents.lines().find_map(|line| {
+        let (interface, fields) = line.trim().split_once(':')?;
+        if interface != name {
+            return None;
         }
         
-        info!("Monitor mode enabled for {}", interface_name);
-        Ok(())
+        let level = fields.split_whitespace().nth(2)?;
+        level.trim_end_matches('.').parse::<i8>().ok()
+    })
+}
+
+/// Extract the channel from `iw dev <name> info` output
+fn parse_iw_channel(output: &str) -> Option<u8> {
+    output.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("channel ")?;
+        rest.split_whitespace().next()?.parse().ok()
+    })
+}
+
+/// Extract the interface mode (`managed`, `monitor`, ...) from `iw dev <name> info` output
+fn parse_iw_type(output: &str) -> Option<&str> {
+    output.lines().find_map(|line| line.trim().strip_prefix("type ").map(str::trim))
+}
+
+/// ARP hardware type sysfs reports for interfaces in monitor mode
+const ARPHRD_IEEE80211_RADIOTAP: &str = "803";
+
+/// Query the current channel with `iw`; `None` if it is unavailable
+fn iw_channel(name: &str) -> Option<u8> {
+    let output = std::process::Command::new("iw")
+        .args(["dev", name, "info"])
+        .output()
+        .ok()?;
+    
+    if !output.status.success() {
+        return None;
     }
+    parse_iw_channel(&String::from_utf8_lossy(&output.stdout))
 }
 
 /// Platform detection
@@ -372,6 +956,27 @@ mod platform {
 mod tests {
     use super::*;
     
+    /// Interface that is up, with a Linux driver if `driver` is given
+    fn interface(name: &str, index: u32, interface_type: InterfaceType, driver: Option<&str>) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            index,
+            mac_address: MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, index.to_le_bytes()[0]]),
+            interface_type,
+            status: InterfaceStatus::Up,
+            supported_channels: Vec::new(),
+            current_channel: None,
+            signal_strength: None,
+            platform_data: driver.map_or(PlatformInterfaceData::Unknown, |driver| {
+                PlatformInterfaceData::Linux(LinuxInterfaceData {
+                    ifindex: index,
+                    flags: 0,
+                    driver: driver.to_string(),
+                })
+            }),
+        }
+    }
+    
     #[test]
     fn test_interface_manager_creation() {
         let manager = InterfaceManager::new();
@@ -397,4 +1002,279 @@ mod tests {
         #[cfg(target_os = "macos")]
         assert_eq!(platform, platform::Platform::MacOS);
     }
+    
+    #[test]
+    fn test_channel_support() {
+        let mut wlan0 = NetworkInterface {
+            supported_channels: vec![1, 6, 11, 36, 40],
+            current_channel: Some(6),
+            ..interface("wlan0", 3, InterfaceType::WiFi, None)
+        };
+        
+        let manager = InterfaceManager::from_interfaces([wlan0.clone()]);
+        
+        assert!(manager.is_channel_supported("wlan0", 6));
+        assert!(manager.is_channel_supported("wlan0", 36));
+        assert!(!manager.is_channel_supported("wlan0", 13));
+        assert!(!manager.is_channel_supported("wlan1", 6));
+        
+        // Channel list not reported yet
+        wlan0.supported_channels.clear();
+        assert!(wlan0.supports_channel(13));
+    }
+    
+    #[test]
+    fn test_interfaces_listed_in_index_order() {
+        let manager = InterfaceManager::from_interfaces([
+            interface("wlan1", 7, InterfaceType::WiFi, None),
+            interface("eth0", 2, InterfaceType::Ethernet, None),
+            interface("wlan0mon", 4, InterfaceType::WiFi, None),
+            interface("wlan0", 4, InterfaceType::WiFi, None),
+            interface("docker0", 9, InterfaceType::Ethernet, None),
+        ]);
+        
+        let names = |interfaces: Vec<NetworkInterface>| -> Vec<String> {
+            interfaces.into_iter().map(|iface| iface.name).collect()
+        };
+        
+        for _ in 0..10 {
+            assert_eq!(names(manager.get_interfaces()), ["eth0", "wlan0", "wlan0mon", "wlan1", "docker0"]);
+            assert_eq!(names(manager.get_wifi_interfaces()), ["wlan0", "wlan0mon", "wlan1"]);
+        }
+    }
+    
+    #[test]
+    fn test_injection_capable_driver_filter() {
+        let manager = InterfaceManager::from_interfaces([
+            interface("wlan2", 6, InterfaceType::WiFi, Some("rt2800usb")),
+            interface("wlan0", 3, InterfaceType::WiFi, Some("iwlwifi")),
+            interface("wlan1", 5, InterfaceType::WiFi, Some("ath9k_htc")),
+            interface("wlan3", 7, InterfaceType::WiFi, Some("unknown")),
+            interface("wlan4", 8, InterfaceType::WiFi, None),
+            interface("eth0", 2, InterfaceType::Ethernet, Some("ath9k")),
+        ]);
+        
+        let names = |interfaces: Vec<NetworkInterface>| -> Vec<String> {
+            interfaces.into_iter().map(|iface| iface.name).collect()
+        };
+        
+        // Wired adapters never qualify, whatever their driver
+        assert_eq!(names(manager.with_injection_capable_drivers()), ["wlan1", "wlan2"]);
+        
+        let manager = manager.with_injection_drivers(["iwlwifi"]);
+        assert_eq!(names(manager.with_injection_capable_drivers()), ["wlan0"]);
+        
+        let manager = manager.with_injection_drivers(Vec::<String>::new());
+        assert!(manager.with_injection_capable_drivers().is_empty());
+    }
+    
+    #[test]
+    fn test_preferred_wifi_interface() {
+        let manager = InterfaceManager::from_interfaces([
+            interface("wlan0", 3, InterfaceType::WiFi, Some("iwlwifi")),
+            interface("wlan1", 5, InterfaceType::WiFi, Some("ath9k_htc")),
+            interface("eth0", 2, InterfaceType::Ethernet, Some("ath9k")),
+        ]);
+        
+        let preferred = |selected: &str| manager.preferred_wifi_interface(selected).map(|iface| iface.name);
+        
+        assert_eq!(preferred("wlan0").as_deref(), Some("wlan0"));
+        // Unknown or wired selections fall back to the injection-capable adapter
+        assert_eq!(preferred("wlan9").as_deref(), Some("wlan1"));
+        assert_eq!(preferred("eth0").as_deref(), Some("wlan1"));
+        
+        let manager = InterfaceManager::from_interfaces([interface("wlan0", 3, InterfaceType::WiFi, None)]);
+        assert_eq!(manager.preferred_wifi_interface("wlan9").map(|iface| iface.name).as_deref(), Some("wlan0"));
+        assert!(InterfaceManager::from_interfaces([]).preferred_wifi_interface("wlan0").is_none());
+    }
+    
+    #[test]
+    fn test_sysfs_discovery_sorted() {
+        use std::fs;
+        
+        let root = std::env::temp_dir().join(format!("wifi-deauther-sysfs-{}", std::process::id()));
+        let fixtures = [
+            ("wlan1", "00:11:22:33:44:03", "7", true),
+            ("eth0", "00:11:22:33:44:01", "2", false),
+            ("wlan0", "00:11:22:33:44:02", "3", true),
+            ("broken0", "not-a-mac", "4", false),
+            ("lo", "00:00:00:00:00:00", "1", false),
+        ];
+        
+        for (name, address, ifindex, wireless) in fixtures {
+            let dir = root.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("address"), format!("{}\n", address)).unwrap();
+            fs::write(dir.join("ifindex"), format!("{}\n", ifindex)).unwrap();
+            fs::write(dir.join("operstate"), "up\n").unwrap();
+            if wireless {
+                fs::create_dir_all(dir.join("wireless")).unwrap();
+            }
+        }
+        
+        let interfaces = discover_sysfs_interfaces(&root).expect("Should discover interfaces");
+        fs::remove_dir_all(&root).ok();
+        
+        let names: Vec<_> = interfaces.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["eth0", "wlan0", "wlan1"]);
+        assert_eq!(interfaces[1].interface_type, InterfaceType::WiFi);
+        assert_eq!(interfaces[0].interface_type, InterfaceType::Ethernet);
+        assert_eq!(interfaces[2].status, InterfaceStatus::Up);
+    }
+    
+    #[test]
+    fn test_refresh_updates_volatile_fields() {
+        use std::fs;
+        
+        let root = std::env::temp_dir().join(format!("wifi-deauther-refresh-{}", std::process::id()));
+        let net = root.join("net");
+        let wireless = root.join("wireless");
+        let dir = net.join("wlan0");
+        fs::create_dir_all(dir.join("wireless")).unwrap();
+        fs::write(dir.join("address"), "00:11:22:33:44:02\n").unwrap();
+        fs::write(dir.join("ifindex"), "3\n").unwrap();
+        fs::write(dir.join("operstate"), "up\n").unwrap();
+        fs::write(
+            &wireless,
+            "Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE\n \
+             face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22\n \
+             wlan0: 0000   54.  -56.  -256        0      0      0      0      0        0\n",
+        )
+        .unwrap();
+        
+        let manager = InterfaceManager::from_interfaces(discover_sysfs_interfaces(&net).expect("Should discover interfaces"));
+        
+        // The cache stays unlocked while the channel is read
+        let read_channel = |_: &str| manager.interfaces.try_read().is_ok().then_some(6);
+        let refreshed = manager.refresh_from("wlan0", &net, &wireless, read_channel).expect("Should refresh");
+        assert_eq!(refreshed.status, InterfaceStatus::Up);
+        assert_eq!(refreshed.signal_strength, Some(-56));
+        assert_eq!(refreshed.current_channel, Some(6));
+        
+        // The link goes down and stops reporting a signal
+        fs::write(dir.join("operstate"), "down\n").unwrap();
+        fs::write(&wireless, "Inter-| sta-|   Quality\n").unwrap();
+        let refreshed = manager.refresh_from("wlan0", &net, &wireless, |_| None).expect("Should refresh");
+        let missing = manager.refresh_from("wlan9", &net, &wireless, |_| None);
+        fs::remove_dir_all(&root).ok();
+        
+        assert_eq!(refreshed.status, InterfaceStatus::Down);
+        assert_eq!(refreshed.signal_strength, None);
+        assert_eq!(refreshed.current_channel, None);
+        assert_eq!(manager.get_interface("wlan0").map(|i| i.status), Some(InterfaceStatus::Down));
+        assert!(matches!(missing, Err(DeauthError::InterfaceError(_))));
+        
+        assert_eq!(parse_iw_channel("Interface wlan0\n\ttype monitor\n\tchannel 36 (5180 MHz), width: 20 MHz\n"), Some(36));
+        assert_eq!(parse_iw_channel("Interface wlan0\n\ttype managed\n"), None);
+    }
+    
+    #[test]
+    fn test_parse_iw_type() {
+        let managed = "Interface wlan0\n\tifindex 3\n\twdev 0x1\n\taddr 00:11:22:33:44:55\n\t\
+                       ssid home\n\ttype managed\n\twiphy 0\n\tchannel 6 (2437 MHz), width: 20 MHz\n";
+        let monitor = "Interface wlan0mon\n\tifindex 4\n\twdev 0x2\n\taddr 00:11:22:33:44:55\n\t\
+                       type monitor\n\twiphy 0\n\tchannel 11 (2462 MHz), width: 20 MHz (no HT)\n";
+        
+        assert_eq!(parse_iw_type(managed), Some("managed"));
+        assert_eq!(parse_iw_type(monitor), Some("monitor"));
+        assert_eq!(parse_iw_type("Interface wlan0\n\tifindex 3\n"), None);
+    }
+    
+    /// Records commands and answers them after yielding to the runtime
+    #[derive(Default)]
+    struct StubRunner {
+        calls: std::sync::Mutex<Vec<Vec<String>>>,
+    }
+    
+    impl StubRunner {
+        fn answer(&self, args: &[String]) -> CommandOutput {
+            self.calls.lock().unwrap().push(args.to_vec());
+            
+            match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+                ["link", "show", ..] => CommandOutput {
+                    success: true,
+                    stdout: "3: wlan0: <BROADCAST,MULTICAST> mtu 1500 qdisc noop state DOWN mode DEFAULT\n".to_string(),
+                    stderr: String::new(),
+                },
+                [_, _, "info", ..] => CommandOutput {
+                    success: true,
+                    stdout: "Interface wlan0\n\ttype monitor\n\tchannel 11 (2462 MHz), width: 20 MHz\n".to_string(),
+                    stderr: String::new(),
+                },
+                _ => CommandOutput {
+                    success: true,
+                    ..CommandOutput::default()
+                },
+            }
+        }
+    }
+    
+    impl CommandRunner for StubRunner {
+        fn run(&self, _program: &str, args: &[String]) -> Result<CommandOutput> {
+            Ok(self.answer(args))
+        }
+        
+        fn run_async<'a>(&'a self, _program: &'a str, args: &'a [String]) -> CommandFuture<'a> {
+            Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                Ok(self.answer(args))
+            })
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_async_commands_use_runner() {
+        let runner = Arc::new(StubRunner::default());
+        let manager = InterfaceManager::from_interfaces([NetworkInterface {
+            supported_channels: vec![1, 6, 11],
+            ..interface("wlan0", 3, InterfaceType::WiFi, None)
+        }])
+        .with_command_runner(Arc::clone(&runner) as Arc<dyn CommandRunner>);
+        
+        assert_eq!(manager.current_channel_async("wlan0").await.expect("Should query channel"), Some(11));
+        manager.set_channel_async("wlan0", 6).await.expect("Should set channel");
+        assert_eq!(manager.get_interface("wlan0").and_then(|i| i.current_channel), Some(6));
+        
+        // Unsupported channels are refused before any command runs
+        assert!(manager.set_channel_async("wlan0", 36).await.is_err());
+        manager.set_channel("wlan0", 1).expect("Should set channel");
+        
+        let calls = runner.calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0], ["dev", "wlan0", "info"]);
+        assert_eq!(calls[1], ["dev", "wlan0", "set", "channel", "6"]);
+        assert_eq!(calls[2], ["dev", "wlan0", "set", "channel", "1"]);
+    }
+    
+    #[test]
+    fn test_monitor_mode_cycles_link_in_order() {
+        let runner = Arc::new(StubRunner::default());
+        let manager = InterfaceManager::from_interfaces([])
+            .with_command_runner(Arc::clone(&runner) as Arc<dyn CommandRunner>)
+            .with_link_cycling(true);
+        let wlan0 = NetworkInterface {
+            status: InterfaceStatus::Down,
+            supported_channels: vec![1, 6, 11],
+            ..interface("wlan0", 3, InterfaceType::WiFi, Some("ath9k"))
+        };
+        
+        manager.enable_monitor_mode(&wlan0).expect("Should enable monitor mode");
+        assert_eq!(manager.original_admin_state("wlan0"), Some(false));
+        
+        // The interface was down to begin with, so restoring takes it down again
+        manager.restore_admin_states().expect("Should restore admin state");
+        assert_eq!(manager.original_admin_state("wlan0"), None);
+        manager.restore_admin_states().expect("Nothing left to restore");
+        
+        let calls = runner.calls.lock().unwrap();
+        assert_eq!(calls.len(), 5);
+        assert_eq!(calls[0], ["link", "show", "dev", "wlan0"]);
+        assert_eq!(calls[1], ["link", "set", "dev", "wlan0", "down"]);
+        assert_eq!(calls[2], ["wlan0", "set", "monitor", "fcs"]);
+        assert_eq!(calls[3], ["link", "set", "dev", "wlan0", "up"]);
+        assert_eq!(calls[4], ["link", "set", "dev", "wlan0", "down"]);
+        
+        assert_eq!(parse_ip_link_up("4: wlan1: <NO-CARRIER,BROADCAST,MULTICAST,UP> mtu 1500 state DOWN"), Some(true));
+        assert_eq!(parse_ip_link_up("Device \"wlan9\" does not exist."), None);
+    }
 }
\ No newline at end of file
@@ -4,11 +4,20 @@
 //! packet injection, and channel hopping functionality.
 
 pub mod interface;
+pub mod association;
 pub mod injection;
 pub mod capture;
 pub mod channel;
+pub mod loopback;
+pub mod pcap_file;
+pub mod sink;
+#[cfg(target_os = "macos")]
+pub mod bpf;
 
 pub use interface::{NetworkInterface, InterfaceManager};
-pub use injection::{PacketInjector, InjectionResult};
-pub use capture::{PacketCapture, CaptureResult};
-pub use channel::{ChannelHopper, ChannelInfo};
\ No newline at end of file
+pub use injection::{Injector, PacketInjector, InjectionResult, InjectionSummary, SimulatedInjector};
+pub use capture::{PacketCapture, CaptureResult, CaptureSource, DedupCapture, DuplicateDetector, ReconnectionEstimator, StationTracker, SupervisedCapture};
+pub use association::AssociationGraph;
+pub use channel::{ChannelHopper, ChannelInfo};
+pub use loopback::LoopbackChannel;
+pub use sink::{FileFrameSink, FrameSinkConfig, UdpFrameSink};
\ No newline at end of file
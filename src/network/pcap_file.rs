@@ -0,0 +1,80 @@
+//! Classic pcap file writing
+//! 
+//! Frame sinks and capture exports both write little-endian pcap files with
+//! microsecond timestamps and radiotap link type; this module owns that
+//! layout so the two cannot drift apart.
+
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Magic number of a classic little-endian pcap file with microsecond timestamps
+pub const PCAP_MAGIC: u32 = 0xA1B2_C3D4;
+
+/// Link type of written files: 802.11 frames with radiotap headers
+pub const LINKTYPE_IEEE802_11_RADIOTAP: u32 = 127;
+
+/// Snapshot length advertised in untruncated files
+pub const MAX_SNAP_LENGTH: u32 = 65535;
+
+/// Length of the global header
+pub const HEADER_LEN: usize = 24;
+
+/// Length of a record header
+pub const RECORD_HEADER_LEN: usize = 16;
+
+/// Write the global header: magic, version 2.4, UTC, no timestamp accuracy, snaplen, link type
+pub fn write_header<W: Write>(writer: &mut W, snap_length: u32) -> io::Result<()> {
+    writer.write_all(&PCAP_MAGIC.to_le_bytes())?;
+    writer.write_all(&2u16.to_le_bytes())?;
+    writer.write_all(&4u16.to_le_bytes())?;
+    writer.write_all(&[0; 8])?;
+    writer.write_all(&snap_length.to_le_bytes())?;
+    writer.write_all(&LINKTYPE_IEEE802_11_RADIOTAP.to_le_bytes())
+}
+
+/// Check whether `header` is a global header this module would write
+pub fn is_radiotap_header(header: &[u8; HEADER_LEN]) -> bool {
+    header[..4] == PCAP_MAGIC.to_le_bytes() && header[20..] == LINKTYPE_IEEE802_11_RADIOTAP.to_le_bytes()
+}
+
+/// Write one record of `data`, captured at `timestamp` from a packet of `original_length` bytes
+///
+/// Lengths beyond `u32::MAX` saturate, as do timestamps past 2106.
+pub fn write_record<W: Write>(writer: &mut W, timestamp: SystemTime, data: &[u8], original_length: usize) -> io::Result<()> {
+    let since_epoch = timestamp.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO);
+    let seconds = u32::try_from(since_epoch.as_secs()).unwrap_or(u32::MAX);
+    let captured = u32::try_from(data.len()).unwrap_or(u32::MAX);
+    let original = u32::try_from(original_length.max(data.len())).unwrap_or(u32::MAX);
+    
+    writer.write_all(&seconds.to_le_bytes())?;
+    writer.write_all(&since_epoch.subsec_micros().to_le_bytes())?;
+    writer.write_all(&captured.to_le_bytes())?;
+    writer.write_all(&original.to_le_bytes())?;
+    writer.write_all(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_header_and_record_layout() {
+        let mut file = Vec::new();
+        write_header(&mut file, MAX_SNAP_LENGTH).unwrap();
+        assert_eq!(file.len(), HEADER_LEN);
+        assert!(is_radiotap_header(&file[..HEADER_LEN].try_into().unwrap()));
+        
+        write_record(&mut file, UNIX_EPOCH + Duration::from_micros(2_000_250), &[0xAB; 3], 10).unwrap();
+        assert_eq!(file.len(), HEADER_LEN + RECORD_HEADER_LEN + 3);
+        
+        let u32_at = |offset: usize| u32::from_le_bytes(file[offset..offset + 4].try_into().unwrap());
+        assert_eq!(u32_at(16), MAX_SNAP_LENGTH);
+        assert_eq!((u32_at(24), u32_at(28)), (2, 250));
+        assert_eq!((u32_at(32), u32_at(36)), (3, 10));
+        
+        let mut other = [0u8; HEADER_LEN];
+        other[..4].copy_from_slice(&PCAP_MAGIC.to_le_bytes());
+        other[20..].copy_from_slice(&1u32.to_le_bytes());
+        assert!(!is_radiotap_header(&other));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,205 @@
+//! Frame sinks for transports other than pcap
+//! 
+//! A sink takes the engine's serialized frames somewhere other than a
+//! wireless interface: a UDP endpoint feeding an SDR front end or testbed,
+//! or a file for offline replay. Sinks implement `Injector`, so the engine
+//! drives them like any other transmitter (see `EngineConfig::frame_sink`).
+
+use super::injection::Injector;
+use super::pcap_file::{self, HEADER_LEN, MAX_SNAP_LENGTH, RECORD_HEADER_LEN};
+use crate::{DeauthError, Result};
+use parking_lot::Mutex;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tracing::{debug, info};
+
+/// Transport the engine sends frames to instead of its injector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameSinkConfig {
+    /// One UDP datagram per frame to this endpoint
+    Udp(SocketAddr),
+    
+    /// Frames appended as records to this pcap file
+    File(PathBuf),
+}
+
+impl FrameSinkConfig {
+    /// Open the configured sink
+    pub fn open(&self) -> Result<Arc<dyn Injector>> {
+        Ok(match self {
+            Self::Udp(endpoint) => Arc::new(UdpFrameSink::connect(*endpoint)?),
+            Self::File(path) => Arc::new(FileFrameSink::open(path)?),
+        })
+    }
+}
+
+/// Sends each frame, radiotap header included, as a UDP datagram
+#[derive(Debug)]
+pub struct UdpFrameSink {
+    socket: UdpSocket,
+    endpoint: SocketAddr,
+}
+
+impl UdpFrameSink {
+    /// Bind an ephemeral local port and connect it to `endpoint`
+    pub fn connect(endpoint: SocketAddr) -> Result<Self> {
+        let local: SocketAddr = if endpoint.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(endpoint)?;
+        info!("Sending frames to UDP {}", endpoint);
+        
+        Ok(Self { socket, endpoint })
+    }
+    
+    /// Endpoint frames are sent to
+    pub fn endpoint(&self) -> SocketAddr {
+        self.endpoint
+    }
+}
+
+impl Injector for UdpFrameSink {
+    fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+        let sent = self
+            .socket
+            .send(frame)
+            .map_err(|e| DeauthError::InjectionError(format!("sending frame to {}: {}", self.endpoint, e)))?;
+        debug!("Sent {} bytes to {}", sent, self.endpoint);
+        Ok(sent)
+    }
+}
+
+/// Appends each frame, radiotap header included, as a record to a pcap file
+///
+/// The record headers carry each frame's length, so frames of any layout
+/// split apart again, and the file replays with `PacketCapture::from_file`.
+#[derive(Debug)]
+pub struct FileFrameSink {
+    file: Mutex<File>,
+    path: PathBuf,
+}
+
+impl FileFrameSink {
+    /// Open `path` for appending, creating it if needed
+    ///
+    /// A new or empty file gets a pcap header; an existing file must already
+    /// be a radiotap pcap file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new().create(true).read(true).append(true).open(&path)?;
+        
+        if file.metadata()?.len() == 0 {
+            pcap_file::write_header(&mut file, MAX_SNAP_LENGTH)?;
+        } else {
+            let mut header = [0u8; HEADER_LEN];
+            let is_pcap = file.read_exact(&mut header).is_ok() && pcap_file::is_radiotap_header(&header);
+            if !is_pcap {
+                return Err(DeauthError::ConfigError(format!(
+                    "{} is not a radiotap pcap file; refusing to append frames",
+                    path.display()
+                )));
+            }
+        }
+        info!("Appending frames to {}", path.display());
+        
+        Ok(Self {
+            file: Mutex::new(file),
+            path,
+        })
+    }
+    
+    /// File frames are appended to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Injector for FileFrameSink {
+    fn inject_frame(&self, frame: &[u8]) -> Result<usize> {
+        if u32::try_from(frame.len()).is_err() {
+            return Err(DeauthError::InjectionError(format!("{}-byte frame does not fit a pcap record", frame.len())));
+        }
+        
+        let mut record = Vec::with_capacity(RECORD_HEADER_LEN + frame.len());
+        pcap_file::write_record(&mut record, SystemTime::now(), frame, frame.len())?;
+        
+        // One write per record keeps records from concurrent workers whole
+        self.file
+            .lock()
+            .write_all(&record)
+            .map_err(|e| DeauthError::InjectionError(format!("writing frame to {}: {}", self.path.display(), e)))?;
+        Ok(frame.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::packet::{DeauthPacket, MacAddress};
+    use std::time::Duration;
+    
+    fn frame(reason_code: u16) -> Vec<u8> {
+        let client = MacAddress::new([0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB]);
+        let ap = MacAddress::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        DeauthPacket::new(client, ap, ap, reason_code).to_bytes().to_vec()
+    }
+    
+    #[test]
+    fn test_udp_sink_sends_frame_per_datagram() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let sink = FrameSinkConfig::Udp(receiver.local_addr().unwrap()).open().expect("Should connect");
+        
+        let (first, second) = (frame(7), frame(3));
+        assert_eq!(sink.inject_frame(&first).unwrap(), first.len());
+        assert_eq!(sink.inject_frame(&second).unwrap(), second.len());
+        
+        let mut buffer = [0u8; 512];
+        for expected in [&first, &second] {
+            let len = receiver.recv(&mut buffer).expect("Should receive datagram");
+            assert_eq!(&buffer[..len], expected.as_slice());
+        }
+    }
+    
+    #[test]
+    fn test_file_sink_appends_frames() {
+        use crate::network::PacketCapture;
+        
+        let path = std::env::temp_dir().join(format!("wifi-deauther-sink-{}.pcap", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        
+        // Frames of different lengths, so nothing but the records can split them
+        let (first, mut second) = (frame(7), frame(3));
+        second.extend_from_slice(&[0xDD; 9]);
+        {
+            let sink = FrameSinkConfig::File(path.clone()).open().expect("Should open file");
+            sink.inject_frame(&first).unwrap();
+        }
+        
+        // Reopening appends rather than truncating or repeating the header
+        let sink = FileFrameSink::open(&path).expect("Should reopen file");
+        assert_eq!(sink.inject_frame(&second).unwrap(), second.len());
+        drop(sink);
+        
+        let replay = PacketCapture::from_file(&path).expect("Should replay sink file");
+        for expected in [&first, &second] {
+            let captured = replay.capture_packet().unwrap().expect("Should read frame");
+            assert_eq!(&captured.data, expected);
+        }
+        assert!(replay.capture_packet().unwrap().is_none());
+        drop(replay);
+        
+        // Frames are never appended to something that is not a sink file
+        std::fs::write(&path, b"not a capture").unwrap();
+        assert!(matches!(FileFrameSink::open(&path), Err(DeauthError::ConfigError(_))));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
\ No newline at end of file
@@ -6,12 +6,15 @@
 pub mod linux;
 pub mod windows;
 pub mod macos;
+pub mod null;
 
 pub use linux::LinuxPlatform;
 pub use windows::WindowsPlatform;
 pub use macos::MacOSPlatform;
+pub use null::NullPlatform;
 
 use crate::Result;
+use std::path::Path;
 
 /// Platform trait for cross-platform operations
 pub trait Platform {
@@ -47,5 +50,226 @@ pub fn get_current_platform() -> Box<dyn Platform> {
     return Box::new(MacOSPlatform::new());
     
     #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
-    panic!("Unsupported platform");
+    return Box::new(NullPlatform::new());
+}
+
+/// Kernel wireless module status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KernelModuleStatus {
+    pub mac80211: bool,
+    pub cfg80211: bool,
+}
+
+impl KernelModuleStatus {
+    /// Check if the wireless stack is loaded
+    pub fn wireless_available(&self) -> bool {
+        self.mac80211 || self.cfg80211
+    }
+}
+
+/// What the current process may do on this platform
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrivilegeReport {
+    /// Frames can be injected
+    pub can_inject: bool,
+    
+    /// Frames can be captured, so scanning works
+    pub can_capture: bool,
+    
+    /// What is missing for the capabilities that are not available
+    pub missing: Vec<String>,
+}
+
+impl PrivilegeReport {
+    /// Report with every capability available
+    fn full() -> Self {
+        Self {
+            can_inject: true,
+            can_capture: true,
+            missing: Vec::new(),
+        }
+    }
+    
+    /// Report with no capability available for `reason`
+    fn none(reason: impl Into<String>) -> Self {
+        Self {
+            can_inject: false,
+            can_capture: false,
+            missing: vec![reason.into()],
+        }
+    }
+}
+
+/// Npcap packet library, preferred on Windows
+pub const NPCAP_LIBRARY: &str = "C:\\Windows\\System32\\Npcap.dll";
+
+/// Legacy WinPcap packet library
+pub const WINPCAP_LIBRARY: &str = "C:\\Windows\\System32\\wpcap.dll";
+
+/// Check what the current process may do, without exiting on a shortfall
+///
+/// Front ends decide from the report which modes to offer, e.g. scanning
+/// only when injection is unavailable.
+pub fn privileges() -> PrivilegeReport {
+    #[cfg(target_os = "linux")]
+    return linux_privileges(unsafe { libc::geteuid() });
+    
+    #[cfg(target_os = "windows")]
+    return windows_privileges(&[Path::new(NPCAP_LIBRARY), Path::new(WINPCAP_LIBRARY)]);
+    
+    #[cfg(target_os = "macos")]
+    return macos_privileges(Path::new("/dev"));
+    
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    return PrivilegeReport::none("Packet capture and injection are not supported on this platform");
+}
+
+/// Privileges on Linux for effective user ID `euid`
+///
+/// Injection needs root. Scanning stays available without it, as in scan-only
+/// mode; opening a capture device can still fail without `CAP_NET_RAW`.
+pub fn linux_privileges(euid: u32) -> PrivilegeReport {
+    if privileges_from_euid(euid) {
+        return PrivilegeReport::full();
+    }
+    
+    PrivilegeReport {
+        can_inject: false,
+        can_capture: true,
+        missing: vec![format!("Root privileges are required for packet injection (running as UID {})", euid)],
+    }
+}
+
+/// Privileges on Windows, given the packet libraries to look for
+///
+/// Capture and injection both go through Npcap or WinPcap; adapter access is
+/// verified when the device is opened.
+pub fn windows_privileges(libraries: &[&Path]) -> PrivilegeReport {
+    if libraries.iter().any(|library| library.exists()) {
+        PrivilegeReport::full()
+    } else {
+        PrivilegeReport::none("Npcap or WinPcap not found. Please install Npcap from https://npcap.com/")
+    }
+}
+
+/// Privileges on macOS, given the device directory holding the BPF devices
+///
+/// Capture and injection both go through BPF. An unreadable device directory
+/// is not held against the process; opening the device reports the error.
+pub fn macos_privileges(dev: &Path) -> PrivilegeReport {
+    let Ok(entries) = std::fs::read_dir(dev) else {
+        return PrivilegeReport::full();
+    };
+    
+    let has_bpf = entries
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.file_name().to_string_lossy().starts_with("bpf"));
+    if has_bpf {
+        PrivilegeReport::full()
+    } else {
+        PrivilegeReport::none("No BPF devices found. You may need to load the BPF kernel extension.")
+    }
+}
+
+/// Check if the current process may inject packets, as reported by `privileges`
+pub fn has_injection_privileges() -> bool {
+    privileges().can_inject
+}
+
+/// Check if an effective user ID grants injection privileges
+pub fn privileges_from_euid(euid: u32) -> bool {
+    euid == 0
+}
+
+/// Check which wireless kernel modules are loaded
+pub fn check_kernel_modules() -> Result<KernelModuleStatus> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::process::Command;
+        
+        let output = Command::new("lsmod")
+            .output()
+            .map_err(|e| crate::DeauthError::PlatformError(format!("Failed to check kernel modules: {}", e)))?;
+        
+        return Ok(parse_lsmod(&String::from_utf8_lossy(&output.stdout)));
+    }
+    
+    #[cfg(not(target_os = "linux"))]
+    Err(crate::DeauthError::PlatformError(
+        "Kernel module check is only available on Linux".to_string()
+    ))
+}
+
+/// Parse `lsmod` output into a module status
+pub fn parse_lsmod(output: &str) -> KernelModuleStatus {
+    let mut status = KernelModuleStatus::default();
+    
+    // First line is the "Module Size Used by" header
+    for line in output.lines().skip(1) {
+        match line.split_whitespace().next() {
+            Some("mac80211") => status.mac80211 = true,
+            Some("cfg80211") => status.cfg80211 = true,
+            _ => {}
+        }
+    }
+    
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_null_platform() {
+        let platform = NullPlatform::new();
+        let capabilities = platform.capabilities();
+        
+        assert!(!platform.is_supported());
+        assert!(!capabilities.raw_socket_support);
+        assert!(!capabilities.monitor_mode_support);
+        assert!(!capabilities.bpf_support);
+        assert!(!capabilities.netlink_support);
+        assert!(!capabilities.winpcap_support);
+    }
+    
+    #[test]
+    fn test_privileges_from_euid() {
+        assert!(privileges_from_euid(0));
+        assert!(!privileges_from_euid(1000));
+    }
+    
+    #[test]
+    fn test_linux_privileges() {
+        let root = linux_privileges(0);
+        assert!(root.can_inject && root.can_capture);
+        assert!(root.missing.is_empty());
+        
+        // Unprivileged users keep scanning but lose injection
+        let user = linux_privileges(1000);
+        assert!(!user.can_inject);
+        assert!(user.can_capture);
+        assert_eq!(user.missing.len(), 1);
+        assert!(user.missing[0].contains("1000"));
+    }
+    
+    #[test]
+    fn test_parse_lsmod() {
+        let loaded = "Module                  Size  Used by\n\
+                      iwlmvm                454656  0\n\
+                      mac80211             1241088  1 iwlmvm\n\
+                      cfg80211             1101824  3 iwlmvm,iwlwifi,mac80211\n";
+        let status = parse_lsmod(loaded);
+        assert!(status.mac80211);
+        assert!(status.cfg80211);
+        assert!(status.wireless_available());
+        
+        // Modules that merely depend on mac80211 must not count
+        let missing = "Module                  Size  Used by\n\
+                       snd_hda_intel          57344  0\n\
+                       nvme                   49152  2 mac80211_hwsim\n";
+        let status = parse_lsmod(missing);
+        assert_eq!(status, KernelModuleStatus::default());
+        assert!(!status.wireless_available());
+    }
 }
\ No newline at end of file
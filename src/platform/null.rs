@@ -0,0 +1,31 @@
+//! Fallback platform for unsupported operating systems
+
+use super::{Platform, PlatformCapabilities};
+
+pub struct NullPlatform;
+
+impl NullPlatform {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Platform for NullPlatform {
+    fn name(&self) -> &str {
+        "Unsupported"
+    }
+    
+    fn is_supported(&self) -> bool {
+        false
+    }
+    
+    fn capabilities(&self) -> PlatformCapabilities {
+        PlatformCapabilities {
+            raw_socket_support: false,
+            monitor_mode_support: false,
+            bpf_support: false,
+            netlink_support: false,
+            winpcap_support: false,
+        }
+    }
+}
\ No newline at end of file